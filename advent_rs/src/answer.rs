@@ -0,0 +1,256 @@
+use num::BigInt;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Screen {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Screen {
+    pub fn new(width: usize, height: usize) -> Self {
+        Screen {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    pub fn from_points<I>(width: usize, height: usize, points: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        let mut screen = Screen::new(width, height);
+        points.into_iter().for_each(|(x, y)| screen.set(x, y, true));
+        screen
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    // a pixel past the right edge would otherwise wrap onto the next row
+    fn index(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "pixel ({}, {}) is outside a {}x{} screen",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        y * self.width + x
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.pixels[self.index(x, y)]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, on: bool) {
+        let i = self.index(x, y);
+        self.pixels[i] = on;
+    }
+
+    pub fn lit(&self) -> usize {
+        self.pixels.iter().filter(|p| **p).count()
+    }
+}
+
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.pixels.chunks(self.width.max(1)).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for p in row {
+                write!(f, "{}", if *p { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Screen {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .trim_matches('\n')
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .map(|c| match c {
+                        '#' | '█' => Ok(true),
+                        '.' | ' ' => Ok(false),
+                        _ => Err(format!("invalid pixel: {c:?}")),
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let height = rows.len();
+        let mut screen = Screen::new(width, height);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, p) in row.iter().enumerate() {
+                screen.set(x, y, *p);
+            }
+        }
+        Ok(screen)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(BigInt),
+    Text(String),
+    Screen(Screen),
+}
+
+impl Answer {
+    pub fn matches(&self, expected: &str) -> bool {
+        match self {
+            Answer::Int(n) => expected.trim().parse::<BigInt>().is_ok_and(|e| *n == e),
+            Answer::Text(s) => s.trim() == expected.trim(),
+            Answer::Screen(screen) => expected.parse::<Screen>().is_ok_and(|e| *screen == e),
+        }
+    }
+}
+
+// numbers become Int, multi-line `#`/`.` drawings become Screen, anything else Text
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(n) = s.trim().parse::<BigInt>() {
+            return Ok(Answer::Int(n));
+        }
+        if s.trim_matches('\n').contains('\n') {
+            if let Ok(screen) = s.parse::<Screen>() {
+                return Ok(Answer::Screen(screen));
+            }
+        }
+        Ok(Answer::Text(s.to_string()))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Screen(screen) => write!(f, "{screen}"),
+        }
+    }
+}
+
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        self.matches(other)
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self.matches(other)
+    }
+}
+
+macro_rules! answer_from_int {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Int(BigInt::from(n))
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Answer::Int(n)
+    }
+}
+
+// strings are classified the same way `parse` does
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::from(s.as_str())
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        match s.parse() {
+            Ok(answer) => answer,
+            Err(e) => match e {},
+        }
+    }
+}
+
+impl From<Screen> for Answer {
+    fn from(screen: Screen) -> Self {
+        Answer::Screen(screen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_int() {
+        assert_eq!(Answer::from(42u8), "42");
+        assert_eq!(Answer::from(-7i64), " -7\n");
+        assert_eq!(
+            Answer::from(u128::MAX),
+            "340282366920938463463374607431768211455"
+        );
+        assert_eq!(Answer::from(i128::MIN).to_string(), i128::MIN.to_string());
+        assert_eq!(Answer::from(3usize), Answer::from(3i32));
+        assert_ne!(Answer::from(3u32), "3.0");
+        assert_eq!(Answer::from(3u32), Answer::from("3"));
+        assert_eq!(
+            Answer::from("3".to_string()),
+            "3".parse::<Answer>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(Answer::from("CMZ"), Answer::Text("CMZ".to_string()));
+        assert_eq!(Answer::from("CMZ"), "CMZ");
+        assert_eq!(Answer::from("CMZ".to_string()).to_string(), "CMZ");
+        assert_ne!(Answer::from("CMZ"), "MCD");
+    }
+
+    #[test]
+    fn test_screen() {
+        let screen = Screen::from_points(4, 2, vec![(0, 0), (3, 0), (1, 1)]);
+        assert_eq!(screen.lit(), 3);
+        assert_eq!(screen.to_string(), "#..#\n.#..");
+
+        let answer = Answer::from(screen.clone());
+        assert_eq!(answer, "#..#\n.#..");
+        assert_eq!(answer, "\n█  █\n █  \n");
+        assert_ne!(answer, "#..#\n#...");
+        assert_eq!("#..#\n.#".parse::<Screen>(), Ok(screen));
+        assert!("#x".parse::<Screen>().is_err());
+
+        assert_eq!("#..#\n.#..".parse::<Answer>(), Ok(answer));
+        assert_eq!("-12".parse::<Answer>(), Ok(Answer::from(-12)));
+        assert_eq!("#..#".parse::<Answer>(), Ok(Answer::from("#..#")));
+    }
+
+    #[test]
+    #[should_panic(expected = "pixel (4, 0) is outside a 4x2 screen")]
+    fn test_screen_bounds() {
+        Screen::from_points(4, 2, vec![(4, 0)]);
+    }
+}
@@ -0,0 +1,91 @@
+use advent_rs::dashboard::Dashboard;
+use advent_rs::{debugger, read_file, runner, trace};
+use std::str::FromStr;
+
+const USAGE: &str =
+    "usage: aoc [--log FILTER] (serve [--port PORT] | run YEAR [DAY] | debug YEAR DAY [INPUT])";
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    std::process::exit(2);
+}
+
+fn number<T: FromStr>(arg: &str) -> T {
+    arg.parse().unwrap_or_else(|_| usage())
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let mut command = None;
+    let mut port = 8023;
+    let mut operands = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--log" => {
+                let spec = args.next().unwrap_or_else(|| usage());
+                if let Err(e) = trace::set_filter(&spec) {
+                    eprintln!("{e}");
+                    std::process::exit(2);
+                }
+            }
+            "--port" => {
+                port = args
+                    .next()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or_else(|| usage())
+            }
+            "serve" | "run" | "debug" if command.is_none() => command = Some(arg),
+            _ => operands.push(arg),
+        }
+    }
+
+    match (command.as_deref(), &operands[..]) {
+        (Some("serve"), []) => {
+            if let Err(e) = Dashboard::default().serve(port) {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+        (Some("run"), [year, rest @ ..]) if rest.len() <= 1 => {
+            let year = number(year);
+            let records = runner::run(year, rest.first().map(|d| number(d)));
+            if records.is_empty() {
+                eprintln!("no solvers registered for {year}");
+                std::process::exit(1);
+            }
+            for r in &records {
+                let status = match r.passed() {
+                    Some(true) => "ok",
+                    Some(false) => "FAIL",
+                    None => "?",
+                };
+                let answer = if r.answer.contains('\n') {
+                    format!("\n{}", r.answer)
+                } else {
+                    r.answer.clone()
+                };
+                println!(
+                    "{} day {:2} part {}  {:>4}  {:>12?}  {answer}",
+                    r.year, r.day, r.part, status, r.elapsed
+                );
+            }
+            if records.iter().any(|r| r.passed() == Some(false)) {
+                std::process::exit(1);
+            }
+        }
+        (Some("debug"), [year, day, rest @ ..]) if rest.len() <= 1 => {
+            let (year, day) = (number(year), number(day));
+            let Some(target) = debugger::targets()
+                .into_iter()
+                .find(|t| t.year == year && t.day == day)
+            else {
+                eprintln!("no simulation to debug for {year} day {day}");
+                std::process::exit(1);
+            };
+            let input = rest.first().map(|file| read_file(file));
+            (target.open)(input.as_deref());
+        }
+        _ => usage(),
+    }
+}
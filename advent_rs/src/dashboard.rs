@@ -0,0 +1,461 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::runner;
+
+pub const RESULTS_ENV_VAR: &str = "AOC_RESULTS";
+pub const VISUALS_ENV_VAR: &str = "AOC_VISUALS";
+
+const RESULTS_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/target/aoc/results.tsv");
+const VISUALS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/target/aoc/visuals");
+
+const IMAGE_TYPES: [(&str, &str); 4] = [
+    ("svg", "image/svg+xml"),
+    ("png", "image/png"),
+    ("gif", "image/gif"),
+    ("jpg", "image/jpeg"),
+];
+
+pub fn results_file() -> PathBuf {
+    std::env::var(RESULTS_ENV_VAR).map_or(PathBuf::from(RESULTS_FILE), PathBuf::from)
+}
+
+pub fn visuals_dir() -> PathBuf {
+    std::env::var(VISUALS_ENV_VAR).map_or(PathBuf::from(VISUALS_DIR), PathBuf::from)
+}
+
+// off by default so test runs on sample inputs leave the visuals alone
+static SAVE_VISUALS: AtomicBool = AtomicBool::new(false);
+
+pub fn save_visuals(on: bool) {
+    SAVE_VISUALS.store(on, Ordering::Relaxed);
+}
+
+// stores an image of a day under the visuals directory, where the day page
+// picks it up
+pub fn save_visual(year: u16, day: u8, name: &str, contents: &[u8]) {
+    if !SAVE_VISUALS.load(Ordering::Relaxed) {
+        return;
+    }
+    let dir = visuals_dir().join(year.to_string()).join(format!("d{day}"));
+    fs::create_dir_all(&dir).expect("cannot create visuals directory");
+    fs::write(dir.join(name), contents).expect("write error");
+}
+
+// year -> implemented days, the ones with a registered solver
+pub fn calendar() -> BTreeMap<u16, BTreeSet<u8>> {
+    let mut calendar = BTreeMap::<u16, BTreeSet<u8>>::new();
+    for s in runner::solvers() {
+        calendar.entry(s.year).or_default().insert(s.day);
+    }
+    calendar
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub expected: Option<String>,
+    pub elapsed: Duration,
+}
+
+impl Record {
+    pub fn passed(&self) -> Option<bool> {
+        let answer = self.answer.parse::<Answer>().unwrap();
+        self.expected.as_ref().map(|e| answer.matches(e))
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.year,
+            self.day,
+            self.part,
+            escape(&self.answer),
+            self.expected.as_deref().map_or("-".to_string(), escape),
+            self.elapsed.as_micros()
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        if fields.len() != 6 {
+            return None;
+        }
+        Some(Record {
+            year: fields[0].parse().ok()?,
+            day: fields[1].parse().ok()?,
+            part: fields[2].parse().ok()?,
+            answer: unescape(fields[3]),
+            expected: (fields[4] != "-").then(|| unescape(fields[4])),
+            elapsed: Duration::from_micros(fields[5].parse().ok()?),
+        })
+    }
+}
+
+// a lone `-` marks a missing expected answer, so an answer of `-` is escaped
+fn escape(s: &str) -> String {
+    if s == "-" {
+        return "\\-".to_string();
+    }
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some(c) => result.push(c),
+            None => {}
+        }
+    }
+    result
+}
+
+pub fn record(path: &Path, record: &Record) {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).expect("cannot create results directory");
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .expect("cannot open results file");
+    writeln!(file, "{}", record.to_line()).expect("write error");
+}
+
+pub fn measure<T: Into<Answer>>(
+    year: u16,
+    day: u8,
+    part: u8,
+    expected: Option<&str>,
+    solve: impl FnOnce() -> T,
+) -> Record {
+    let start = Instant::now();
+    let answer: Answer = solve().into();
+    let elapsed = start.elapsed();
+    let rec = Record {
+        year,
+        day,
+        part,
+        answer: answer.to_string(),
+        expected: expected.map(str::to_string),
+        elapsed,
+    };
+    record(&results_file(), &rec);
+    rec
+}
+
+// the last record of every (year, day, part)
+pub fn latest_results(path: &Path) -> BTreeMap<(u16, u8, u8), Record> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(Record::from_line)
+        .map(|r| ((r.year, r.day, r.part), r))
+        .collect()
+}
+
+fn visuals(dir: &Path, year: u16, day: u8) -> Vec<String> {
+    let mut files = fs::read_dir(dir.join(year.to_string()).join(format!("d{day}")))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| content_type(name).is_some())
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+fn content_type(name: &str) -> Option<&'static str> {
+    let ext = Path::new(name).extension()?.to_str()?;
+    IMAGE_TYPES
+        .iter()
+        .find(|(e, _)| e.eq_ignore_ascii_case(ext))
+        .map(|(_, t)| *t)
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const STYLE: &str = "body{font-family:monospace;background:#0f0f23;color:#ccc;margin:2em}\
+a{color:#090;text-decoration:none}a:hover{color:#9f9}\
+table{border-collapse:collapse}td,th{padding:.2em .6em;text-align:left;vertical-align:top}\
+.day{display:inline-block;width:2.5em}.none{color:#333}\
+.norun{color:#666}.pass{color:#ff0}.fail{color:#f55}.unknown{color:#99f}pre{margin:0}";
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{}</title>\
+         <style>{}</style></head><body><h1>{}</h1>{}</body></html>",
+        html_escape(title),
+        STYLE,
+        html_escape(title),
+        body
+    )
+}
+
+fn status(records: &[&Record]) -> &'static str {
+    if records.is_empty() {
+        "norun"
+    } else if records.iter().any(|r| r.passed() == Some(false)) {
+        "fail"
+    } else if records.iter().all(|r| r.passed() == Some(true)) {
+        "pass"
+    } else {
+        "unknown"
+    }
+}
+
+pub struct Dashboard {
+    pub results: PathBuf,
+    pub visuals: PathBuf,
+}
+
+impl Default for Dashboard {
+    fn default() -> Self {
+        Dashboard {
+            results: results_file(),
+            visuals: visuals_dir(),
+        }
+    }
+}
+
+impl Dashboard {
+    fn index(&self) -> String {
+        let results = latest_results(&self.results);
+        let mut body = String::new();
+        for (year, days) in calendar().iter().rev() {
+            body += &format!("<h2>{year}</h2><p>");
+            for day in 1..=25 {
+                if !days.contains(&day) {
+                    body += &format!("<span class=\"day none\">{day:2}</span>");
+                    continue;
+                }
+                let records = results
+                    .range((*year, day, 0)..=(*year, day, u8::MAX))
+                    .map(|(_, r)| r)
+                    .collect::<Vec<_>>();
+                body += &format!(
+                    "<a class=\"day {}\" href=\"/{year}/{day}\">{day:2}</a>",
+                    status(&records)
+                );
+            }
+            body += "</p>";
+        }
+        page("Advent of Code", &body)
+    }
+
+    fn day(&self, year: u16, day: u8) -> Option<String> {
+        if !calendar().get(&year)?.contains(&day) {
+            return None;
+        }
+        let results = latest_results(&self.results);
+
+        let mut body = String::from("<p><a href=\"/\">&larr; calendar</a></p><table>");
+        body += "<tr><th>part</th><th>answer</th><th>expected</th><th>time</th></tr>";
+        for (_, r) in results.range((year, day, 0)..=(year, day, u8::MAX)) {
+            body += &format!(
+                "<tr class=\"{}\"><td>{}</td><td><pre>{}</pre></td><td><pre>{}</pre></td><td>{:?}</td></tr>",
+                status(&[r]),
+                r.part,
+                html_escape(&r.answer),
+                html_escape(r.expected.as_deref().unwrap_or("?")),
+                r.elapsed
+            );
+        }
+        body += "</table>";
+
+        for name in visuals(&self.visuals, year, day) {
+            body += &format!(
+                "<p><img src=\"/visuals/{year}/d{day}/{}\" alt=\"{}\"></p>",
+                html_escape(&name),
+                html_escape(&name)
+            );
+        }
+        Some(page(&format!("{year} day {day}"), &body))
+    }
+
+    fn visual(&self, rest: &str) -> Option<(&'static str, Vec<u8>)> {
+        let path = Path::new(rest);
+        if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+            return None;
+        }
+        let content_type = content_type(rest)?;
+        let bytes = fs::read(self.visuals.join(path)).ok()?;
+        Some((content_type, bytes))
+    }
+
+    // (status, content type, body)
+    pub fn handle(&self, path: &str) -> (u16, &'static str, Vec<u8>) {
+        const HTML: &str = "text/html; charset=utf-8";
+        let path = path.split('?').next().unwrap_or("/");
+
+        if path == "/" {
+            return (200, HTML, self.index().into_bytes());
+        }
+        if let Some(rest) = path.strip_prefix("/visuals/") {
+            if let Some((content_type, bytes)) = self.visual(rest) {
+                return (200, content_type, bytes);
+            }
+        }
+        let parts = path.trim_matches('/').split('/').collect::<Vec<_>>();
+        if let [year, day] = parts[..] {
+            if let (Ok(year), Ok(day)) = (year.parse(), day.parse()) {
+                if let Some(html) = self.day(year, day) {
+                    return (200, HTML, html.into_bytes());
+                }
+            }
+        }
+        (404, HTML, page("Not found", "").into_bytes())
+    }
+
+    fn respond(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let mut request_line = String::new();
+        BufReader::new(&stream).read_line(&mut request_line)?;
+
+        let mut words = request_line.split_whitespace();
+        let (status, content_type, body) = match (words.next(), words.next()) {
+            (Some("GET"), Some(path)) => self.handle(path),
+            _ => (405, "text/plain", b"method not allowed".to_vec()),
+        };
+        let reason = match status {
+            200 => "OK",
+            404 => "Not Found",
+            _ => "Method Not Allowed",
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {status} {reason}\r\nContent-Type: {content_type}\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        )?;
+        stream.write_all(&body)
+    }
+
+    pub fn serve(&self, port: u16) -> std::io::Result<()> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        eprintln!("serving on http://{}", listener.local_addr()?);
+        for stream in listener.incoming() {
+            if let Err(e) = stream.and_then(|s| self.respond(s)) {
+                eprintln!("request failed: {e}");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-dashboard-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_calendar() {
+        let calendar = calendar();
+        assert_eq!(calendar[&2015].first(), Some(&7));
+        assert_eq!(calendar[&2022], (1..=17).collect());
+        assert!(!calendar[&2021].contains(&22));
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let dir = temp_dir("records");
+        let path = dir.join("results.tsv");
+        let mut rec = Record {
+            year: 2022,
+            day: 10,
+            part: 2,
+            answer: "-".to_string(),
+            expected: None,
+            elapsed: Duration::from_micros(1234),
+        };
+        record(&path, &rec);
+        assert_eq!(latest_results(&path)[&(2022, 10, 2)], rec);
+
+        rec.answer = "#..#\n-\\t\tx".to_string();
+        rec.expected = Some(rec.answer.clone());
+        record(&path, &rec);
+
+        let results = latest_results(&path);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[&(2022, 10, 2)], rec);
+        assert_eq!(rec.passed(), Some(true));
+        assert_eq!(escape("1-2"), "1-2");
+    }
+
+    #[test]
+    fn test_handle() {
+        let dir = temp_dir("handle");
+        let results = dir.join("results.tsv");
+        let visuals = dir.join("visuals");
+        fs::create_dir_all(visuals.join("2022/d12")).unwrap();
+        fs::write(visuals.join("2022/d12/map.svg"), "<svg/>").unwrap();
+        fs::write(dir.join("secret.svg"), "<svg/>").unwrap();
+
+        let answer = |part, answer: &str, expected: &str| Record {
+            year: 2022,
+            day: 12,
+            part,
+            answer: answer.to_string(),
+            expected: Some(expected.to_string()),
+            elapsed: Duration::from_millis(3),
+        };
+        record(&results, &answer(1, "339", "339"));
+        record(&results, &answer(2, "331", "332"));
+
+        let dashboard = Dashboard { results, visuals };
+
+        let (status, _, body) = dashboard.handle("/");
+        let body = String::from_utf8(body).unwrap();
+        assert_eq!(status, 200);
+        assert!(body.contains("<a class=\"day fail\" href=\"/2022/12\">12</a>"));
+        assert!(body.contains("<a class=\"day norun\" href=\"/2022/11\">11</a>"));
+        assert!(body.contains("<span class=\"day none\">18</span>"));
+
+        let (status, _, body) = dashboard.handle("/2022/12");
+        let body = String::from_utf8(body).unwrap();
+        assert_eq!(status, 200);
+        assert!(body.contains("<tr class=\"pass\"><td>1</td><td><pre>339</pre>"));
+        assert!(body.contains("<tr class=\"fail\"><td>2</td><td><pre>331</pre>"));
+        assert!(body.contains("src=\"/visuals/2022/d12/map.svg\""));
+
+        let (status, content_type, body) = dashboard.handle("/visuals/2022/d12/map.svg");
+        assert_eq!(
+            (status, content_type, &body[..]),
+            (200, "image/svg+xml", &b"<svg/>"[..])
+        );
+
+        assert_eq!(dashboard.handle("/visuals/../secret.svg").0, 404);
+        assert_eq!(dashboard.handle("/2021/22").0, 404);
+        assert_eq!(dashboard.handle("/nothing").0, 404);
+    }
+}
@@ -0,0 +1,453 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::{BufRead, Write};
+use std::process::{Command as Process, Stdio};
+
+pub trait Simulation {
+    type State: Clone;
+    type Key: Hash + Eq;
+
+    fn state(&self) -> Self::State;
+    fn restore(&mut self, state: Self::State);
+    // returns false once the simulation has nothing left to do
+    fn step(&mut self) -> bool;
+    fn render(&self) -> String;
+    fn key(&self) -> Self::Key;
+}
+
+pub struct Debugger<S: Simulation> {
+    sim: S,
+    steps: usize,
+    finished: bool,
+    interval: usize,
+    snapshots: Vec<(usize, S::State)>,
+    seen: HashMap<S::Key, usize>,
+    cycle: Option<(usize, usize)>,
+}
+
+impl<S: Simulation> Debugger<S> {
+    pub fn new(sim: S) -> Self {
+        Debugger::with_interval(sim, 100)
+    }
+
+    pub fn with_interval(sim: S, interval: usize) -> Self {
+        let snapshots = vec![(0, sim.state())];
+        let mut seen = HashMap::new();
+        seen.insert(sim.key(), 0);
+        Debugger {
+            sim,
+            steps: 0,
+            finished: false,
+            interval: interval.max(1),
+            snapshots,
+            seen,
+            cycle: None,
+        }
+    }
+
+    pub fn sim(&self) -> &S {
+        &self.sim
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    // (first step of the cycle, cycle length)
+    pub fn cycle(&self) -> Option<(usize, usize)> {
+        self.cycle
+    }
+
+    pub fn step(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+        if !self.sim.step() {
+            self.finished = true;
+            return false;
+        }
+        self.steps += 1;
+
+        if self.steps.is_multiple_of(self.interval) {
+            self.snapshots.push((self.steps, self.sim.state()));
+        }
+
+        let key = self.sim.key();
+        if let Some(&first) = self.seen.get(&key) {
+            if self.cycle.is_none() {
+                self.cycle = Some((first, self.steps - first));
+            }
+        } else {
+            self.seen.insert(key, self.steps);
+        }
+        true
+    }
+
+    pub fn run(&mut self, count: usize) -> usize {
+        (0..count).take_while(|_| self.step()).count()
+    }
+
+    pub fn run_until<F: Fn(&S) -> bool>(&mut self, cond: F) -> bool {
+        while !cond(&self.sim) {
+            if !self.step() {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn goto(&mut self, target: usize) -> bool {
+        if target < self.steps {
+            self.rewind_to(target);
+        }
+        self.run(target - self.steps);
+        self.steps == target
+    }
+
+    pub fn back(&mut self, count: usize) {
+        self.rewind_to(self.steps.saturating_sub(count));
+    }
+
+    fn rewind_to(&mut self, target: usize) {
+        let at = self
+            .snapshots
+            .iter()
+            .rposition(|(steps, _)| *steps <= target)
+            .unwrap();
+        self.snapshots.truncate(at + 1);
+        let (steps, state) = self.snapshots[at].clone();
+
+        self.sim.restore(state);
+        self.steps = steps;
+        self.finished = false;
+        self.seen.retain(|_, s| *s <= steps);
+        if matches!(self.cycle, Some((first, len)) if first + len > steps) {
+            self.cycle = None;
+        }
+        self.run(target - steps);
+    }
+
+    // stops early the first time a repeated state shows up
+    pub fn finish(&mut self, limit: usize) -> bool {
+        let known = self.cycle.is_some();
+        let end = self.steps.saturating_add(limit);
+        while self.steps < end && self.step() {
+            if !known && self.cycle.is_some() {
+                break;
+            }
+        }
+        self.finished
+    }
+
+    pub fn find_cycle(&mut self, limit: usize) -> Option<(usize, usize)> {
+        while self.cycle.is_none() && self.steps < limit && self.step() {}
+        self.cycle
+    }
+
+    pub fn jump_to_cycle(&mut self, limit: usize) -> Option<(usize, usize)> {
+        let cycle = self.find_cycle(limit)?;
+        self.goto(cycle.0);
+        Some(cycle)
+    }
+
+    pub fn status(&self) -> String {
+        let mut status = format!("step {}", self.steps);
+        if self.finished {
+            status += " (finished)";
+        }
+        if let Some((first, len)) = self.cycle {
+            status += &format!(" cycle: {len} steps from {first}");
+        }
+        status
+    }
+
+    fn draw<W: Write>(&self, output: &mut W, keys: &Keys, message: Option<&str>) {
+        write!(
+            output,
+            "\x1b[2J\x1b[H{}\n{}",
+            self.sim.render(),
+            self.status()
+        )
+        .unwrap();
+        if let Some(count) = keys.count {
+            write!(output, " [{count}]").unwrap();
+        }
+        write!(output, "\n{HELP}\n").unwrap();
+        if let Some(message) = message {
+            writeln!(output, "{message}").unwrap();
+        }
+        output.flush().unwrap();
+    }
+
+    // redraws after every key, with the last error below the board so the
+    // next clear screen doesn't wipe it before it can be read
+    pub fn interact<R: BufRead, W: Write>(&mut self, input: R, mut output: W) {
+        let mut keys = Keys::default();
+        let mut message = None;
+        self.draw(&mut output, &keys, None);
+        for byte in input.bytes() {
+            let Ok(byte) = byte else {
+                break;
+            };
+            match keys.press(byte as char) {
+                Ok(Some(Command::Quit)) => break,
+                Ok(Some(command)) => {
+                    self.execute(command);
+                    message = None;
+                }
+                Ok(None) => {}
+                Err(e) => message = Some(e),
+            }
+            self.draw(&mut output, &keys, message.as_deref());
+        }
+    }
+
+    pub fn run_terminal(&mut self) {
+        let _mode = KeyMode::enable();
+        let stdin = std::io::stdin();
+        self.interact(stdin.lock(), std::io::stdout());
+    }
+
+    pub fn execute(&mut self, command: Command) {
+        match command {
+            Command::Step(n) => {
+                self.run(n);
+            }
+            Command::Back(n) => self.back(n),
+            Command::Goto(n) => {
+                self.goto(n);
+            }
+            Command::Finish(limit) => {
+                self.finish(limit);
+            }
+            Command::Cycle(limit) => {
+                self.jump_to_cycle(limit);
+            }
+            Command::Quit => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    Step(usize),
+    Back(usize),
+    Goto(usize),
+    Finish(usize),
+    Cycle(usize),
+    Quit,
+}
+
+const HELP: &str = "[n]s/space step  [n]b back  ng goto  [n]f finish  [n]c cycle  q quit";
+
+// single keys, each optionally preceded by a count: `25s` steps 25 times,
+// `120g` goes to step 120
+#[derive(Debug, Default)]
+pub struct Keys {
+    count: Option<usize>,
+}
+
+impl Keys {
+    pub fn press(&mut self, key: char) -> Result<Option<Command>, String> {
+        if let Some(d) = key.to_digit(10) {
+            let count = self.count.unwrap_or(0);
+            self.count = Some(count.saturating_mul(10).saturating_add(d as usize));
+            return Ok(None);
+        }
+        let count = self.count.take();
+        match key {
+            's' | 'n' | ' ' => Ok(Some(Command::Step(count.unwrap_or(1)))),
+            'b' => Ok(Some(Command::Back(count.unwrap_or(1)))),
+            'g' => count
+                .map(|n| Some(Command::Goto(n)))
+                .ok_or("g needs a step number first".to_string()),
+            'f' => Ok(Some(Command::Finish(count.unwrap_or(usize::MAX)))),
+            'c' => Ok(Some(Command::Cycle(count.unwrap_or(usize::MAX)))),
+            'q' => Ok(Some(Command::Quit)),
+            // escape drops a pending count, enter does nothing
+            '\x1b' | '\n' | '\r' => Ok(None),
+            _ => Err(format!("unknown key: {key:?}")),
+        }
+    }
+}
+
+// the terminal hands over keys as they are pressed, without echo, until
+// dropped. Anything that isn't a terminal stays line buffered
+struct KeyMode {
+    saved: Option<String>,
+}
+
+impl KeyMode {
+    fn enable() -> Self {
+        let saved = stty(&["-g"]).filter(|_| stty(&["-icanon", "-echo", "min", "1"]).is_some());
+        KeyMode { saved }
+    }
+}
+
+impl Drop for KeyMode {
+    fn drop(&mut self) {
+        if let Some(saved) = &self.saved {
+            stty(&[saved.trim()]);
+        }
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Process::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// a simulation `aoc debug` can open, on the day's own input unless it is
+// given another one
+#[derive(Clone, Copy)]
+pub struct Target {
+    pub year: u16,
+    pub day: u8,
+    pub open: fn(Option<&str>),
+}
+
+impl Target {
+    pub fn new(year: u16, day: u8, open: fn(Option<&str>)) -> Self {
+        Target { year, day, open }
+    }
+}
+
+pub fn targets() -> Vec<Target> {
+    [
+        crate::y2022::debug_targets(),
+        crate::y2023::debug_targets(),
+        crate::y2024::debug_targets(),
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // x -> x * 3 % 7 starting at 1 visits 1 3 2 6 4 5 1 ...
+    struct Mul {
+        x: u32,
+        limit: Option<u32>,
+    }
+
+    impl Simulation for Mul {
+        type State = u32;
+        type Key = u32;
+
+        fn state(&self) -> u32 {
+            self.x
+        }
+
+        fn restore(&mut self, state: u32) {
+            self.x = state;
+        }
+
+        fn step(&mut self) -> bool {
+            if Some(self.x) == self.limit {
+                return false;
+            }
+            self.x = self.x * 3 % 7;
+            true
+        }
+
+        fn render(&self) -> String {
+            format!("x = {}", self.x)
+        }
+
+        fn key(&self) -> u32 {
+            self.x
+        }
+    }
+
+    #[test]
+    fn test_step_and_rewind() {
+        let mut dbg = Debugger::with_interval(Mul { x: 1, limit: None }, 4);
+        assert_eq!(dbg.run(5), 5);
+        assert_eq!(dbg.sim().x, 5);
+
+        dbg.back(2);
+        assert_eq!((dbg.steps(), dbg.sim().x), (3, 6));
+
+        assert!(dbg.goto(9));
+        assert_eq!(dbg.sim().x, 6);
+        dbg.goto(1);
+        assert_eq!(dbg.sim().x, 3);
+        assert_eq!(dbg.cycle(), None);
+    }
+
+    #[test]
+    fn test_run_until_finished() {
+        let mut dbg = Debugger::new(Mul {
+            x: 1,
+            limit: Some(4),
+        });
+        assert!(dbg.run_until(|sim| sim.x == 6));
+        assert_eq!(dbg.steps(), 3);
+        assert!(!dbg.run_until(|sim| sim.x == 0));
+        assert!(dbg.is_finished());
+        assert_eq!(dbg.steps(), 4);
+        dbg.back(1);
+        assert!(!dbg.is_finished());
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut dbg = Debugger::new(Mul { x: 3, limit: None });
+        assert!(!dbg.finish(usize::MAX));
+        assert_eq!(dbg.steps(), 6);
+        assert_eq!(dbg.jump_to_cycle(100), Some((0, 6)));
+        assert_eq!(dbg.steps(), 0);
+        dbg.execute(Command::Goto(8));
+        assert_eq!(dbg.sim().x, 6);
+    }
+
+    #[test]
+    fn test_keys() {
+        let mut keys = Keys::default();
+        let mut press = |text: &str| text.chars().map(|c| keys.press(c)).collect::<Vec<_>>();
+        assert_eq!(press("s"), [Ok(Some(Command::Step(1)))]);
+        assert_eq!(
+            press("12b"),
+            [Ok(None), Ok(None), Ok(Some(Command::Back(12)))]
+        );
+        assert_eq!(
+            press("3\x1bf"),
+            [Ok(None), Ok(None), Ok(Some(Command::Finish(usize::MAX)))]
+        );
+        assert_eq!(
+            press("g")[0],
+            Err("g needs a step number first".to_string())
+        );
+        assert_eq!(press("7x")[1], Err("unknown key: 'x'".to_string()));
+        assert_eq!(press("s"), [Ok(Some(Command::Step(1)))]);
+    }
+
+    #[test]
+    fn test_interact() {
+        let mut dbg = Debugger::new(Mul { x: 1, limit: None });
+        let mut out = vec![];
+        dbg.interact("\n3sbx2q s".as_bytes(), &mut out);
+        assert_eq!(dbg.steps(), 2);
+
+        let out = String::from_utf8(out).unwrap();
+        let frames = out.split("\x1b[2J").skip(1).collect::<Vec<_>>();
+        assert_eq!(frames.len(), 7);
+        assert!(frames[3].contains("x = 6\nstep 3\n"));
+        // the error stays below the board until the next command runs
+        assert!(frames[5].ends_with(&format!("step 2\n{HELP}\nunknown key: 'x'\n")));
+        assert!(frames[6].contains("step 2 [2]\n"));
+        assert!(frames[6].ends_with("unknown key: 'x'\n"));
+    }
+}
@@ -0,0 +1,327 @@
+use colored::Colorize;
+use num::ToPrimitive;
+use std::fs::File;
+use std::io::Write;
+
+use crate::{interpolate_color, write_file};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f32, [u8; 3])>,
+}
+
+impl Gradient {
+    pub fn new(stops: &[(f32, [u8; 3])]) -> Self {
+        assert!(!stops.is_empty(), "gradient needs at least one stop");
+        let mut stops = stops.to_vec();
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        Gradient { stops }
+    }
+
+    pub fn even(colors: &[[u8; 3]]) -> Self {
+        let n = colors.len().max(2) - 1;
+        let stops = colors
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i as f32 / n as f32, *c))
+            .collect::<Vec<_>>();
+        Gradient::new(&stops)
+    }
+
+    pub fn heat() -> Self {
+        Gradient::even(&[[255, 255, 255], [255, 0, 0]])
+    }
+
+    pub fn spectral() -> Self {
+        Gradient::even(&[
+            [94, 79, 162],
+            [50, 136, 189],
+            [171, 221, 164],
+            [254, 224, 139],
+            [244, 109, 67],
+            [158, 1, 66],
+        ])
+    }
+
+    pub fn color(&self, t: f32) -> [u8; 3] {
+        let t = t.clamp(0.0, 1.0);
+        let first = self.stops[0];
+        if t <= first.0 {
+            return first.1;
+        }
+        for w in self.stops.windows(2) {
+            let (p1, c1) = w[0];
+            let (p2, c2) = w[1];
+            if t <= p2 {
+                let factor = if p2 > p1 { (t - p1) / (p2 - p1) } else { 1.0 };
+                return interpolate_color(c1, c2, factor);
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scale {
+    MinMax,
+    Percentile(f64, f64),
+    Fixed(f64, f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Marker {
+    pub idx: usize,
+    pub symbol: char,
+    pub color: [u8; 3],
+}
+
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    values: Vec<Option<f64>>,
+    rows: usize,
+    cols: usize,
+    gradient: Gradient,
+    scale: Scale,
+    blank: [u8; 3],
+    markers: Vec<Marker>,
+}
+
+impl Heatmap {
+    pub fn new(values: Vec<Option<f64>>, cols: usize) -> Self {
+        assert!(
+            cols > 0 && values.len().is_multiple_of(cols),
+            "grid is not rectangular"
+        );
+        let rows = values.len() / cols;
+        Heatmap {
+            values,
+            rows,
+            cols,
+            gradient: Gradient::heat(),
+            scale: Scale::MinMax,
+            blank: [0, 0, 0],
+            markers: vec![],
+        }
+    }
+
+    pub fn from_grid<T, F>(grid: &[T], cols: usize, visible: F) -> Self
+    where
+        T: ToPrimitive + Copy,
+        F: Fn(T) -> bool,
+    {
+        let values = grid
+            .iter()
+            .map(|&v| if visible(v) { v.to_f64() } else { None })
+            .collect();
+        Heatmap::new(values, cols)
+    }
+
+    pub fn gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = gradient;
+        self
+    }
+
+    pub fn scale(mut self, scale: Scale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn blank(mut self, color: [u8; 3]) -> Self {
+        self.blank = color;
+        self
+    }
+
+    pub fn marker(mut self, idx: usize, symbol: char, color: [u8; 3]) -> Self {
+        self.markers.retain(|m| m.idx != idx);
+        self.markers.push(Marker { idx, symbol, color });
+        self
+    }
+
+    pub fn start(self, idx: usize) -> Self {
+        self.marker(idx, 'S', [255, 255, 0])
+    }
+
+    pub fn end(self, idx: usize) -> Self {
+        self.marker(idx, 'E', [0, 255, 255])
+    }
+
+    pub fn path(self, path: &[usize]) -> Self {
+        path.iter()
+            .fold(self, |hm, &idx| hm.marker(idx, '*', [0, 0, 255]))
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn bounds(&self) -> Option<(f64, f64)> {
+        let mut vs = self.values.iter().flatten().copied().collect::<Vec<_>>();
+        if vs.is_empty() {
+            return None;
+        }
+        vs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let percentile = |p: f64| {
+            let i = ((vs.len() - 1) as f64 * p.clamp(0.0, 1.0)).round() as usize;
+            vs[i]
+        };
+        Some(match self.scale {
+            Scale::MinMax => (vs[0], vs[vs.len() - 1]),
+            Scale::Percentile(lo, hi) => (percentile(lo), percentile(hi)),
+            Scale::Fixed(lo, hi) => (lo, hi),
+        })
+    }
+
+    fn find_marker(&self, idx: usize) -> Option<&Marker> {
+        self.markers.iter().rev().find(|m| m.idx == idx)
+    }
+
+    pub fn cell_color(&self, idx: usize) -> [u8; 3] {
+        self.color_within(idx, self.bounds())
+    }
+
+    fn color_within(&self, idx: usize, bounds: Option<(f64, f64)>) -> [u8; 3] {
+        if let Some(m) = self.find_marker(idx) {
+            return m.color;
+        }
+        match (self.values[idx], bounds) {
+            (Some(v), Some((lo, hi))) => {
+                let factor = if hi > lo { (v - lo) / (hi - lo) } else { 0.0 };
+                self.gradient.color(factor as f32)
+            }
+            _ => self.blank,
+        }
+    }
+
+    fn render<F>(&self, paint: F) -> String
+    where
+        F: Fn(char, [u8; 3]) -> String,
+    {
+        let bounds = self.bounds();
+        let mut text = String::new();
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let idx = row * self.cols + col;
+                let symbol = self.find_marker(idx).map_or('█', |m| m.symbol);
+                text += &paint(symbol, self.color_within(idx, bounds));
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn render_terminal(&self) -> String {
+        self.render(|symbol, [r, g, b]| symbol.to_string().truecolor(r, g, b).to_string())
+    }
+
+    // the same layout without escape codes, for logs and tests
+    pub fn render_plain(&self) -> String {
+        self.render(|symbol, _| symbol.to_string())
+    }
+
+    pub fn to_ppm(&self, cell_size: usize) -> Vec<u8> {
+        let width = self.cols * cell_size;
+        let height = self.rows * cell_size;
+        let bounds = self.bounds();
+        let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        for y in 0..height {
+            for x in 0..width {
+                let idx = (y / cell_size) * self.cols + x / cell_size;
+                image.extend_from_slice(&self.color_within(idx, bounds));
+            }
+        }
+        image
+    }
+
+    pub fn to_svg(&self, cell_size: usize) -> String {
+        let bounds = self.bounds();
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">",
+            self.cols * cell_size,
+            self.rows * cell_size
+        );
+        for idx in 0..self.values.len() {
+            let [r, g, b] = self.color_within(idx, bounds);
+            svg += &format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{cell_size}\" height=\"{cell_size}\" fill=\"#{r:02x}{g:02x}{b:02x}\"/>",
+                (idx % self.cols) * cell_size,
+                (idx / self.cols) * cell_size,
+            );
+        }
+        svg + "</svg>"
+    }
+
+    pub fn write_svg(&self, filename: &str, cell_size: usize) {
+        write_file(filename, &self.to_svg(cell_size));
+    }
+
+    pub fn write_ppm(&self, filename: &str, cell_size: usize) {
+        let mut file = File::create(filename).expect("cannot create file");
+        file.write_all(&self.to_ppm(cell_size))
+            .expect("write error");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradient_color() {
+        let g = Gradient::even(&[[0, 0, 0], [100, 0, 0], [100, 100, 0]]);
+        assert_eq!(g.color(0.0), [0, 0, 0]);
+        assert_eq!(g.color(0.25), [50, 0, 0]);
+        assert_eq!(g.color(0.5), [100, 0, 0]);
+        assert_eq!(g.color(0.75), [100, 50, 0]);
+        assert_eq!(g.color(1.0), [100, 100, 0]);
+        assert_eq!(g.color(2.0), [100, 100, 0]);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let grid = (0..=100).collect::<Vec<u32>>();
+        let hm = Heatmap::from_grid(&grid, 101, |v| v != 100);
+        assert_eq!(hm.bounds(), Some((0.0, 99.0)));
+
+        let hm = hm.scale(Scale::Percentile(0.1, 0.9));
+        assert_eq!(hm.bounds(), Some((10.0, 89.0)));
+
+        let hm = Heatmap::from_grid(&[u32::MAX; 4], 2, |v| v != u32::MAX);
+        assert_eq!(hm.bounds(), None);
+    }
+
+    #[test]
+    fn test_cell_color() {
+        let hm = Heatmap::from_grid(&[0, 5, 10, -1], 2, |v: i32| v >= 0)
+            .gradient(Gradient::even(&[[0, 0, 0], [200, 100, 0]]))
+            .blank([1, 2, 3])
+            .end(2);
+
+        assert_eq!(hm.size(), (2, 2));
+        assert_eq!(hm.cell_color(0), [0, 0, 0]);
+        assert_eq!(hm.cell_color(1), [100, 50, 0]);
+        assert_eq!(hm.cell_color(2), [0, 255, 255]);
+        assert_eq!(hm.cell_color(3), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_render() {
+        let hm = Heatmap::from_grid(&[1, 2, 3, 4, 5, 6], 3, |_| true)
+            .start(0)
+            .end(5)
+            .path(&[1, 4]);
+        assert_eq!(hm.render_plain(), "S*█\n█*E\n");
+
+        let ppm = hm.to_ppm(2);
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        assert_eq!(&ppm[header.len()..header.len() + 3], &[255, 255, 0]);
+
+        let svg = hm.to_svg(2);
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"6\" height=\"4\">")
+        );
+        assert!(svg.contains("<rect x=\"4\" y=\"2\" width=\"2\" height=\"2\" fill=\"#00ffff\"/>"));
+        assert_eq!(svg.matches("<rect").count(), 6);
+    }
+}
@@ -0,0 +1,244 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub type Point3 = [i64; 3];
+
+// the sum of three squares fits in a u64 only while the points are less
+// than 2^31 apart on every axis, further apart it overflows
+pub fn dist2(a: &Point3, b: &Point3) -> u64 {
+    (0..3).map(|i| a[i].abs_diff(b[i]).pow(2)).sum()
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    point: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+    // bounding box of every point in the subtree
+    min: Point3,
+    max: Point3,
+}
+
+// a 3D k-d tree over a fixed set of points, split on the median of the
+// axes in turn
+#[derive(Debug, Clone)]
+pub struct KdTree {
+    points: Vec<Point3>,
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+impl KdTree {
+    pub fn new(points: Vec<Point3>) -> Self {
+        let mut tree = KdTree {
+            points,
+            nodes: vec![],
+            root: None,
+        };
+        let mut order = (0..tree.points.len()).collect::<Vec<_>>();
+        tree.root = tree.build(&mut order, 0);
+        tree
+    }
+
+    fn build(&mut self, order: &mut [usize], depth: usize) -> Option<usize> {
+        if order.is_empty() {
+            return None;
+        }
+        let axis = depth % 3;
+        let mid = order.len() / 2;
+        let points = &self.points;
+        order.select_nth_unstable_by_key(mid, |i| points[*i][axis]);
+
+        let mut min = [i64::MAX; 3];
+        let mut max = [i64::MIN; 3];
+        for i in order.iter() {
+            for a in 0..3 {
+                min[a] = min[a].min(points[*i][a]);
+                max[a] = max[a].max(points[*i][a]);
+            }
+        }
+
+        let point = order[mid];
+        let (left, rest) = order.split_at_mut(mid);
+        let left = self.build(left, depth + 1);
+        let right = self.build(&mut rest[1..], depth + 1);
+        self.nodes.push(Node {
+            point,
+            left,
+            right,
+            min,
+            max,
+        });
+        Some(self.nodes.len() - 1)
+    }
+
+    pub fn points(&self) -> &[Point3] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    // the other points by increasing distance from points[query]
+    pub fn neighbors(&self, query: usize) -> Neighbors<'_> {
+        let mut heap = BinaryHeap::new();
+        if let Some(root) = self.root {
+            heap.push(Reverse((0, Entry::Node(root))));
+        }
+        Neighbors {
+            tree: self,
+            query,
+            heap,
+        }
+    }
+
+    // every pair (i < j) by increasing squared distance, then by (i, j).
+    // Each point keeps a lazy neighbor search and only its next pair waits
+    // in the merge heap, but every search holds the nodes and points it has
+    // opened, up to O(n) of them per point as the pairs get further apart
+    pub fn closest_pairs(&self) -> ClosestPairs<'_> {
+        let mut searches = (0..self.len())
+            .map(|i| self.neighbors(i))
+            .collect::<Vec<_>>();
+        let mut heap = BinaryHeap::new();
+        for (i, search) in searches.iter_mut().enumerate() {
+            if let Some((d, j)) = search.find(|(_, j)| *j > i) {
+                heap.push(Reverse((d, i, j)));
+            }
+        }
+        ClosestPairs { searches, heap }
+    }
+
+    // same range limit as dist2
+    fn box_dist2(&self, node: &Node, p: &Point3) -> u64 {
+        (0..3)
+            .map(|a| {
+                let gap = if p[a] < node.min[a] {
+                    node.min[a].abs_diff(p[a])
+                } else if p[a] > node.max[a] {
+                    p[a].abs_diff(node.max[a])
+                } else {
+                    0
+                };
+                gap.pow(2)
+            })
+            .sum()
+    }
+}
+
+// nodes sort before points at the same distance so equally distant points
+// come out in index order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Entry {
+    Node(usize),
+    Point(usize),
+}
+
+pub struct Neighbors<'a> {
+    tree: &'a KdTree,
+    query: usize,
+    heap: BinaryHeap<Reverse<(u64, Entry)>>,
+}
+
+impl Iterator for Neighbors<'_> {
+    type Item = (u64, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tree = self.tree;
+        let q = &tree.points[self.query];
+        while let Some(Reverse((d, entry))) = self.heap.pop() {
+            match entry {
+                Entry::Point(i) => return Some((d, i)),
+                Entry::Node(n) => {
+                    let node = &tree.nodes[n];
+                    if node.point != self.query {
+                        let d = dist2(q, &tree.points[node.point]);
+                        self.heap.push(Reverse((d, Entry::Point(node.point))));
+                    }
+                    for child in node.left.iter().chain(node.right.iter()) {
+                        let d = tree.box_dist2(&tree.nodes[*child], q);
+                        self.heap.push(Reverse((d, Entry::Node(*child))));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+pub struct ClosestPairs<'a> {
+    searches: Vec<Neighbors<'a>>,
+    heap: BinaryHeap<Reverse<(u64, usize, usize)>>,
+}
+
+impl Iterator for ClosestPairs<'_> {
+    type Item = (u64, usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((d, i, j)) = self.heap.pop()?;
+        if let Some((d, j)) = self.searches[i].find(|(_, j)| *j > i) {
+            self.heap.push(Reverse((d, i, j)));
+        }
+        Some((d, i, j))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cloud(n: usize, seed: u64) -> Vec<Point3> {
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 100) as i64 - 50
+        };
+        (0..n).map(|_| [next(), next(), next()]).collect()
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let points = cloud(200, 7);
+        let tree = KdTree::new(points.clone());
+        for query in [0, 17, 199] {
+            let mut expected = (0..points.len())
+                .filter(|i| *i != query)
+                .map(|i| (dist2(&points[query], &points[i]), i))
+                .collect::<Vec<_>>();
+            expected.sort();
+            assert_eq!(tree.neighbors(query).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_closest_pairs() {
+        // duplicates and ties included
+        let mut points = cloud(150, 11);
+        points.push(points[3]);
+        let tree = KdTree::new(points.clone());
+
+        let mut expected = vec![];
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                expected.push((dist2(&points[i], &points[j]), i, j));
+            }
+        }
+        expected.sort();
+        assert_eq!(tree.closest_pairs().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_small() {
+        assert_eq!(KdTree::new(vec![]).closest_pairs().next(), None);
+        assert_eq!(KdTree::new(vec![[1, 2, 3]]).closest_pairs().next(), None);
+        let tree = KdTree::new(vec![[0, 0, 0], [3, 4, 0]]);
+        assert_eq!(tree.closest_pairs().collect::<Vec<_>>(), vec![(25, 0, 1)]);
+    }
+}
@@ -1,6 +1,17 @@
 #![allow(unused)]
 
+pub mod answer;
+pub mod dashboard;
+pub mod debugger;
+pub mod heatmap;
+pub mod kdtree;
+pub mod polygon;
+pub mod rotation;
+pub mod runner;
+pub mod trace;
+
 mod y2015;
+mod y2020;
 mod y2021;
 mod y2022;
 mod y2023;
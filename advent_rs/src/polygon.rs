@@ -0,0 +1,262 @@
+use num::integer::gcd;
+
+pub type Point = (i64, i64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+// a simple polygon on integer coordinates, vertices in walking order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point>) -> Self {
+        assert!(vertices.len() >= 3, "a polygon needs at least 3 vertices");
+        Polygon { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| (self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    pub fn is_rectilinear(&self) -> bool {
+        self.edges().all(|(a, b)| a.0 == b.0 || a.1 == b.1)
+    }
+
+    // shoelace formula; twice the area keeps it an integer, positive when
+    // the vertices turn counter-clockwise with y pointing up
+    pub fn signed_area2(&self) -> i64 {
+        self.edges().map(|(a, b)| a.0 * b.1 - b.0 * a.1).sum()
+    }
+
+    pub fn area2(&self) -> u64 {
+        self.signed_area2().unsigned_abs()
+    }
+
+    // lattice points on the edges
+    pub fn boundary_points(&self) -> u64 {
+        self.edges()
+            .map(|(a, b)| gcd(b.0 - a.0, b.1 - a.1).unsigned_abs())
+            .sum()
+    }
+
+    // lattice points strictly inside, by Pick's theorem A = I + B/2 - 1
+    pub fn interior_points(&self) -> u64 {
+        (self.area2() + 2 - self.boundary_points()) / 2
+    }
+
+    pub fn locate(&self, p: Point) -> Location {
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            let cross = (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+            let within = a.0.min(b.0) <= p.0
+                && p.0 <= a.0.max(b.0)
+                && a.1.min(b.1) <= p.1
+                && p.1 <= a.1.max(b.1);
+            if cross == 0 && within {
+                return Location::Boundary;
+            }
+            // half-open in y so a ray through a vertex is counted once
+            if (a.1 > p.1) != (b.1 > p.1) && (cross > 0) == (b.1 > a.1) {
+                inside = !inside;
+            }
+        }
+        if inside {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.locate(p) != Location::Outside
+    }
+}
+
+// the closed region of a rectilinear polygon on a compressed grid, for
+// answering "is this axis-aligned rectangle fully inside" in O(log n).
+// Sample 2i is the line xs[i], sample 2i+1 the open gap after it.
+#[derive(Debug, Clone)]
+pub struct Region {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    // outside[y][x] counts outside samples above and left of (x, y)
+    outside: Vec<Vec<u32>>,
+}
+
+impl Region {
+    pub fn new(polygon: &Polygon) -> Self {
+        assert!(polygon.is_rectilinear(), "the polygon must be rectilinear");
+        let axis = |f: fn(&Point) -> i64| {
+            let mut vs = polygon.vertices().iter().map(f).collect::<Vec<_>>();
+            vs.sort_unstable();
+            vs.dedup();
+            vs
+        };
+        let xs = axis(|p| p.0);
+        let ys = axis(|p| p.1);
+        let (w, h) = (xs.len() * 2 - 1, ys.len() * 2 - 1);
+        let index = |vs: &[i64], v: i64| vs.binary_search(&v).unwrap() * 2;
+
+        let mut boundary = vec![vec![false; w]; h];
+        let mut crossings = vec![vec![]; h];
+        for (a, b) in polygon.edges() {
+            let (x1, x2) = (index(&xs, a.0.min(b.0)), index(&xs, a.0.max(b.0)));
+            let (y1, y2) = (index(&ys, a.1.min(b.1)), index(&ys, a.1.max(b.1)));
+            for row in boundary.iter_mut().take(y2 + 1).skip(y1) {
+                row[x1..=x2].iter_mut().for_each(|s| *s = true);
+            }
+            if x1 == x2 {
+                for y in (y1 + 1..y2).step_by(2) {
+                    crossings[y].push(x1);
+                }
+            }
+        }
+
+        let mut inside = boundary.clone();
+        for (y, xs) in crossings.iter_mut().enumerate().skip(1).step_by(2) {
+            xs.sort_unstable();
+            for pair in xs.chunks(2) {
+                inside[y][pair[0]..=pair[1]]
+                    .iter_mut()
+                    .for_each(|s| *s = true);
+            }
+        }
+        // a line sample off the boundary takes after the gaps beside it
+        for y in (0..h).step_by(2) {
+            let near = (0..w)
+                .map(|x| (y > 0 && inside[y - 1][x]) || (y + 1 < h && inside[y + 1][x]))
+                .collect::<Vec<_>>();
+            for (s, near) in inside[y].iter_mut().zip(near) {
+                *s |= near;
+            }
+        }
+
+        let mut outside = vec![vec![0; w + 1]; h + 1];
+        for y in 0..h {
+            for x in 0..w {
+                outside[y + 1][x + 1] = outside[y][x + 1] + outside[y + 1][x] - outside[y][x]
+                    + u32::from(!inside[y][x]);
+            }
+        }
+
+        Region { xs, ys, outside }
+    }
+
+    fn sample(vs: &[i64], v: i64) -> Option<usize> {
+        match vs.binary_search(&v) {
+            Ok(i) => Some(i * 2),
+            Err(i) if i > 0 && i < vs.len() => Some(i * 2 - 1),
+            Err(_) => None,
+        }
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.contains_rect(p, p)
+    }
+
+    // every point of the rectangle spanned by the two corners is inside
+    // or on the boundary
+    pub fn contains_rect(&self, a: Point, b: Point) -> bool {
+        let corners = (
+            Region::sample(&self.xs, a.0.min(b.0)),
+            Region::sample(&self.xs, a.0.max(b.0)),
+            Region::sample(&self.ys, a.1.min(b.1)),
+            Region::sample(&self.ys, a.1.max(b.1)),
+        );
+        match corners {
+            (Some(x1), Some(x2), Some(y1), Some(y2)) => {
+                let o = &self.outside;
+                o[y2 + 1][x2 + 1] + o[y1][x1] - o[y1][x2 + 1] - o[y2 + 1][x1] == 0
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn l_shape() -> Polygon {
+        // the red-tile loop from 2025 day 9
+        Polygon::new(vec![
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ])
+    }
+
+    #[test]
+    fn test_area() {
+        let square = Polygon::new(vec![(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(square.signed_area2(), 32);
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+
+        let triangle = Polygon::new(vec![(0, 0), (0, 3), (6, 0)]);
+        assert_eq!(triangle.signed_area2(), -18);
+        assert_eq!(triangle.area2(), 18);
+        assert_eq!(triangle.boundary_points(), 12);
+        assert_eq!(triangle.interior_points(), 4);
+    }
+
+    #[test]
+    fn test_locate() {
+        let triangle = Polygon::new(vec![(0, 0), (6, 0), (0, 3)]);
+        assert_eq!(triangle.locate((1, 1)), Location::Inside);
+        assert_eq!(triangle.locate((2, 2)), Location::Boundary);
+        assert_eq!(triangle.locate((0, 2)), Location::Boundary);
+        assert_eq!(triangle.locate((5, 1)), Location::Outside);
+        assert_eq!(triangle.locate((-1, 0)), Location::Outside);
+
+        let polygon = l_shape();
+        assert_eq!(polygon.locate((8, 4)), Location::Inside);
+        assert_eq!(polygon.locate((3, 3)), Location::Boundary);
+        assert_eq!(polygon.locate((3, 2)), Location::Outside);
+        assert_eq!(polygon.locate((10, 8)), Location::Outside);
+        // the ray from here passes through the vertices at y = 3
+        assert_eq!(polygon.locate((1, 3)), Location::Outside);
+
+        let inside = (0..14)
+            .flat_map(|x| (0..9).map(move |y| (x, y)))
+            .filter(|p| polygon.contains(*p))
+            .count() as u64;
+        assert_eq!(
+            inside,
+            polygon.interior_points() + polygon.boundary_points()
+        );
+    }
+
+    #[test]
+    fn test_region() {
+        let polygon = l_shape();
+        let region = Region::new(&polygon);
+
+        for x in 0..14 {
+            for y in 0..9 {
+                assert_eq!(region.contains((x, y)), polygon.contains((x, y)));
+            }
+        }
+        assert!(region.contains_rect((7, 3), (11, 1)));
+        assert!(region.contains_rect((9, 5), (2, 3)));
+        assert!(!region.contains_rect((2, 5), (11, 1)));
+        assert!(!region.contains_rect((9, 7), (2, 3)));
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::HashSet;
+use std::ops::Mul;
+
+pub type Vec3 = [i32; 3];
+
+// a 3x3 integer matrix mapping one axis-aligned orientation to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Rotation([[i32; 3]; 3]);
+
+// quarter turns about x, y and z
+const GENERATORS: [Rotation; 3] = [
+    Rotation([[1, 0, 0], [0, 0, -1], [0, 1, 0]]),
+    Rotation([[0, 0, 1], [0, 1, 0], [-1, 0, 0]]),
+    Rotation([[0, -1, 0], [1, 0, 0], [0, 0, 1]]),
+];
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    // the 24 proper rotations of the cube, identity first
+    pub fn all() -> Vec<Rotation> {
+        let mut seen = HashSet::new();
+        let mut rotations = vec![Rotation::IDENTITY];
+        seen.insert(Rotation::IDENTITY);
+
+        let mut i = 0;
+        while i < rotations.len() {
+            for g in GENERATORS.iter() {
+                let r = *g * rotations[i];
+                if seen.insert(r) {
+                    rotations.push(r);
+                }
+            }
+            i += 1;
+        }
+        rotations
+    }
+
+    pub fn matrix(&self) -> [[i32; 3]; 3] {
+        self.0
+    }
+
+    pub fn apply(&self, v: Vec3) -> Vec3 {
+        let m = &self.0;
+        [0, 1, 2].map(|r| m[r][0] * v[0] + m[r][1] * v[1] + m[r][2] * v[2])
+    }
+
+    pub fn inverse(&self) -> Rotation {
+        let m = &self.0;
+        Rotation([0, 1, 2].map(|r| [m[0][r], m[1][r], m[2][r]]))
+    }
+
+    pub fn determinant(&self) -> i32 {
+        let m = &self.0;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+}
+
+impl Mul for Rotation {
+    type Output = Rotation;
+
+    fn mul(self, rhs: Rotation) -> Rotation {
+        let (a, b) = (&self.0, &rhs.0);
+        Rotation([0, 1, 2].map(|r| [0, 1, 2].map(|c| (0..3).map(|k| a[r][k] * b[k][c]).sum())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all() {
+        let all = Rotation::all();
+        assert_eq!(all.len(), 24);
+        assert_eq!(all[0], Rotation::IDENTITY);
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 24);
+
+        for r in all.iter() {
+            assert_eq!(r.determinant(), 1);
+            assert_eq!(*r * r.inverse(), Rotation::IDENTITY);
+            for s in all.iter() {
+                assert!(all.contains(&(*r * *s)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let v = [1, 2, 3];
+        let images = Rotation::all()
+            .iter()
+            .map(|r| r.apply(v))
+            .collect::<HashSet<_>>();
+        assert_eq!(images.len(), 24);
+        assert!(images.contains(&[-1, -2, 3]));
+        // a reflection is not a rotation
+        assert!(!images.contains(&[-1, 2, 3]));
+
+        let (r, s) = (GENERATORS[0], GENERATORS[2]);
+        assert_eq!((r * s).apply(v), r.apply(s.apply(v)));
+        assert_eq!(r.inverse().apply(r.apply(v)), v);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::answer::Answer;
+use crate::dashboard::{self, Record};
+
+// one part of one day, with the answer it is known to give
+#[derive(Clone, Copy)]
+pub struct Solver {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub expected: Option<&'static str>,
+    pub solve: fn() -> Answer,
+}
+
+impl Solver {
+    pub fn new(
+        year: u16,
+        day: u8,
+        part: u8,
+        expected: Option<&'static str>,
+        solve: fn() -> Answer,
+    ) -> Self {
+        Solver {
+            year,
+            day,
+            part,
+            expected,
+            solve,
+        }
+    }
+}
+
+pub fn solvers() -> Vec<Solver> {
+    [
+        crate::y2015::solvers(),
+        crate::y2020::solvers(),
+        crate::y2021::solvers(),
+        crate::y2022::solvers(),
+        crate::y2023::solvers(),
+        crate::y2024::solvers(),
+        crate::y2025::solvers(),
+    ]
+    .concat()
+}
+
+// runs the matching solvers through `dashboard::measure` so their answers
+// and timings show up in `aoc serve`, and saves the visuals they draw
+pub fn run(year: u16, day: Option<u8>) -> Vec<Record> {
+    dashboard::save_visuals(true);
+    solvers()
+        .into_iter()
+        .filter(|s| s.year == year && day.is_none_or(|d| s.day == d))
+        .map(|s| dashboard::measure(s.year, s.day, s.part, s.expected, s.solve))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::fs;
+
+    // the `mod dN;` lines of a year module
+    fn declared_days(year: u16) -> Vec<u8> {
+        let path = format!("{}/src/y{year}/mod.rs", env!("CARGO_MANIFEST_DIR"));
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.trim_start_matches("pub ").strip_prefix("mod d"))
+            .filter_map(|rest| rest.strip_suffix(';')?.parse().ok())
+            .collect()
+    }
+
+    #[test]
+    fn test_solvers() {
+        let mut seen = HashSet::new();
+        for s in solvers() {
+            assert!(
+                seen.insert((s.year, s.day, s.part)),
+                "{} {} {}",
+                s.year,
+                s.day,
+                s.part
+            );
+            assert!(declared_days(s.year).contains(&s.day));
+            assert!(s.part == 1 || s.part == 2);
+        }
+        for year in dashboard::calendar().keys() {
+            for day in declared_days(*year) {
+                assert!(seen.contains(&(*year, day, 1)), "{} {}", year, day);
+            }
+        }
+    }
+}
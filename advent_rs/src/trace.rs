@@ -0,0 +1,168 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+pub const ENV_VAR: &str = "AOC_LOG";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(Level::Off),
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            other => Err(format!("unknown log level: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Off => "OFF",
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        f.pad(name)
+    }
+}
+
+// `debug,y2022::d16=trace,y2022::d11=off`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    default: Level,
+    targets: Vec<(String, Level)>,
+}
+
+impl Filter {
+    pub fn off() -> Self {
+        Filter {
+            default: Level::Off,
+            targets: vec![],
+        }
+    }
+
+    pub fn level(&self, target: &str) -> Level {
+        self.targets
+            .iter()
+            .filter(|(prefix, _)| {
+                target == prefix
+                    || (target.starts_with(prefix.as_str())
+                        && target[prefix.len()..].starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::off();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match part.split_once('=') {
+                Some((target, level)) => filter
+                    .targets
+                    .push((target.trim().to_string(), level.parse()?)),
+                None => filter.default = part.parse()?,
+            }
+        }
+        Ok(filter)
+    }
+}
+
+static FILTER: RwLock<Option<Filter>> = RwLock::new(None);
+
+pub fn set_filter(spec: &str) -> Result<(), String> {
+    let filter = spec.parse()?;
+    *FILTER.write().unwrap() = Some(filter);
+    Ok(())
+}
+
+fn from_env() -> Filter {
+    std::env::var(ENV_VAR)
+        .ok()
+        .and_then(|spec| spec.parse().ok())
+        .unwrap_or_else(Filter::off)
+}
+
+pub fn target(module_path: &str) -> &str {
+    module_path
+        .split_once("::")
+        .map_or(module_path, |(_, rest)| rest)
+}
+
+pub fn enabled(target: &str, level: Level) -> bool {
+    if let Some(filter) = FILTER.read().unwrap().as_ref() {
+        return level <= filter.level(target);
+    }
+    let mut filter = FILTER.write().unwrap();
+    level <= filter.get_or_insert_with(from_env).level(target)
+}
+
+pub fn log(target: &str, level: Level, args: fmt::Arguments) {
+    eprintln!("[{level:5} {target}] {args}");
+}
+
+#[macro_export]
+macro_rules! event {
+    ($level:expr, $($arg:tt)+) => {{
+        let target = $crate::trace::target(module_path!());
+        if $crate::trace::enabled(target, $level) {
+            $crate::trace::log(target, $level, format_args!($($arg)+));
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::event!($crate::trace::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::event!($crate::trace::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filter() {
+        let filter: Filter = "debug, y2022::d16=trace,y2022=off".parse().unwrap();
+        assert_eq!(filter.level("y2021::d19"), Level::Debug);
+        assert_eq!(filter.level("y2022::d11"), Level::Off);
+        assert_eq!(filter.level("y2022::d16"), Level::Trace);
+        assert_eq!(filter.level("y2022::d16::tests"), Level::Trace);
+        assert_eq!(filter.level("y2022::d1"), Level::Off);
+
+        assert_eq!("".parse::<Filter>(), Ok(Filter::off()));
+        assert!("verbose".parse::<Filter>().is_err());
+        assert!("y2022=loud".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn test_target() {
+        assert_eq!(target("advent_rs::y2022::d16"), "y2022::d16");
+        assert_eq!(target("advent_rs"), "advent_rs");
+    }
+}
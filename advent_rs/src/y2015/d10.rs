@@ -51,6 +51,20 @@ fn say(seq: &str) -> String {
     result
 }
 
+const INPUT: &str = "1321131112";
+
+fn say_times(times: usize) -> String {
+    (0..times).fold(INPUT.to_string(), |seq, _| say(&seq))
+}
+
+pub(crate) fn quiz1() -> usize {
+    say_times(40).len()
+}
+
+pub(crate) fn quiz2() -> usize {
+    say_times(50).len()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -70,23 +84,11 @@ mod tests {
 
     #[test]
     fn quiz1() {
-        let mut input = String::from("1321131112");
-        for _ in 0..40 {
-            input = say(&input);
-        }
-
-        // println!("{}", input);
-        assert_eq!(input.len(), 492982);
+        assert_eq!(super::quiz1(), 492982);
     }
 
     #[test]
     fn quiz2() {
-        let mut input = String::from("1321131112");
-        for _ in 0..50 {
-            input = say(&input);
-        }
-
-        // println!("{}", input);
-        assert_eq!(input.len(), 6989950);
+        assert_eq!(super::quiz2(), 6989950);
     }
 }
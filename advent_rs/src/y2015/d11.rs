@@ -148,20 +148,28 @@ fn next_password(pwd: &str) -> String {
     next
 }
 
+const INPUT: &str = "vzbxkghb";
+
+pub(crate) fn quiz1() -> String {
+    next_password(INPUT)
+}
+
+pub(crate) fn quiz2() -> String {
+    next_password(&next_password(INPUT))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn quiz1() {
-        let pwd = "vzbxkghb";
-        assert_eq!(next_password(pwd), "vzbxxyzz");
+        assert_eq!(super::quiz1(), "vzbxxyzz");
     }
 
     #[test]
     fn quiz2() {
-        let pwd = "vzbxkghb";
-        assert_eq!(next_password(&next_password(pwd)), "vzcaabcc");
+        assert_eq!(super::quiz2(), "vzcaabcc");
     }
 
     #[test]
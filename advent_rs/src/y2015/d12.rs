@@ -37,83 +37,367 @@ the value "red". Do this only for objects ({...}), not arrays ([...]).
 
  */
 
-use regex::Regex;
+use std::fmt;
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2015/input12.txt";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct JsonError {
+    pub pos: usize,
+    pub msg: String,
+}
 
-fn find_numbers(text: &str) -> Vec<i32> {
-    let mut result = Vec::new();
-    let re = Regex::new(r"-?\d+").unwrap();
-    for m in re.find_iter(text) {
-        result.push(m.as_str().parse::<i32>().unwrap());
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.msg, self.pos)
     }
-    result
 }
 
-fn find_open_curl_brace(text: &str, start: usize) -> Option<usize> {
-    let mut meet_ccb = 0;
-    let mut meet_csb = 0;
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, msg: &str) -> Result<T, JsonError> {
+        Err(JsonError {
+            pos: self.pos,
+            msg: msg.to_string(),
+        })
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        while self
+            .text
+            .get(self.pos)
+            .is_some_and(|c| c.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+        self.text.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), JsonError> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.error(&format!("expected '{}'", c as char))
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: Json) -> Result<Json, JsonError> {
+        if self.text[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            self.error("unexpected character")
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, JsonError> {
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => self.string().map(Json::Str),
+            Some(b't') => self.keyword("true", Json::Bool(true)),
+            Some(b'f') => self.keyword("false", Json::Bool(false)),
+            Some(b'n') => self.keyword("null", Json::Null),
+            Some(c) if c == b'-' || c.is_ascii_digit() => self.number(),
+            Some(_) => self.error("unexpected character"),
+            None => self.error("unexpected end of input"),
+        }
+    }
 
-    for i in (0..start+1).rev() {
-        match (text.as_bytes())[i] {
-            b'}' => meet_ccb += 1,
-            b']' => meet_csb += 1,
-            b'[' => {
-                if meet_ccb == 0 && meet_csb == 0 {
-                    return None
+    fn number(&mut self) -> Result<Json, JsonError> {
+        let start = self.pos;
+        if self.text[self.pos] == b'-' {
+            self.pos += 1;
+        }
+        while self.text.get(self.pos).is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if matches!(self.text.get(self.pos), Some(b'.' | b'e' | b'E')) {
+            return self.error("only integers are supported");
+        }
+        let digits = std::str::from_utf8(&self.text[start..self.pos]).unwrap();
+        match digits.parse() {
+            Ok(n) => Ok(Json::Number(n)),
+            Err(_) => {
+                self.pos = start;
+                self.error("invalid number")
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect(b'"')?;
+        let mut bytes = vec![];
+        loop {
+            match self.text.get(self.pos) {
+                None => return self.error("unterminated string"),
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let c = match self.text.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => match self.unicode_escape() {
+                            Some(c) => c,
+                            None => return self.error("invalid unicode escape"),
+                        },
+                        _ => return self.error("invalid escape"),
+                    };
+                    bytes.extend(c.to_string().bytes());
                 }
-                meet_csb -= 1;
-            },
-            b'{' => if meet_ccb == 0 {
-                return Some(i);
-            } else {
-                meet_ccb -= 1;
-            },
-            _ => {}
+                Some(&c) => bytes.push(c),
+            }
+            self.pos += 1;
         }
+        self.pos += 1;
+        String::from_utf8(bytes).or_else(|_| self.error("invalid utf-8"))
     }
 
-    None
-}
+    // the 4 hex digits at `at`
+    fn hex4(&self, at: usize) -> Option<u32> {
+        let hex = self.text.get(at..at + 4)?;
+        if !hex.iter().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        u32::from_str_radix(std::str::from_utf8(hex).unwrap(), 16).ok()
+    }
 
-fn find_close_curl_brace(text: &str, start: usize) -> Option<usize> {
-    let mut meet_ocb = 0;
+    // `pos` is on the 'u' of `\uXXXX` and ends on the last hex digit read.
+    // Characters outside the BMP come as a UTF-16 surrogate pair, a high
+    // surrogate escape followed right away by a low one
+    fn unicode_escape(&mut self) -> Option<char> {
+        let c = match self.hex4(self.pos + 1)? {
+            hi @ 0xd800..=0xdbff => {
+                if self.text.get(self.pos + 5..self.pos + 7) != Some(b"\\u") {
+                    return None;
+                }
+                let lo = self
+                    .hex4(self.pos + 7)
+                    .filter(|lo| (0xdc00..=0xdfff).contains(lo))?;
+                self.pos += 6;
+                char::from_u32(0x10000 + ((hi - 0xd800) << 10) + (lo - 0xdc00))?
+            }
+            // a low surrogate on its own is not a character
+            unit => char::from_u32(unit)?,
+        };
+        self.pos += 4;
+        Some(c)
+    }
 
-    for i in start..text.len() {
-        match (text.as_bytes())[i] {
-            b'{' => meet_ocb += 1,
-            b'}' => if meet_ocb == 0 {
-                return Some(i);
-            } else {
-                meet_ocb -= 1;
-            },
-            _ => {}
+    fn array(&mut self) -> Result<Json, JsonError> {
+        self.expect(b'[')?;
+        let mut items = vec![];
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return self.error("expected ',' or ']'"),
+            }
         }
     }
 
-    None
+    fn object(&mut self) -> Result<Json, JsonError> {
+        self.expect(b'{')?;
+        let mut entries = vec![];
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(entries));
+        }
+        loop {
+            if self.peek() != Some(b'"') {
+                return self.error("expected a key");
+            }
+            let key = self.string()?;
+            self.expect(b':')?;
+            entries.push((key, self.value()?));
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(entries));
+                }
+                _ => return self.error("expected ',' or '}'"),
+            }
+        }
+    }
 }
 
-fn remove_red_object(text: &str) -> String {
-    let re = Regex::new(r#""red""#).unwrap();
-    let mut result = String::from(text);
-    let mut start = 0;
+pub trait Visitor {
+    type Output;
+
+    // null, booleans, numbers and strings
+    fn leaf(&mut self, value: &Json) -> Self::Output;
+    fn array(&mut self, items: Vec<Self::Output>) -> Self::Output;
+    fn object(&mut self, entries: Vec<(&str, Self::Output)>) -> Self::Output;
+}
 
-    while let Some(m) = re.find(&result[start..]) {
-        let ms = m.start() + start;
-        let me = m.end() + start;
+impl Json {
+    pub fn parse(text: &str) -> Result<Json, JsonError> {
+        let mut parser = Parser {
+            text: text.as_bytes(),
+            pos: 0,
+        };
+        let value = parser.value()?;
+        if parser.peek().is_some() {
+            return parser.error("trailing characters");
+        }
+        Ok(value)
+    }
 
-        if let Some(ss) = find_open_curl_brace(&result, ms) {
-            if let Some(es) = find_close_curl_brace(&result, me) {
-                result.replace_range(ss..es + 1, "");
-                start = ss;
-            } else {
-                panic!("Invalid format {}", result);
+    pub fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output {
+        match self {
+            Json::Array(items) => {
+                let items = items.iter().map(|v| v.accept(visitor)).collect();
+                visitor.array(items)
+            }
+            Json::Object(entries) => {
+                let entries = entries
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.accept(visitor)))
+                    .collect();
+                visitor.object(entries)
+            }
+            _ => visitor.leaf(self),
+        }
+    }
+
+    // drops every object the predicate matches, None when it matches the root
+    pub fn prune<F: Fn(&[(String, Json)]) -> bool>(&self, pred: &F) -> Option<Json> {
+        match self {
+            Json::Object(entries) if pred(entries) => None,
+            Json::Object(entries) => Some(Json::Object(
+                entries
+                    .iter()
+                    .filter_map(|(k, v)| Some((k.clone(), v.prune(pred)?)))
+                    .collect(),
+            )),
+            Json::Array(items) => Some(Json::Array(
+                items.iter().filter_map(|v| v.prune(pred)).collect(),
+            )),
+            _ => Some(self.clone()),
+        }
+    }
+}
+
+fn write_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{b}"),
+            Json::Number(n) => write!(f, "{n}"),
+            Json::Str(s) => write_str(f, s),
+            Json::Array(items) => {
+                write!(f, "[")?;
+                for (i, v) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{v}")?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(entries) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_str(f, k)?;
+                    write!(f, ":{v}")?;
+                }
+                write!(f, "}}")
             }
-        } else {
-            start = me;
         }
     }
+}
+
+struct SumNumbers;
+
+impl Visitor for SumNumbers {
+    type Output = i64;
+
+    fn leaf(&mut self, value: &Json) -> i64 {
+        match value {
+            Json::Number(n) => *n,
+            _ => 0,
+        }
+    }
+
+    fn array(&mut self, items: Vec<i64>) -> i64 {
+        items.iter().sum()
+    }
+
+    fn object(&mut self, entries: Vec<(&str, i64)>) -> i64 {
+        entries.iter().map(|(_, n)| n).sum()
+    }
+}
+
+fn has_red(entries: &[(String, Json)]) -> bool {
+    entries
+        .iter()
+        .any(|(_, v)| matches!(v, Json::Str(s) if s == "red"))
+}
 
-    result
+fn sum_numbers(json: &Json) -> i64 {
+    json.accept(&mut SumNumbers)
+}
+
+fn sum_without_red(json: &Json) -> i64 {
+    json.prune(&has_red).map_or(0, |json| sum_numbers(&json))
+}
+
+pub(crate) fn quiz1() -> i64 {
+    sum_numbers(&Json::parse(&read_file(FILE_NAME)).unwrap())
+}
+
+pub(crate) fn quiz2() -> i64 {
+    sum_without_red(&Json::parse(&read_file(FILE_NAME)).unwrap())
 }
 
 #[cfg(test)]
@@ -122,64 +406,148 @@ mod tests {
     use super::*;
     use crate::y2015::*;
 
+    fn parse(text: &str) -> Json {
+        Json::parse(text).unwrap()
+    }
+
     #[test]
     fn quiz1() {
-        let json = read_file("../data/2015/input12.txt");
-        assert_eq!(find_numbers(&json).iter().sum::<i32>(), 191164);
+        assert_eq!(super::quiz1(), 191164);
     }
 
     #[test]
     fn quiz2() {
-        let json = read_file("../data/2015/input12.txt");
-        let removed = remove_red_object(&json);
-        // write_file("../data/2015/input12-2-output.txt", &removed);
-        assert_eq!(find_numbers(&removed).iter().sum::<i32>(), 87842);
+        assert_eq!(super::quiz2(), 87842);
     }
 
     #[test]
-    fn test_find_numbers() {
-        assert_eq!(find_numbers(r#"{"a":[-1,1]}"#), vec![-1, 1]);
+    fn test_sum_numbers() {
+        let cases = [
+            ("[1,2,3]", 6),
+            (r#"{"a":2,"b":4}"#, 6),
+            ("[[[3]]]", 3),
+            (r#"{"a":{"b":4},"c":-1}"#, 3),
+            (r#"{"a":[-1,1]}"#, 0),
+            (r#"[-1,{"a":1}]"#, 0),
+            ("[]", 0),
+            ("{}", 0),
+        ];
+        for (text, sum) in cases {
+            assert_eq!(sum_numbers(&parse(text)), sum, "{}", text);
+        }
     }
 
     #[test]
-    fn test_re_number() {
-        let re = Regex::new(r"-?\d+").unwrap();
-
-        assert_number_matches(&re,
-                              r#"{"a":[-1,1]}"#,
-                              vec!["-1", "1"]);
+    fn test_sum_without_red() {
+        let cases = [
+            ("[1,2,3]", 6),
+            (r#"[1,{"c":"red","b":2},3]"#, 4),
+            (r#"{"d":"red","e":[1,2,3,4],"f":5}"#, 0),
+            (r#"[1,"red",5]"#, 6),
+            (r#"[1,{"c":["red"],"b":2},3]"#, 6),
+            // braces inside strings used to confuse the old scanner
+            (r#"[1,{"c":"}","b":2},{"x":"{","y":"red","z":7}]"#, 3),
+        ];
+        for (text, sum) in cases {
+            assert_eq!(sum_without_red(&parse(text)), sum, "{}", text);
+        }
+    }
 
-        assert_number_matches(&re,
-                              r#"{"a":{"b":4},"c":-1} "#,
-                              vec!["4", "-1"]);
+    #[test]
+    fn test_prune() {
+        let json = parse(r#"{"a":[1,{"c":"red"},{"d":{"c":"red"}}],"b":["red"]}"#);
+        assert_eq!(
+            json.prune(&has_red).unwrap().to_string(),
+            r#"{"a":[1,{}],"b":["red"]}"#
+        );
+        assert_eq!(parse(r#"{"c":"red"}"#).prune(&has_red), None);
+        let empty = |entries: &[(String, Json)]| entries.is_empty();
+        assert_eq!(
+            parse(r#"[{},{"a":{}}]"#).prune(&empty).unwrap().to_string(),
+            "[{}]"
+        );
     }
 
-    fn assert_number_matches(re: &Regex, text: &str, expected: Vec<&str>) {
-        let mut matches = re.find_iter(text);
-        for t in expected {
-            assert_eq!(matches.next().unwrap().as_str(), t);
-        }
+    #[test]
+    fn test_parse() {
+        let text = r#" { "s" : "a\"b\\cé\n", "t": [true, false, null], "n": -12 } "#;
+        let json = parse(text);
+        assert_eq!(
+            json,
+            Json::Object(vec![
+                ("s".to_string(), Json::Str("a\"b\\cé\n".to_string())),
+                (
+                    "t".to_string(),
+                    Json::Array(vec![Json::Bool(true), Json::Bool(false), Json::Null])
+                ),
+                ("n".to_string(), Json::Number(-12)),
+            ])
+        );
+        assert_eq!(parse(&json.to_string()), json);
+    }
 
+    #[test]
+    fn test_unicode_escapes() {
+        assert_eq!(parse(r#""\u00e9\u0041""#), Json::Str("éA".to_string()));
+        assert_eq!(parse(r#""\ud83d\ude00""#), Json::Str("😀".to_string()));
+        assert_eq!(parse(r#""a\uD83D\uDE00b""#), Json::Str("a😀b".to_string()));
+        assert_eq!(
+            parse(r#""\udbff\udfff""#),
+            Json::Str("\u{10ffff}".to_string())
+        );
+
+        let err = |text: &str| Json::parse(text).unwrap_err();
+        // a high surrogate needs a low one right after it, and vice versa
+        assert_eq!(err(r#""\ud83d""#).pos, 2);
+        assert_eq!(err(r#""\ud83dx""#).pos, 2);
+        assert_eq!(err(r#""\ud83d\u0041""#).pos, 2);
+        assert_eq!(err(r#""\ud83d\ud83d""#).pos, 2);
+        assert_eq!(err(r#""\ude00""#).msg, "invalid unicode escape");
+        assert_eq!(err(r#""\u+041""#).pos, 2);
+        assert_eq!(err(r#""\u00""#).pos, 2);
     }
 
     #[test]
-    fn test_remove_red_object() {
-        assert_eq!(remove_red_object("[1,2,3]"),
-                   "[1,2,3]".to_string());
+    fn test_parse_errors() {
+        let err = |text: &str| Json::parse(text).unwrap_err();
+        assert_eq!(
+            err("[1,2"),
+            JsonError {
+                pos: 4,
+                msg: "expected ',' or ']'".to_string()
+            }
+        );
+        assert_eq!(err(r#"{"a" 1}"#).pos, 5);
+        assert_eq!(err(r#"{1:2}"#).msg, "expected a key");
+        assert_eq!(err(r#"["abc"#).msg, "unterminated string");
+        assert_eq!(err(r#"["\q"]"#).pos, 3);
+        assert_eq!(err("[1.5]").pos, 2);
+        assert_eq!(err("[tru]").pos, 1);
+        assert_eq!(err("[] x").msg, "trailing characters");
+        assert_eq!(err("").msg, "unexpected end of input");
+    }
 
-        assert_eq!(remove_red_object(r#"[1,{"c":"red","b":2},3]"#),
-                   "[1,,3]".to_string());
+    struct Depth;
 
-        assert_eq!(remove_red_object(r#"{"d":"red","e":[1,2,3,4],"f":5}"#),
-                   "".to_string());
+    impl Visitor for Depth {
+        type Output = usize;
 
-        assert_eq!(remove_red_object(r#"[1,"red",5]"#),
-                   r#"[1,"red",5]"#.to_string());
+        fn leaf(&mut self, _: &Json) -> usize {
+            0
+        }
+
+        fn array(&mut self, items: Vec<usize>) -> usize {
+            1 + items.into_iter().max().unwrap_or(0)
+        }
 
-        assert_eq!(remove_red_object(r#"[1,{"c":"red","b":2},3,{"c":"red","b":2}]"#),
-                   r#"[1,,3,]"#.to_string());
+        fn object(&mut self, entries: Vec<(&str, usize)>) -> usize {
+            1 + entries.into_iter().map(|(_, d)| d).max().unwrap_or(0)
+        }
+    }
 
-        assert_eq!(remove_red_object(r#"[1,{"c":["red"],"b":2},3]"#),
-                   r#"[1,{"c":["red"],"b":2},3]"#.to_string());
+    #[test]
+    fn test_visitor() {
+        assert_eq!(parse("1").accept(&mut Depth), 0);
+        assert_eq!(parse(r#"[1,{"a":[[]]}]"#).accept(&mut Depth), 4);
     }
 }
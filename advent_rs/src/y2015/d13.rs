@@ -1,4 +1,7 @@
 use regex::Regex;
+use crate::{collect_keys, permutations, read_file};
+
+const FILE_NAME: &str = "data/2015/input13.txt";
 
 fn parse_line(line: &str) -> (&str, &str, i32) {
     let re = Regex::new(r"^(\w+) would (gain|lose) (\d+) happiness units by sitting next to (\w+).$").unwrap();
@@ -43,6 +46,30 @@ fn calc_happiness(arrange: &Vec<&str>, happiness: &Vec<(&str, &str, i32)>) -> i3
     h
 }
 
+fn max_happiness(happiness: &Vec<(&str, &str, i32)>) -> i32 {
+    permutations(&collect_keys(happiness))
+        .iter()
+        .map(|p| calc_happiness(p, happiness))
+        .max()
+        .unwrap()
+}
+
+pub(crate) fn quiz1() -> i32 {
+    let data = read_file(FILE_NAME);
+    max_happiness(&parse_input_data(&data))
+}
+
+// seats "ME" too, indifferent to everyone
+pub(crate) fn quiz2() -> i32 {
+    let data = read_file(FILE_NAME);
+    let mut happiness = parse_input_data(&data);
+    for guest in collect_keys(&happiness) {
+        happiness.push(("ME", guest, 0));
+        happiness.push((guest, "ME", 0));
+    }
+    max_happiness(&happiness)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,46 +77,12 @@ mod tests {
 
     #[test]
     fn quiz1() {
-        let data = read_file("../data/2015/input13.txt");
-        let happiness = parse_input_data(&data);
-        let guests = collect_keys(&happiness);
-        let perms = permutations(&guests);
-
-        let max_change = perms
-            .iter()
-            .map(|p| calc_happiness(p, &happiness))
-            .max()
-            .unwrap();
-
-        assert_eq!(max_change, 664); // 452
+        assert_eq!(super::quiz1(), 664);
     }
 
     #[test]
     fn quiz2() {
-        let me = "ME";
-
-        let data = read_file("../data/2015/input13.txt");
-        let mut happiness = parse_input_data(&data);
-        let mut guests = collect_keys(&happiness);
-
-        if !guests.insert(me) {
-            panic!("try other name");
-        }
-
-        for guest in &guests {
-            happiness.push((me, *guest, 0));
-            happiness.push((*guest, me, 0));
-        }
-
-        let perms = permutations(&guests);
-
-        let max_change = perms
-            .iter()
-            .map(|p| calc_happiness(p, &happiness))
-            .max()
-            .unwrap();
-
-        assert_eq!(max_change, 640);
+        assert_eq!(super::quiz2(), 640);
     }
 
     #[test]
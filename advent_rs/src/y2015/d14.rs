@@ -1,4 +1,7 @@
 use regex::Regex;
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2015/input14.txt";
 
 fn parse_line(line: &str) -> (i32, i32, i32) {
     let re = Regex::new(
@@ -52,6 +55,25 @@ fn calc_points(rules: &Vec<(i32, i32, i32)>, time: i32) -> Vec<i32> {
     points
 }
 
+const DURATION: i32 = 2503;
+
+fn load(text: &str) -> Vec<(i32, i32, i32)> {
+    text.lines().map(parse_line).collect()
+}
+
+pub(crate) fn quiz1() -> i32 {
+    load(&read_file(FILE_NAME))
+        .into_iter()
+        .map(|d| calc_dist(d, DURATION))
+        .max()
+        .unwrap()
+}
+
+pub(crate) fn quiz2() -> i32 {
+    let points = calc_points(&load(&read_file(FILE_NAME)), DURATION);
+    *points.iter().max().unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,14 +83,7 @@ mod tests {
 
     #[test]
     fn quiz2() {
-        let rules: Vec<(i32, i32, i32)> = read_file("../data/2015/input14.txt")
-            .lines()
-            .map(|line| parse_line(line))
-            .collect();
-
-        let points = calc_points(&rules, DURATION);
-
-        assert_eq!(points.iter().max().unwrap(), &1059);
+        assert_eq!(super::quiz2(), 1059);
     }
 
     #[test]
@@ -81,13 +96,7 @@ mod tests {
 
     #[test]
     fn quiz1() {
-        let distance = read_file("../data/2015/input14.txt")
-            .lines()
-            .map(|line| parse_line(line))
-            .map(|d| calc_dist(d, DURATION))
-            .max()
-            .unwrap();
-        assert_eq!(distance, 2655);
+        assert_eq!(super::quiz1(), 2655);
     }
 
     #[test]
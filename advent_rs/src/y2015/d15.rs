@@ -1,4 +1,7 @@
 use regex::Regex;
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2015/input15.txt";
 
 #[derive(PartialEq, Eq, Debug)]
 struct Ingredient(i32, i32, i32, i32, i32);
@@ -75,6 +78,14 @@ fn max_score2(ingredients: &Vec<Ingredient>) -> i32 {
         .unwrap()
 }
 
+pub(crate) fn quiz1() -> i32 {
+    max_score(&load_ingredients(&read_file(FILE_NAME)))
+}
+
+pub(crate) fn quiz2() -> i32 {
+    max_score2(&load_ingredients(&read_file(FILE_NAME)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,14 +101,12 @@ mod tests {
 
     #[test]
     fn quiz1() {
-        let ingredients = load_ingredients(&read_file("../data/2015/input15.txt"));
-        assert_eq!(max_score(&ingredients), 18965440);
+        assert_eq!(super::quiz1(), 18965440);
     }
 
     #[test]
     fn quiz2() {
-        let ingredients = load_ingredients(&read_file("../data/2015/input15.txt"));
-        assert_eq!(max_score2(&ingredients), 15862900);
+        assert_eq!(super::quiz2(), 15862900);
     }
 
     #[test]
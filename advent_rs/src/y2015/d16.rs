@@ -6,6 +6,8 @@ use regex::Regex;
 
 use Substance::*;
 
+const FILE_NAME: &str = "data/2015/input16.txt";
+
 #[derive(Hash, Eq, PartialEq)]
 enum Substance {
     Children,
@@ -96,47 +98,46 @@ fn match_mfcsam_2(sue: &Sue, menu: &HashMap<Substance, i32>) -> bool {
             })
 }
 
+fn create_menu() -> HashMap<Substance, i32> {
+    let mut menu = HashMap::new();
+    menu.insert(Children, 3);
+    menu.insert(Cats, 7);
+    menu.insert(Samoyeds, 2);
+    menu.insert(Pomeranians, 3);
+    menu.insert(Akitas, 0);
+    menu.insert(Vizslas, 0);
+    menu.insert(Goldfish, 5);
+    menu.insert(Trees, 3);
+    menu.insert(Cars, 2);
+    menu.insert(Perfumes, 1);
+    menu
+}
+
+fn find_sue<T: Fn(&Sue, &HashMap<Substance, i32>) -> bool>(matcher: T) -> i32 {
+    let sues: Vec<Sue> = read_file(FILE_NAME).lines().map(parse_line).collect();
+    query_find(&sues, &create_menu(), matcher).no
+}
+
+pub(crate) fn quiz1() -> i32 {
+    find_sue(match_mfcsam_1)
+}
+
+pub(crate) fn quiz2() -> i32 {
+    find_sue(match_mfcsam_2)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn create_menu() -> HashMap<Substance, i32> {
-        let mut menu = HashMap::new();
-        menu.insert(Children, 3);
-        menu.insert(Cats, 7);
-        menu.insert(Samoyeds, 2);
-        menu.insert(Pomeranians, 3);
-        menu.insert(Akitas, 0);
-        menu.insert(Vizslas, 0);
-        menu.insert(Goldfish, 5);
-        menu.insert(Trees, 3);
-        menu.insert(Cars, 2);
-        menu.insert(Perfumes, 1);
-        menu
-    }
-
     #[test]
     fn quiz1() {
-        let menu = create_menu();
-        let sues: Vec<Sue> = read_file("../data/2015/input16.txt")
-            .lines()
-            .map(parse_line)
-            .collect();
-
-        let result = query_find(&sues, &menu, match_mfcsam_1);
-        assert_eq!(result.no, 40);
+        assert_eq!(super::quiz1(), 40);
     }
 
     #[test]
     fn quiz2() {
-        let menu = create_menu();
-        let sues: Vec<Sue> = read_file("../data/2015/input16.txt")
-            .lines()
-            .map(parse_line)
-            .collect();
-
-        let result = query_find(&sues, &menu, match_mfcsam_2);
-        assert_eq!(result.no, 241);
+        assert_eq!(super::quiz2(), 241);
     }
 
     #[test]
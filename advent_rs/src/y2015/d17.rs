@@ -1,3 +1,7 @@
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2015/input17.txt";
+
 // TODO Iterator implementations
 
 // https://mitpress.mit.edu/sites/default/files/sicp/full-text/book/book-Z-H-11.html#%_idx_728
@@ -29,6 +33,21 @@ fn combinations_d(amount: i32, coins: &Vec<i32>) -> Vec<Vec<i32>> {
     }
 }
 
+fn load(text: &str) -> Vec<i32> {
+    text.lines().map(|line| line.parse().unwrap()).collect()
+}
+
+pub(crate) fn quiz1() -> usize {
+    combinations_n(150, &load(&read_file(FILE_NAME)))
+}
+
+// the number of ways to use as few containers as possible
+pub(crate) fn quiz2() -> usize {
+    let combs = combinations_d(150, &load(&read_file(FILE_NAME)));
+    let min_size = combs.iter().map(|vs| vs.len()).min().unwrap();
+    combs.iter().filter(|vs| vs.len() == min_size).count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,12 +55,7 @@ mod tests {
 
     #[test]
     fn quiz1() {
-        let containers: Vec<i32> = read_file("../data/2015/input17.txt")
-            .lines()
-            .map(|line| line.parse::<i32>().unwrap())
-            .collect();
-
-        assert_eq!(combinations_n(150, &containers), 4372);
+        assert_eq!(super::quiz1(), 4372);
     }
 
     #[test]
@@ -1,3 +1,7 @@
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2015/input18.txt";
+
 fn tick_one(current: i32, neighbors: i32) -> i32 {
     if current == 1 {
         if neighbors == 2 || neighbors == 3 {
@@ -59,6 +63,31 @@ fn tick_grid(size: (i32, i32), grid: Vec<i32>) -> Vec<i32> {
         .collect()
 }
 
+fn light_corners((rows, cols): (i32, i32), grid: &mut [i32]) {
+    for i in [0, rows - 1, (rows - 1) * cols, rows * cols - 1] {
+        grid[i as usize] = 1;
+    }
+}
+
+pub(crate) fn quiz1() -> i32 {
+    let (rows, cols, mut grid) = build_grid(&read_file(FILE_NAME));
+    for _ in 0..100 {
+        grid = tick_grid((rows, cols), grid);
+    }
+    grid.iter().sum()
+}
+
+// the four corners are stuck on
+pub(crate) fn quiz2() -> i32 {
+    let (rows, cols, mut grid) = build_grid(&read_file(FILE_NAME));
+    for _ in 0..100 {
+        light_corners((rows, cols), &mut grid);
+        grid = tick_grid((rows, cols), grid);
+    }
+    light_corners((rows, cols), &mut grid);
+    grid.iter().sum()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -66,34 +95,12 @@ mod test {
 
     #[test]
     fn quiz1() {
-        let text = read_file("../data/2015/input18.txt");
-        let (rows, cols, mut grid) = build_grid(&text);
-
-        for _ in 0..100 {
-            grid = tick_grid((rows, cols), grid);
-        }
-
-        assert_eq!(grid.iter().sum::<i32>(), 821);
+        assert_eq!(super::quiz1(), 821);
     }
 
     #[test]
     fn quiz2() {
-        let text = read_file("../data/2015/input18.txt");
-        let (rows, cols, mut grid) = build_grid(&text);
-
-        for _ in 0..100 {
-            grid[0] = 1;
-            grid[(rows - 1) as usize] = 1;
-            grid[((rows - 1) * cols) as usize] = 1;
-            grid[(rows * cols - 1) as usize] = 1;
-            grid = tick_grid((rows, cols), grid);
-        }
-
-        grid[0] = 1;
-        grid[(rows - 1) as usize] = 1;
-        grid[((rows - 1) * cols) as usize] = 1;
-        grid[(rows * cols - 1) as usize] = 1;
-        assert_eq!(grid.iter().sum::<i32>(), 886);
+        assert_eq!(super::quiz2(), 886);
     }
 
     #[test]
@@ -3,6 +3,8 @@
 use super::*;
 use regex::Regex;
 
+const FILE_NAME: &str = "data/2015/input19.txt";
+
 // https://www.reddit.com/r/adventofcode/comments/3xflz8/day_19_solutions/
 // 이 힌트가 없었으면 절대 풀지 못했을 것이다.
 
@@ -158,6 +160,33 @@ fn replace_all(replacements: &Vec<(&str, &str)>, molecule: &str) -> HashSet<Stri
         .collect()
 }
 
+pub(crate) fn quiz1() -> usize {
+    let contents = read_file(FILE_NAME);
+    let (replacements, molecule) = load_data(&contents);
+    replace_all(&replacements, molecule).len()
+}
+
+// collapses the molecule back to a single electron, one replacement a step
+pub(crate) fn quiz2() -> usize {
+    let (replacements, mut molecules) = load_data_q2(&read_file(FILE_NAME));
+    let mut step = 0;
+    loop {
+        let old = step;
+        while collapse_RnAr(&mut molecules, &replacements) {
+            step += 1;
+        }
+        while collapse_simple_inside_RnAr(&mut molecules, &replacements) {
+            step += 1;
+        }
+        if collapse_simple(&mut molecules, &replacements) {
+            step += 1;
+        }
+        if old == step {
+            return step;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,37 +210,7 @@ mod tests {
 
     #[test]
     fn quiz2() {
-        let contents = read_file("../data/2015/input19.txt");
-        let (replacements, mut molecules) = load_data_q2(&contents);
-
-        println!("START {}", format_molecule(&molecules.join("")));
-
-        let mut step = 0;
-        loop {
-            let old = step;
-
-            while collapse_RnAr(&mut molecules, &replacements) {
-                step += 1;
-                // println!("{} {}", step, format_molecule(&molecules.join("")));
-            }
-
-            while collapse_simple_inside_RnAr(&mut molecules, &replacements) {
-                step += 1;
-                // println!("{} {}", step, format_molecule(&molecules.join("")));
-            }
-
-            if collapse_simple(&mut molecules, &replacements) {
-                step += 1;
-                // println!("{} {}", step, format_molecule(&molecules.join("")));
-            }
-
-            if old == step {
-                break;
-            }
-        }
-
-        println!("Final {} {}", step, format_molecule(&molecules.join("")));
-        assert_eq!(step, 195);
+        assert_eq!(super::quiz2(), 195);
     }
 
     #[test]
@@ -241,9 +240,7 @@ mod tests {
 
     #[test]
     fn quiz1() {
-        let contents = read_file("../data/2015/input19.txt");
-        let (replacements, molecule) = load_data(&contents);
-        assert_eq!(replace_all(&replacements, molecule).len(), 509);
+        assert_eq!(super::quiz1(), 509);
     }
 
     #[test]
@@ -42,45 +42,37 @@ fn sum_of_factors(factors: &Vec<(i32, u32)>) -> i32 {
         .product()
 }
 
+const INPUT: i32 = 36000000;
+
+pub(crate) fn quiz1() -> i32 {
+    (2..)
+        .find(|&house| sum_of_factors(&prime_factorize(house)) * 10 >= INPUT)
+        .unwrap()
+}
+
+// each elf stops after 50 houses, so divisors below house / 50 drop out
+pub(crate) fn quiz2() -> i32 {
+    // 실패한 결과부터 시도
+    (776160..)
+        .find(|&house| {
+            let subtraction: i32 = (1..=(house - 1) / 50).filter(|n| house % n == 0).sum();
+            (sum_of_factors(&prime_factorize(house)) - subtraction) * 11 >= INPUT
+        })
+        .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     // #[test]
     fn quiz1() {
-        let mut house = 2;
-        let (_sum, r) = loop {
-            let factors = prime_factorize(house);
-            let presents = sum_of_factors(&factors);
-            let sum = presents * 10;
-            // println!("{} {} {}", house, presents, sum);
-            if sum >= 36000000 {
-                break (sum, house);
-            }
-            house += 1;
-        };
-
-        assert_eq!(r, 831600);
+        assert_eq!(super::quiz1(), 831600);
     }
 
     // #[test]
     fn quiz2() {
-        // 실패한 결과부터 시도
-        let mut house = 776160; // 2
-        let (_sum, r) = loop {
-            let factors = prime_factorize(house);
-            let subtraction: i32 = (1..=(house - 1) / 50).filter(|n| house % n == 0).sum();
-
-            let presents = sum_of_factors(&factors);
-            let sum = (presents - subtraction) * 11;
-            // println!("{} {} {} {}", house, presents, subtraction, sum);
-            if sum >= 36000000 {
-                break (sum, house);
-            }
-            house += 1;
-        };
-
-        assert_eq!(r, 884520);
+        assert_eq!(super::quiz2(), 884520);
     }
 
     // #[test]
@@ -136,38 +136,38 @@ impl Player {
     }
 }
 
+// the cheapest gear that still beats the boss
+pub(crate) fn quiz1() -> i32 {
+    players()
+        .into_iter()
+        .filter(|(_, p)| p.is_win(&BOSS))
+        .map(|(cost, _)| cost)
+        .min()
+        .unwrap()
+}
+
+// the most expensive gear that still loses
+pub(crate) fn quiz2() -> i32 {
+    players()
+        .into_iter()
+        .filter(|(_, p)| !p.is_win(&BOSS))
+        .map(|(cost, _)| cost)
+        .max()
+        .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn quiz1() {
-        let mut players = players();
-        players.sort_by(|p1, p2| (*p1).0.cmp(&((*p2).0)));
-
-        let (c, p) = players
-                .into_iter()
-                .skip_while(|(_, p)| !p.is_win(&BOSS))
-                .nth(0)
-                .unwrap();
-
-        println!("{} {:?}", c, p);
-        assert_eq!(c, 78);
+        assert_eq!(super::quiz1(), 78);
     }
 
     #[test]
     fn quiz2() {
-        let mut players = players();
-        players.sort_by(|p1, p2| (*p2).0.cmp(&((*p1).0)));
-
-        let (c, p) = players
-            .into_iter()
-            .skip_while(|(_, p)| p.is_win(&BOSS))
-            .nth(0)
-            .unwrap();
-
-        println!("{} {:?}", c, p);
-        assert_eq!(c, 148);
+        assert_eq!(super::quiz2(), 148);
     }
 
     #[test]
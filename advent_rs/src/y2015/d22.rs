@@ -1,99 +1,464 @@
-//
-/*
-struct Boss {
-    hit_points: i32,
-    damage: i32,
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+#[derive(Debug)]
+pub struct Spell {
+    pub name: &'static str,
+    pub cost: i32,
+    // 0 for instant spells, otherwise the number of turns the effect lasts
+    pub duration: u32,
+    pub damage: i32,
+    pub heal: i32,
+    pub armor: i32,
+    pub mana: i32,
 }
 
-const BOSS: Boss = Boss {
-    hit_points: 71,
-    damage: 10,
-};
+const fn spell(name: &'static str, cost: i32, duration: u32) -> Spell {
+    Spell {
+        name,
+        cost,
+        duration,
+        damage: 0,
+        heal: 0,
+        armor: 0,
+        mana: 0,
+    }
+}
 
-struct Wizard {
-    hit_points: i32,
-    armor: i32,
-    mana: i32,
-    magics: Vec<Magic>,
+pub const SPELLS: [Spell; 5] = [
+    Spell {
+        damage: 4,
+        ..spell("Magic Missile", 53, 0)
+    },
+    Spell {
+        damage: 2,
+        heal: 2,
+        ..spell("Drain", 73, 0)
+    },
+    Spell {
+        armor: 7,
+        ..spell("Shield", 113, 6)
+    },
+    Spell {
+        damage: 3,
+        ..spell("Poison", 173, 6)
+    },
+    Spell {
+        mana: 101,
+        ..spell("Recharge", 229, 5)
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct State {
+    pub player_hp: i32,
+    pub mana: i32,
+    pub boss_hp: i32,
+    pub timers: [u32; SPELLS.len()],
+    pub spent: i32,
 }
 
-struct Magic {
-    cost: i32,
-    turns: i32,
+impl State {
+    // the armor the effects still running provide, before they tick
+    fn armor(&self) -> i32 {
+        SPELLS
+            .iter()
+            .zip(&self.timers)
+            .filter(|(_, timer)| **timer > 0)
+            .map(|(spell, _)| spell.armor)
+            .sum()
+    }
 }
-// enum Magic {
-//     MagicMissle,
-//     Drain,
-//     Shield,
-//     Poison,
-//     Recharge,
-// }
 
-impl Wizard {
-    // fn spell(&mut self, magic: &Magic, other: &mut Boss) {
-    //     self.mana -= magic.cost();
-    //     magics.push(magic.clone());
-    // }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Won(State),
+    // the effects killed the boss at the start of the player turn, so the
+    // spell was never cast
+    WonBeforeCast(State),
+    Lost(State),
+    Ongoing(State),
+}
 
-    fn turn(&mut self, magic: Magic) {
-        // self.effect_spells();
-        // self.cast(magic);
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastError {
+    NotEnoughMana,
+    // an effect can't be cast again until its timer has run out
+    AlreadyActive,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Battle {
+    pub boss_damage: i32,
+    // the player loses 1 hit point at the start of each of their turns
+    pub hard: bool,
+}
 
-    fn effect_spell(&mut self) {}
+struct Log<'a>(Option<&'a mut Vec<String>>);
 
-    fn cast(&mut self, magic: Magic) {}
+impl Log<'_> {
+    fn push<F: FnOnce() -> String>(&mut self, line: F) {
+        if let Some(log) = self.0.as_mut() {
+            log.push(line());
+        }
+    }
 }
 
-fn player_turn(wizard: &mut Wizard, boss: &mut Boss, magics: &mut Vec<Magic>, magic: Magic) {
-    assert!(wizard.mana - magic.cost >= 0);
-    // assert!(magics.iter().find(|m| *m == magic).is_none());
+impl Battle {
+    pub fn start(&self, player_hp: i32, mana: i32, boss_hp: i32) -> State {
+        State {
+            player_hp,
+            mana,
+            boss_hp,
+            timers: [0; SPELLS.len()],
+            spent: 0,
+        }
+    }
 
-    for magic in magics.iter() {
-        // magic.effect(wizard, boss);
+    // ticks every active effect and returns the armor they provide
+    fn apply_effects(&self, state: &mut State, log: &mut Log) -> i32 {
+        let mut armor = 0;
+        for (spell, timer) in SPELLS.iter().zip(state.timers.iter_mut()) {
+            if *timer == 0 {
+                continue;
+            }
+            *timer -= 1;
+            state.boss_hp -= spell.damage;
+            state.mana += spell.mana;
+            armor += spell.armor;
+            log.push(|| {
+                let what = if spell.damage > 0 {
+                    format!("deals {} damage", spell.damage)
+                } else if spell.mana > 0 {
+                    format!("provides {} mana", spell.mana)
+                } else {
+                    format!("provides {} armor", spell.armor)
+                };
+                format!("{} {what}; its timer is now {timer}.", spell.name)
+            });
+        }
+        armor
     }
 
-    magics.retain(|magic| magic.turns > 0);
+    fn status(&self, state: &State, log: &mut Log) {
+        log.push(|| {
+            format!(
+                "- Player has {} hit points, {} armor, {} mana",
+                state.player_hp,
+                state.armor(),
+                state.mana
+            )
+        });
+        log.push(|| format!("- Boss has {} hit points", state.boss_hp));
+    }
 
-    wizard.mana -= magic.cost;
-    // magic.effect(wizard, boss);
+    // the start of the player turn up to casting, which may end the battle
+    fn open_turn(&self, state: &mut State, log: &mut Log) -> Option<Outcome> {
+        log.push(|| "-- Player turn --".to_string());
+        self.status(state, log);
+        if self.hard {
+            state.player_hp -= 1;
+            if state.player_hp <= 0 {
+                return Some(Outcome::Lost(*state));
+            }
+        }
+        self.apply_effects(state, log);
+        if state.boss_hp <= 0 {
+            log.push(|| "This kills the boss, and the player wins.".to_string());
+            return Some(Outcome::WonBeforeCast(*state));
+        }
+        None
+    }
+
+    fn round_logged(
+        &self,
+        state: &State,
+        spell: usize,
+        log: &mut Log,
+    ) -> Result<Outcome, CastError> {
+        let mut state = *state;
+        if let Some(outcome) = self.open_turn(&mut state, log) {
+            return Ok(outcome);
+        }
+
+        let s = &SPELLS[spell];
+        if state.timers[spell] > 0 {
+            return Err(CastError::AlreadyActive);
+        }
+        if state.mana < s.cost {
+            return Err(CastError::NotEnoughMana);
+        }
+        state.mana -= s.cost;
+        state.spent += s.cost;
+        if s.duration == 0 {
+            state.boss_hp -= s.damage;
+            state.player_hp += s.heal;
+            log.push(|| match s.heal {
+                0 => format!("Player casts {}, dealing {} damage.", s.name, s.damage),
+                heal => format!(
+                    "Player casts {}, dealing {} damage, and healing {heal} hit points.",
+                    s.name, s.damage
+                ),
+            });
+        } else {
+            state.timers[spell] = s.duration;
+            log.push(|| format!("Player casts {}.", s.name));
+        }
+        if state.boss_hp <= 0 {
+            log.push(|| "This kills the boss, and the player wins.".to_string());
+            return Ok(Outcome::Won(state));
+        }
+
+        log.push(String::new);
+        log.push(|| "-- Boss turn --".to_string());
+        self.status(&state, log);
+        let armor = self.apply_effects(&mut state, log);
+        if state.boss_hp <= 0 {
+            log.push(|| "This kills the boss, and the player wins.".to_string());
+            return Ok(Outcome::Won(state));
+        }
+        let damage = (self.boss_damage - armor).max(1);
+        state.player_hp -= damage;
+        log.push(|| match armor {
+            0 => format!("Boss attacks for {damage} damage."),
+            _ => format!(
+                "Boss attacks for {} - {armor} = {damage} damage.",
+                self.boss_damage
+            ),
+        });
+        log.push(String::new);
+        if state.player_hp <= 0 {
+            return Ok(Outcome::Lost(state));
+        }
+        Ok(Outcome::Ongoing(state))
+    }
+
+    // one player turn casting SPELLS[spell] followed by the boss turn
+    pub fn round(&self, state: &State, spell: usize) -> Result<Outcome, CastError> {
+        self.round_logged(state, spell, &mut Log(None))
+    }
+
+    pub fn replay(
+        &self,
+        start: &State,
+        spells: &[usize],
+    ) -> (Result<Outcome, CastError>, Vec<String>) {
+        let mut lines = vec![];
+        let mut log = Log(Some(&mut lines));
+        let mut outcome = Ok(Outcome::Ongoing(*start));
+        for &spell in spells {
+            outcome = match outcome {
+                Ok(Outcome::Ongoing(state)) => self.round_logged(&state, spell, &mut log),
+                _ => break,
+            };
+        }
+        // effects still running may finish the boss before another cast
+        if let Ok(Outcome::Ongoing(state)) = outcome {
+            let won = self.open_turn(&mut state.clone(), &mut Log(None));
+            if let Some(won @ Outcome::WonBeforeCast(_)) = won {
+                self.open_turn(&mut state.clone(), &mut log);
+                outcome = Ok(won);
+            }
+        }
+        (outcome, lines)
+    }
+
+    // best-first on mana spent, so the first win popped is the cheapest
+    pub fn cheapest_win(&self, start: &State) -> Option<Plan> {
+        let mut queue = BinaryHeap::new();
+        let mut seen = HashSet::new();
+        queue.push((Reverse(0), false, vec![], *start));
+
+        while let Some((Reverse(mana), won, spells, state)) = queue.pop() {
+            if won {
+                let (_, log) = self.replay(start, &spells);
+                return Some(Plan {
+                    mana,
+                    spells: spells.iter().map(|&s| SPELLS[s].name).collect(),
+                    log,
+                });
+            }
+            if !seen.insert(state) {
+                continue;
+            }
+            for spell in 0..SPELLS.len() {
+                let (won, next) = match self.round(&state, spell) {
+                    Ok(Outcome::WonBeforeCast(next)) => {
+                        // the same for every spell, and none of them is cast
+                        queue.push((Reverse(next.spent), true, spells.clone(), next));
+                        break;
+                    }
+                    Ok(Outcome::Won(next)) => (true, next),
+                    Ok(Outcome::Ongoing(next)) => (false, next),
+                    Ok(Outcome::Lost(_)) | Err(_) => continue,
+                };
+                let mut spells = spells.clone();
+                spells.push(spell);
+                queue.push((Reverse(next.spent), won, spells, next));
+            }
+        }
+        None
+    }
 }
 
-fn boss_turn(wizard: Wizard, boss: Boss, magics: Vec<Magic>) {}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub mana: i32,
+    pub spells: Vec<&'static str>,
+    pub log: Vec<String>,
+}
+
+const BOSS_HP: i32 = 71;
+const BOSS_DAMAGE: i32 = 10;
+
+fn quiz(hard: bool) -> i32 {
+    let battle = Battle {
+        boss_damage: BOSS_DAMAGE,
+        hard,
+    };
+    battle
+        .cheapest_win(&battle.start(50, 500, BOSS_HP))
+        .unwrap()
+        .mana
+}
+
+pub(crate) fn quiz1() -> i32 {
+    quiz(false)
+}
+
+pub(crate) fn quiz2() -> i32 {
+    quiz(true)
+}
 
 #[cfg(test)]
 mod tests {
 
-    #[derive(Debug, PartialEq)]
-    enum Hello {
-        World(i32),
-        Bob(i32, i32),
+    use super::*;
+
+    const NORMAL: Battle = Battle {
+        boss_damage: 8,
+        hard: false,
+    };
+
+    fn spells(names: &[&str]) -> Vec<usize> {
+        names
+            .iter()
+            .map(|n| SPELLS.iter().position(|s| s.name == *n).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_first_example() {
+        let start = NORMAL.start(10, 250, 13);
+        let (outcome, log) = NORMAL.replay(&start, &spells(&["Poison", "Magic Missile"]));
+        assert!(matches!(outcome, Ok(Outcome::Won(s)) if s.player_hp == 2 && s.spent == 226));
+        assert_eq!(
+            log[..10].to_vec(),
+            vec![
+                "-- Player turn --",
+                "- Player has 10 hit points, 0 armor, 250 mana",
+                "- Boss has 13 hit points",
+                "Player casts Poison.",
+                "",
+                "-- Boss turn --",
+                "- Player has 10 hit points, 0 armor, 77 mana",
+                "- Boss has 13 hit points",
+                "Poison deals 3 damage; its timer is now 5.",
+                "Boss attacks for 8 damage.",
+            ]
+        );
+        assert_eq!(
+            log.last().unwrap(),
+            "This kills the boss, and the player wins."
+        );
     }
 
     #[test]
-    fn is_ok() {
-        let mut h1 = Hello::World(10);
-        let mut h2 = Hello::Bob(1, 2);
-        let mut h3 = Hello::World(20);
+    fn test_second_example() {
+        let start = NORMAL.start(10, 250, 14);
+        let order = spells(&["Recharge", "Shield", "Drain", "Poison", "Magic Missile"]);
+        let (outcome, log) = NORMAL.replay(&start, &order);
+        assert!(matches!(outcome, Ok(Outcome::Won(s)) if s.player_hp == 1 && s.mana == 114));
+        assert!(log.contains(&"Boss attacks for 8 - 7 = 1 damage.".to_string()));
+        assert!(log.contains(
+            &"Player casts Drain, dealing 2 damage, and healing 2 hit points.".to_string()
+        ));
+        assert!(log.contains(&"Recharge provides 101 mana; its timer is now 0.".to_string()));
+    }
 
-        println!("h1 == h3 {:?}", h1 == h3);
+    #[test]
+    fn test_rules() {
+        let start = NORMAL.start(10, 250, 50);
+        let poison = spells(&["Poison"])[0];
+        let state = match NORMAL.round(&start, poison) {
+            Ok(Outcome::Ongoing(state)) => state,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(NORMAL.round(&state, poison), Err(CastError::AlreadyActive));
+        assert_eq!(NORMAL.round(&state, 4), Err(CastError::NotEnoughMana));
 
-        match h1 {
-            Hello::World(ref mut v) => { *v += 10; }
-            _ => {}
-        }
+        // a 1 turn timer runs out at the start of the player turn so it can be recast
+        let state = State {
+            timers: [0, 0, 0, 1, 0],
+            mana: 250,
+            ..state
+        };
+        assert!(NORMAL.round(&state, poison).is_ok());
 
-        match h2 {
-            Hello::Bob(ref mut a, ref mut b) => {
-                *a += 10;
-                *b += 10;
-            },
-            _ => {}
+        let hard = Battle {
+            hard: true,
+            ..NORMAL
+        };
+        let dying = NORMAL.start(1, 250, 50);
+        assert!(matches!(hard.round(&dying, 0), Ok(Outcome::Lost(_))));
+        assert!(matches!(NORMAL.round(&dying, 0), Ok(Outcome::Lost(_))));
+    }
+
+    #[test]
+    fn test_cheapest_win() {
+        let plan = NORMAL.cheapest_win(&NORMAL.start(10, 250, 13)).unwrap();
+        assert_eq!(plan.mana, 226);
+        assert_eq!(plan.spells, vec!["Poison", "Magic Missile"]);
+
+        let plan = NORMAL.cheapest_win(&NORMAL.start(10, 250, 14)).unwrap();
+        assert_eq!(plan.mana, 641);
+        assert_eq!(NORMAL.cheapest_win(&NORMAL.start(1, 10, 14)), None);
+    }
+
+    #[test]
+    fn test_plan_matches_replay() {
+        for boss_hp in 10..40 {
+            let start = NORMAL.start(50, 500, boss_hp);
+            let plan = NORMAL.cheapest_win(&start).unwrap();
+            let cost = plan
+                .spells
+                .iter()
+                .map(|n| SPELLS.iter().find(|s| s.name == *n).unwrap().cost)
+                .sum::<i32>();
+            assert_eq!(cost, plan.mana, "boss hp {}", boss_hp);
+
+            let (outcome, log) = NORMAL.replay(&start, &spells(&plan.spells));
+            match outcome {
+                Ok(Outcome::Won(s)) | Ok(Outcome::WonBeforeCast(s)) => {
+                    assert_eq!(s.spent, plan.mana)
+                }
+                other => panic!("boss hp {}: {:?}", boss_hp, other),
+            }
+            assert_eq!(log, plan.log);
+            assert_eq!(
+                log.last().unwrap(),
+                "This kills the boss, and the player wins."
+            );
         }
+    }
 
-        println!("{:?}", h1);
-        println!("{:?}", h2);
-        println!("h1 == h3 {:?}", h1 == h3);
+    #[test]
+    fn quiz1() {
+        assert_eq!(super::quiz1(), 1824);
+    }
+
+    #[test]
+    fn quiz2() {
+        assert_eq!(super::quiz2(), 1937);
     }
 }
-*/
\ No newline at end of file
@@ -1,9 +1,11 @@
 #![allow(dead_code)]
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::File;
 use std::io::Read;
 
+const FILE_NAME: &str = "data/2015/input7.txt";
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 enum Expr {
     Val(u16),
@@ -48,7 +50,7 @@ fn parse_expr(expr: &str) -> Expr {
     }
 }
 
-fn load_program_from_file(filename: &str) -> VecDeque<(String, Expr)> {
+fn load_program_from_file(filename: &str) -> Vec<(String, Expr)> {
     let mut f = File::open(filename).expect("file not found");
     let mut contents = String::new();
     f.read_to_string(&mut contents)
@@ -56,43 +58,221 @@ fn load_program_from_file(filename: &str) -> VecDeque<(String, Expr)> {
     load_program(&contents)
 }
 
-fn load_program(source: &str) -> VecDeque<(String, Expr)> {
-    let mut program: VecDeque<(String, Expr)> = VecDeque::new();
+fn load_program(source: &str) -> Vec<(String, Expr)> {
+    source
+        .lines()
+        .map(|line| {
+            let (expr, key) = parse_line(line);
+            (key.to_string(), parse_expr(expr))
+        })
+        .collect()
+}
+
+fn is_literal(operand: &str) -> bool {
+    operand.parse::<u16>().is_ok()
+}
 
-    for line in source.lines() {
-        let (expr, key) = parse_line(line);
-        let expr = parse_expr(expr);
-        program.push_back((key.to_string(), expr));
+impl Expr {
+    fn operands(&self) -> Vec<&str> {
+        match self {
+            Expr::Val(_) => vec![],
+            Expr::Var(a) | Expr::Not(a) | Expr::LShift(a, _) | Expr::RShift(a, _) => vec![a],
+            Expr::And(a, b) | Expr::Or(a, b) => vec![a, b],
+        }
     }
 
-    program
+    fn inputs(&self) -> Vec<&str> {
+        self.operands()
+            .into_iter()
+            .filter(|op| !is_literal(op))
+            .collect()
+    }
 }
 
-fn execute_program(program: &mut VecDeque<(String, Expr)>) -> HashMap<String, u16> {
-    let mut result: HashMap<String, u16> = HashMap::new();
+#[derive(Debug, Eq, PartialEq)]
+enum CircuitError {
+    Undefined(String),
+    Redefined(String),
+    Cycle(Vec<String>),
+}
 
-    while !program.is_empty() {
-        let (var, expr) = program.pop_front().unwrap();
+#[derive(Debug)]
+struct Circuit {
+    wires: HashMap<String, Expr>,
+    dependents: HashMap<String, Vec<String>>,
+    order: Vec<String>,
+    values: HashMap<String, u16>,
+}
 
-        if let Some(value) = eval_expr(&expr, &result) {
-            result.insert(var, value);
-        } else {
-            program.push_back((var, expr));
+impl Circuit {
+    fn new(program: Vec<(String, Expr)>) -> Result<Self, CircuitError> {
+        let mut wires = HashMap::new();
+        for (wire, expr) in program {
+            if wires.contains_key(&wire) {
+                return Err(CircuitError::Redefined(wire));
+            }
+            wires.insert(wire, expr);
         }
+
+        let mut names = wires.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+
+        let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+        for wire in names.iter() {
+            for input in wires[wire].inputs() {
+                if !wires.contains_key(input) {
+                    return Err(CircuitError::Undefined(input.to_string()));
+                }
+                dependents
+                    .entry(input.to_string())
+                    .or_default()
+                    .push(wire.clone());
+            }
+        }
+
+        let order = topological_order(&names, &wires, &dependents)?;
+        let mut circuit = Circuit {
+            wires,
+            dependents,
+            order,
+            values: HashMap::new(),
+        };
+        let all = circuit.order.clone();
+        circuit.evaluate(&all);
+        Ok(circuit)
+    }
+
+    fn evaluate(&mut self, wires: &[String]) {
+        for wire in wires {
+            let value = eval_expr(&self.wires[wire], &self.values)
+                .expect("inputs are evaluated in topological order");
+            self.values.insert(wire.clone(), value);
+        }
+    }
+
+    fn value(&self, wire: &str) -> Option<u16> {
+        self.values.get(wire).copied()
+    }
+
+    fn values(&self) -> &HashMap<String, u16> {
+        &self.values
     }
 
-    result
+    fn expr(&self, wire: &str) -> Option<&Expr> {
+        self.wires.get(wire)
+    }
+
+    fn order(&self) -> &[String] {
+        &self.order
+    }
+
+    fn dependents(&self, wire: &str) -> &[String] {
+        self.dependents.get(wire).map_or(&[], |ws| ws.as_slice())
+    }
+
+    // wires that have to be recomputed when `wire` changes, in evaluation order
+    fn downstream(&self, wire: &str) -> Vec<String> {
+        let mut affected = HashSet::new();
+        let mut stack = vec![wire.to_string()];
+        while let Some(w) = stack.pop() {
+            if affected.insert(w.clone()) {
+                stack.extend(self.dependents(&w).iter().cloned());
+            }
+        }
+        self.order
+            .iter()
+            .filter(|w| affected.contains(*w))
+            .cloned()
+            .collect()
+    }
+
+    fn override_wire(&mut self, wire: &str, value: u16) -> Result<Vec<String>, CircuitError> {
+        let old = self
+            .wires
+            .get_mut(wire)
+            .ok_or_else(|| CircuitError::Undefined(wire.to_string()))?;
+        let inputs = old
+            .inputs()
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>();
+        *old = Expr::Val(value);
+
+        for input in inputs {
+            if let Some(ws) = self.dependents.get_mut(&input) {
+                ws.retain(|w| w != wire);
+            }
+        }
+
+        let affected = self.downstream(wire);
+        self.evaluate(&affected);
+        Ok(affected)
+    }
+}
+
+fn topological_order(
+    names: &[String],
+    wires: &HashMap<String, Expr>,
+    dependents: &HashMap<String, Vec<String>>,
+) -> Result<Vec<String>, CircuitError> {
+    let mut pending: HashMap<&str, usize> = names
+        .iter()
+        .map(|w| (w.as_str(), wires[w].inputs().len()))
+        .collect();
+
+    let mut ready = names
+        .iter()
+        .filter(|w| pending[w.as_str()] == 0)
+        .cloned()
+        .collect::<VecDeque<_>>();
+
+    let mut order = vec![];
+    while let Some(wire) = ready.pop_front() {
+        for next in dependents.get(&wire).into_iter().flatten() {
+            let count = pending.get_mut(next.as_str()).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                ready.push_back(next.clone());
+            }
+        }
+        order.push(wire);
+    }
+
+    if order.len() < names.len() {
+        let start = names.iter().find(|w| pending[w.as_str()] > 0).unwrap();
+        return Err(CircuitError::Cycle(find_cycle(start, wires, &pending)));
+    }
+    Ok(order)
+}
+
+// walk unresolved inputs backwards from `start` until a wire repeats
+fn find_cycle(
+    start: &str,
+    wires: &HashMap<String, Expr>,
+    pending: &HashMap<&str, usize>,
+) -> Vec<String> {
+    let mut path: Vec<&str> = vec![];
+    let mut current = start;
+    loop {
+        if let Some(at) = path.iter().position(|w| *w == current) {
+            let mut cycle = path[at..].iter().map(|w| w.to_string()).collect::<Vec<_>>();
+            cycle.reverse();
+            return cycle;
+        }
+        path.push(current);
+        current = wires[current]
+            .inputs()
+            .into_iter()
+            .find(|w| pending[w] > 0)
+            .unwrap();
+    }
 }
 
 fn eval_operand(operand: &str, envs: &HashMap<String, u16>) -> Option<u16> {
     if let Ok(value) = operand.parse::<u16>() {
         Some(value)
     } else {
-        if let Some(v) = envs.get(operand) {
-            Some(*v)
-        } else {
-            None
-        }
+        envs.get(operand).copied()
     }
 }
 
@@ -128,37 +308,24 @@ fn eval_expr(expr: &Expr, envs: &HashMap<String, u16>) -> Option<u16> {
     }
 }
 
+pub(crate) fn quiz1() -> u16 {
+    let circuit = Circuit::new(load_program_from_file(FILE_NAME)).unwrap();
+    circuit.value("a").unwrap()
+}
+
+// the signal on a is fed back into b and the circuit settles again
+pub(crate) fn quiz2() -> u16 {
+    let mut circuit = Circuit::new(load_program_from_file(FILE_NAME)).unwrap();
+    let a = circuit.value("a").unwrap();
+    circuit.override_wire("b", a).unwrap();
+    circuit.value("a").unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_simple_eval() {
-        let mut program: VecDeque<(String, Expr)> = VecDeque::new();
-        program.push_back(("x".to_string(), Expr::Val(123)));
-        program.push_back(("y".to_string(), Expr::Val(456)));
-        program.push_back(("d".to_string(), Expr::And("x".to_string(), "y".to_string())));
-        program.push_back(("e".to_string(), Expr::Or("x".to_string(), "y".to_string())));
-        program.push_back(("f".to_string(), Expr::LShift("x".to_string(), 2)));
-        program.push_back(("g".to_string(), Expr::RShift("y".to_string(), 2)));
-        program.push_back(("h".to_string(), Expr::Not("x".to_string())));
-        program.push_back(("i".to_string(), Expr::Not("y".to_string())));
-
-        let result: HashMap<String, u16> = execute_program(&mut program);
-
-        assert_eq!(result.get("d"), Some(&72));
-        assert_eq!(result.get("e"), Some(&507));
-        assert_eq!(result.get("f"), Some(&492));
-        assert_eq!(result.get("g"), Some(&114));
-        assert_eq!(result.get("h"), Some(&65412));
-        assert_eq!(result.get("i"), Some(&65079));
-        assert_eq!(result.get("x"), Some(&123));
-        assert_eq!(result.get("y"), Some(&456));
-    }
-
-    #[test]
-    fn test_parse_and_eval() {
-        let input = "123 -> x
+    const SAMPLE: &str = "123 -> x
 456 -> y
 x AND y -> d
 x OR y -> e
@@ -167,38 +334,103 @@ y RSHIFT 2 -> g
 NOT x -> h
 NOT y -> i";
 
-        let mut program: VecDeque<(String, Expr)> = load_program(input);
+    #[test]
+    fn test_simple_eval() {
+        let program = vec![
+            ("h".to_string(), Expr::Not("x".to_string())),
+            ("i".to_string(), Expr::Not("y".to_string())),
+            ("x".to_string(), Expr::Val(123)),
+            ("y".to_string(), Expr::Val(456)),
+            ("d".to_string(), Expr::And("x".to_string(), "y".to_string())),
+            ("e".to_string(), Expr::Or("x".to_string(), "y".to_string())),
+            ("f".to_string(), Expr::LShift("x".to_string(), 2)),
+            ("g".to_string(), Expr::RShift("y".to_string(), 2)),
+        ];
+
+        let result = Circuit::new(program).unwrap();
+
+        assert_eq!(result.value("d"), Some(72));
+        assert_eq!(result.value("e"), Some(507));
+        assert_eq!(result.value("f"), Some(492));
+        assert_eq!(result.value("g"), Some(114));
+        assert_eq!(result.value("h"), Some(65412));
+        assert_eq!(result.value("i"), Some(65079));
+        assert_eq!(result.value("x"), Some(123));
+        assert_eq!(result.value("y"), Some(456));
+    }
+
+    #[test]
+    fn test_parse_and_eval() {
+        let result = Circuit::new(load_program(SAMPLE)).unwrap();
+
+        assert_eq!(result.value("d"), Some(72));
+        assert_eq!(result.value("e"), Some(507));
+        assert_eq!(result.value("f"), Some(492));
+        assert_eq!(result.value("g"), Some(114));
+        assert_eq!(result.value("h"), Some(65412));
+        assert_eq!(result.value("i"), Some(65079));
+        assert_eq!(result.value("x"), Some(123));
+        assert_eq!(result.value("y"), Some(456));
+        assert_eq!(result.values().len(), 8);
+    }
+
+    #[test]
+    fn test_inspect() {
+        let circuit = Circuit::new(load_program("1 AND x -> y\n3 -> x")).unwrap();
+        assert_eq!(circuit.order(), ["x", "y"]);
+        assert_eq!(circuit.dependents("x"), ["y"]);
+        assert_eq!(circuit.dependents("y"), [] as [String; 0]);
+        assert_eq!(
+            circuit.expr("y"),
+            Some(&Expr::And("1".to_string(), "x".to_string()))
+        );
+        assert_eq!(circuit.value("y"), Some(1));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Circuit::new(load_program("x AND z -> y\n3 -> x")).unwrap_err(),
+            CircuitError::Undefined("z".to_string())
+        );
+        assert_eq!(
+            Circuit::new(load_program("3 -> x\n4 -> x")).unwrap_err(),
+            CircuitError::Redefined("x".to_string())
+        );
+        assert_eq!(
+            Circuit::new(load_program("1 -> a\nb OR a -> c\nc -> d\nNOT d -> b")).unwrap_err(),
+            CircuitError::Cycle(vec!["c".to_string(), "d".to_string(), "b".to_string()])
+        );
+    }
 
-        let result: HashMap<String, u16> = execute_program(&mut program);
+    #[test]
+    fn test_override_wire() {
+        let mut circuit = Circuit::new(load_program(SAMPLE)).unwrap();
+        let affected = circuit.override_wire("y", 0).unwrap();
+
+        assert_eq!(affected, ["y", "d", "e", "g", "i"]);
+        assert_eq!(circuit.value("d"), Some(0));
+        assert_eq!(circuit.value("e"), Some(123));
+        assert_eq!(circuit.value("i"), Some(65535));
+        assert_eq!(circuit.value("h"), Some(65412));
 
-        assert_eq!(result.get("d"), Some(&72));
-        assert_eq!(result.get("e"), Some(&507));
-        assert_eq!(result.get("f"), Some(&492));
-        assert_eq!(result.get("g"), Some(&114));
-        assert_eq!(result.get("h"), Some(&65412));
-        assert_eq!(result.get("i"), Some(&65079));
-        assert_eq!(result.get("x"), Some(&123));
-        assert_eq!(result.get("y"), Some(&456));
+        circuit.override_wire("d", 7).unwrap();
+        assert_eq!(circuit.value("d"), Some(7));
+        assert_eq!(circuit.override_wire("y", 1).unwrap(), ["y", "e", "g", "i"]);
+        assert_eq!(circuit.value("d"), Some(7));
+        assert_eq!(
+            circuit.override_wire("q", 1),
+            Err(CircuitError::Undefined("q".to_string()))
+        );
     }
 
     #[test]
     fn run_quiz1() {
-        let mut program = load_program_from_file("../data/2015/input7.txt");
-        let result: HashMap<String, u16> = execute_program(&mut program);
-        assert_eq!(result.get("a"), Some(&46065));
+        assert_eq!(super::quiz1(), 46065);
     }
 
     #[test]
     fn run_qui2() {
-        // 1674 -> b
-        let mut program = load_program_from_file("../data/2015/input7.txt");
-        for (key, expr) in &mut program {
-            if key == "b" {
-                *expr = Expr::Val(46065);
-            }
-        }
-
-        let result: HashMap<String, u16> = execute_program(&mut program);
-        assert_eq!(result.get("a"), Some(&14134));
+        assert_eq!(super::quiz2(), 14134);
     }
 }
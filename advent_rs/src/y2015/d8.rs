@@ -1,140 +1,118 @@
 #![allow(dead_code)]
 
-use regex::Regex;
+use std::fmt;
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2015/input8.txt";
 
 /*
-처음에는 정규식으로 케이스들을 치환한 후 카운트 하였으나 계속 실패
-서브레딧 참고하여 단순히 케이스별로 카운트만 하였더니 정확하게 계산됨.
-replace 할 때 중복되는 케이스들이 존재하기 때문인것으로 판단됨.
-\ 가 여러번 들어갈 경우 replace 를 하나씩하면 문제가 됨
-\\x123 이런 경우 \\->\ 로 변경 된 결과를 다시 hex -> char 변경하면 결국 한문자만 남음
-하지만 이 경우는 \x123 이 최종 결과이어야 함.
+ *  The only escape sequences used are \\ (which represents a single backslash),
+ *  \" (which represents a lone double-quote character),
+ *  and \x plus two hexadecimal characters
+ *  (which represents a single character with that ASCII code).
  */
 
-fn unescape(text: &str) -> String {
-    let mut unescaped = strip_quote(text);
-    unescaped = replace_backslash(&unescaped);
-    unescaped = replace_hex_to_char(&unescaped);
-    replace_quote(&unescaped)
+#[derive(Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    pub pos: usize,
+    pub msg: String,
 }
 
-fn replace_hex_to_char(text: &str) -> String {
-    let re = Regex::new(r"\\x[0-9a-f][0-9a-f]").unwrap();
-    let mut result = String::new();
-    let mut start = 0;
-
-    for m in re.find_iter(text) {
-        if let Ok(_c) = u8::from_str_radix(&text[m.start() + 2..m.end()], 16) {
-            result.push_str(&text[start..m.start()]);
-            result.push('_'); // char::from_u32(c as u32).unwrap());
-            start = m.end();
-        }
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.msg, self.pos)
     }
-
-    result.push_str(&text[start..]);
-    result
-}
-
-fn strip_quote(text: &str) -> String {
-    replace_all(text, r#"(?m)^"|"$"#, "")
 }
 
-fn replace_backslash(text: &str) -> String {
-    replace_all(text, r#"\\\\"#, r#"\"#)
+fn error<T>(pos: usize, msg: &str) -> Result<T, DecodeError> {
+    Err(DecodeError {
+        pos,
+        msg: msg.to_string(),
+    })
 }
 
-fn replace_quote(text: &str) -> String {
-    replace_all(text, r#"\\""#, r#"""#)
+fn hex_digit(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|d| d as u8)
 }
 
-fn remove_whitespace(text: &str) -> String {
-    replace_all(text, r"\s+", "")
-}
-
-fn replace_all(text: &str, pat: &str, rep: &str) -> String {
-    let re = Regex::new(pat).unwrap();
-    re.replace_all(text, rep).to_string()
-}
-
-fn count_char(text: &str) -> u32 {
-    let mut count = 0;
-    for line in text.lines() {
-        let l = remove_whitespace(line);
-        if line != l {
-            println!("{} {}", line.len(), line);
-            println!("{} {}", l.len(), l);
-        }
-        count += line.len() as u32;
+// a quoted literal to the bytes it stands for
+pub fn decode(literal: &str) -> Result<Vec<u8>, DecodeError> {
+    let text = literal.as_bytes();
+    if text.first() != Some(&b'"') {
+        return error(0, "expected '\"'");
     }
-    count
-}
 
-fn unescaped_mem_count(line: &str) -> (usize, usize) {
-    let mut mem: usize = 0;
+    let mut bytes = vec![];
     let mut i = 1;
-
-    let chars: Vec<char> = line.chars().collect();
-
-    while i < chars.len() - 1 {
-        mem += 1;
-        match chars[i] {
-            '\\' => match chars[i + 1] {
-                'x' => i += 4,
-                '\\' | '"' => i += 2,
-                _ => panic!("Invalid format"),
+    loop {
+        match text.get(i) {
+            None => return error(i, "unterminated literal"),
+            Some(b'"') => break,
+            Some(b'\\') => match text.get(i + 1) {
+                Some(&c) if c == b'\\' || c == b'"' => {
+                    bytes.push(c);
+                    i += 2;
+                }
+                Some(b'x') => {
+                    let hi = text.get(i + 2).and_then(|&c| hex_digit(c));
+                    let lo = text.get(i + 3).and_then(|&c| hex_digit(c));
+                    match (hi, lo) {
+                        (Some(hi), Some(lo)) => bytes.push(hi * 16 + lo),
+                        _ => return error(i, "invalid hex escape"),
+                    }
+                    i += 4;
+                }
+                _ => return error(i, "invalid escape"),
             },
-            _ => i += 1,
+            Some(&c) => {
+                bytes.push(c);
+                i += 1;
+            }
         }
     }
 
-    (chars.len(), mem)
-}
-
-fn unescaped_mem_counts(text: &str) -> (usize, usize) {
-    let mut code: usize = 0;
-    let mut mem: usize = 0;
-
-    for line in text.lines() {
-        let (c, m) = unescaped_mem_count(line);
-        mem += m;
-        code += c;
+    if i + 1 != text.len() {
+        return error(i + 1, "trailing characters");
     }
-
-    (code, mem)
+    Ok(bytes)
 }
 
-fn escaped_mem_count(line: &str) -> (usize, usize) {
-    let mut escaped: usize = 0;
-
-    for c in line.chars() {
-        match c {
-            '"' | '\\' => escaped += 2,
-            _ => escaped += 1,
+// the shortest literal for the bytes, non-printable ones as \x escapes
+pub fn encode(bytes: &[u8]) -> String {
+    let mut literal = String::from("\"");
+    for &b in bytes {
+        match b {
+            b'"' => literal.push_str("\\\""),
+            b'\\' => literal.push_str("\\\\"),
+            0x20..=0x7e => literal.push(b as char),
+            _ => literal.push_str(&format!("\\x{b:02x}")),
         }
     }
+    literal.push('"');
+    literal
+}
 
-    (line.len(), escaped + 2)
+fn unescaped_mem_counts(text: &str) -> (usize, usize) {
+    text.lines().fold((0, 0), |(code, mem), line| {
+        (code + line.len(), mem + decode(line).unwrap().len())
+    })
 }
 
 fn escaped_mem_counts(text: &str) -> (usize, usize) {
-    let mut code: usize = 0;
-    let mut escaped: usize = 0;
-
-    for line in text.lines() {
-        let (c, e) = escaped_mem_count(line);
-        code += c;
-        escaped += e;
-    }
+    text.lines().fold((0, 0), |(code, escaped), line| {
+        (code + line.len(), escaped + encode(line.as_bytes()).len())
+    })
+}
 
-    (code, escaped)
+pub(crate) fn quiz1() -> usize {
+    let (code, mem) = unescaped_mem_counts(&read_file(FILE_NAME));
+    code - mem
 }
 
-/*
- *  The only escape sequences used are \\ (which represents a single backslash),
- *  \" (which represents a lone double-quote character),
- *  and \x plus two hexadecimal characters
- *  (which represents a single character with that ASCII code).
- */
+pub(crate) fn quiz2() -> usize {
+    let (code, escaped) = escaped_mem_counts(&read_file(FILE_NAME));
+    escaped - code
+}
 
 #[cfg(test)]
 mod tests {
@@ -142,125 +120,99 @@ mod tests {
     use super::*;
     use crate::*;
 
-    #[test]
-    fn test_string_slice() {
-        let x = "abcde".to_string();
-        assert_eq!(x[1..3], "bc".to_string());
-    }
+    const INPUT: &str = r#"""
+"abc"
+"aaa\"aaa"
+"\x27"
+"#;
 
     #[test]
-    fn test_regex() {
-        let regex = Regex::new(r"\\{2}").unwrap();
-        let text = r"hello\\world\!";
-        assert_eq!(regex.replace_all(text, "__"), r"hello__world\!");
-
-        let re2 = Regex::new(r"\\x\d\d").unwrap();
-        let text = r"hello\x27world";
-        assert_eq!(re2.replace_all(text, "'"), "hello'world");
+    fn test_decode() {
+        assert_eq!(decode(r#""""#), Ok(vec![]));
+        assert_eq!(decode(r#""aaa\"aaa""#), Ok(b"aaa\"aaa".to_vec()));
+        assert_eq!(decode(r#""\x27""#), Ok(vec![b'\'']));
+        assert_eq!(decode(r#""\xA2""#), Ok(vec![0xa2]));
+        // an escaped backslash followed by a literal x27
+        assert_eq!(decode(r#""\\x27""#), Ok(b"\\x27".to_vec()));
+        assert_eq!(decode(r#""\\\x27""#), Ok(b"\\'".to_vec()));
+        assert_eq!(
+            decode(r#""lnxluajtk\x8desue\\k\x7abhwokfhh""#),
+            Ok(b"lnxluajtk\x8desue\\kzbhwokfhh".to_vec())
+        );
     }
 
     #[test]
-    fn test_strip_quote() {
-        let text = r#""hello\"world""#;
-        assert_eq!(strip_quote(text), r#"hello\"world"#);
-    }
-
-    /*#[test]
-    fn test_hex_to_char() {
-        let text = r"hello\xa2world";
-        assert_eq!(replace_hex_to_char(text), "hello¢world".to_string());
-
-        let text = r"\x27hello\x27world\x27";
-        assert_eq!(replace_hex_to_char(text), "'hello'world'".to_string());
-
-        let text = r"hello world";
-        assert_eq!(replace_hex_to_char(text), "hello world".to_string());
+    fn test_decode_errors() {
+        assert_eq!(
+            decode(r#""ab\q""#),
+            Err(DecodeError {
+                pos: 3,
+                msg: "invalid escape".to_string()
+            })
+        );
+        assert_eq!(decode(r#""\x2g""#).unwrap_err().pos, 1);
+        assert_eq!(decode(r#""\x2""#).unwrap_err().msg, "invalid hex escape");
+        assert_eq!(decode("abc").unwrap_err().pos, 0);
+        assert_eq!(decode(r#""abc"#).unwrap_err().pos, 4);
+        assert_eq!(decode(r#""a"b""#).unwrap_err().msg, "trailing characters");
     }
 
     #[test]
-    fn test_unescaped() {
-        let text = r#""lregjexqaqgwloydxdsc\\o\"dnjfmjcu""#;
-        let expected = r#"lregjexqaqgwloydxdsc\o"dnjfmjcu"#;
-        assert_eq!(unescape(text), expected.to_string());
-
-        let text = r#""lnxluajtk\x8desue\\k\x7abhwokfhh""#;
-        let expected = "lnxluajtk\u{8d}esue\\kzbhwokfhh";
-        assert_eq!(unescape(text), expected.to_string());
-    }*/
+    fn test_encode() {
+        assert_eq!(encode(b""), r#""""#);
+        assert_eq!(encode(br#""abc""#), r#""\"abc\"""#);
+        assert_eq!(encode(br#""\x27""#), r#""\"\\x27\"""#);
+        assert_eq!(encode(&[0, b'a', 0xff]), r#""\x00a\xff""#);
+    }
 
     #[test]
-    fn test_count_chars() {
-        let text = r#"""
-"abc"
-"aaa\"aaa"
-"\x27"
-"#;
-        assert_eq!(count_char(text), 23);
-        assert_eq!(count_char(&unescape(text)), 11);
+    fn test_round_trip() {
+        // xorshift so the test stays deterministic without extra crates
+        let mut seed = 0x2545f4914f6cdd1du64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        for _ in 0..1000 {
+            let len = (next() % 32) as usize;
+            let bytes = (0..len)
+                .map(|_| match next() % 4 {
+                    0 => b'\\',
+                    1 => b'"',
+                    2 => b'x',
+                    _ => next() as u8,
+                })
+                .collect::<Vec<_>>();
+            let literal = encode(&bytes);
+            assert_eq!(decode(&literal), Ok(bytes));
+            assert_eq!(encode(&decode(&literal).unwrap()), literal);
+        }
     }
 
     #[test]
     fn test_unescaped_mem_counts() {
-        let text = r#"""
-"abc"
-"aaa\"aaa"
-"\x27"
-"#;
-
-        let (code, mem) = unescaped_mem_counts(text);
+        let (code, mem) = unescaped_mem_counts(INPUT);
         assert_eq!(code, 23);
         assert_eq!(mem, 11);
     }
 
     #[test]
     fn quiz1() {
-        let text = read_file("../data/2015/input8.txt");
-        let (code, mem) = unescaped_mem_counts(&text);
-        assert_eq!(code - mem, 1342); // 1345x, 1277x, 1342X
-    }
-
-    /*
-    #[test]
-    fn quiz1() {
-        let text = read_file("../data/2015/input8.txt");
-        let unescaped = unescape(&text);
-        write_file("../data/2015/input8-processed.txt", &unescaped);
-
-        let s1 = count_char(&text);
-        let s2 = count_char(&unescaped);
-
-        // assert_eq!(s1, 6202);
-        // assert_eq!(s2, 4857);
-
-        assert_eq!(s1 - s2, 1342); // 1345x, 1277x, 1342X
-    }
-     */
-
-    #[test]
-    fn test_escaped_mem_count() {
-        assert_eq!(escaped_mem_count(r#""""#), (2, 6));
-        assert_eq!(escaped_mem_count(r#""abc""#), (5, 9));
-        assert_eq!(escaped_mem_count(r#""aaa\"aaa""#), (10, 16));
-        assert_eq!(escaped_mem_count(r#""\x27""#), (6, 11));
+        assert_eq!(super::quiz1(), 1342);
     }
 
     #[test]
     fn test_escaped_mem_counts() {
-        let text = r#"""
-"abc"
-"aaa\"aaa"
-"\x27"
-"#;
-
-        let (code, escaped) = escaped_mem_counts(text);
+        let (code, escaped) = escaped_mem_counts(INPUT);
         assert_eq!(code, 23);
         assert_eq!(escaped, 42);
     }
 
     #[test]
     fn quiz2() {
-        let text = read_file("../data/2015/input8.txt");
-        let (code, escaped) = escaped_mem_counts(&text);
-        assert_eq!(escaped - code, 2074);
+        assert_eq!(super::quiz2(), 2074);
     }
 }
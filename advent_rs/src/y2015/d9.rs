@@ -1,6 +1,9 @@
 #![allow(dead_code)]
 
 use regex::Regex;
+use crate::{collect_keys, permutations, read_file};
+
+const FILE_NAME: &str = "data/2015/input9.txt";
 
 fn parse_line(line: &str) -> (String, String, i32) {
     let re = Regex::new(r"^(.+) to (.+) = (\d+)").unwrap();
@@ -35,6 +38,23 @@ fn calc_dist(items: &Vec<(String, String, i32)>, path: &Vec<String>) -> i32 {
     total_dist
 }
 
+fn route_lengths() -> Vec<i32> {
+    let items: Vec<(String, String, i32)> =
+        read_file(FILE_NAME).lines().map(parse_line).collect();
+    permutations(&collect_keys(&items))
+        .iter()
+        .map(|path| calc_dist(&items, path))
+        .collect()
+}
+
+pub(crate) fn quiz1() -> i32 {
+    route_lengths().into_iter().min().unwrap()
+}
+
+pub(crate) fn quiz2() -> i32 {
+    route_lengths().into_iter().max().unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,38 +62,12 @@ mod tests {
 
     #[test]
     fn quiz1() {
-        let items: Vec<(String, String, i32)> = read_file("../data/2015/input9.txt")
-            .lines()
-            .map(|line| parse_line(line))
-            .collect();
-
-        let locations = collect_keys(&items);
-
-        let max_dist = permutations(&locations)
-            .iter()
-            .map(|path| calc_dist(&items, &path))
-            .min()
-            .unwrap();
-
-        assert_eq!(207, max_dist);
+        assert_eq!(super::quiz1(), 207);
     }
 
     #[test]
     fn quiz2() {
-        let items: Vec<(String, String, i32)> = read_file("../data/2015/input9.txt")
-            .lines()
-            .map(|line| parse_line(line))
-            .collect();
-
-        let locations = collect_keys(&items);
-
-        let max_dist = permutations(&locations)
-            .iter()
-            .map(|path| calc_dist(&items, &path))
-            .max()
-            .unwrap();
-
-        assert_eq!(804, max_dist);
+        assert_eq!(super::quiz2(), 804);
     }
 
     #[test]
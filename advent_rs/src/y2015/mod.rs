@@ -18,3 +18,41 @@ mod d8;
 mod d9;
 
 use super::*;
+use crate::runner::Solver;
+
+pub(crate) fn solvers() -> Vec<Solver> {
+    vec![
+        Solver::new(2015, 7, 1, Some("46065"), || d7::quiz1().into()),
+        Solver::new(2015, 7, 2, Some("14134"), || d7::quiz2().into()),
+        Solver::new(2015, 8, 1, Some("1342"), || d8::quiz1().into()),
+        Solver::new(2015, 8, 2, Some("2074"), || d8::quiz2().into()),
+        Solver::new(2015, 9, 1, Some("207"), || d9::quiz1().into()),
+        Solver::new(2015, 9, 2, Some("804"), || d9::quiz2().into()),
+        Solver::new(2015, 10, 1, Some("492982"), || d10::quiz1().into()),
+        Solver::new(2015, 10, 2, Some("6989950"), || d10::quiz2().into()),
+        Solver::new(2015, 11, 1, Some("vzbxxyzz"), || d11::quiz1().into()),
+        Solver::new(2015, 11, 2, Some("vzcaabcc"), || d11::quiz2().into()),
+        Solver::new(2015, 12, 1, Some("191164"), || d12::quiz1().into()),
+        Solver::new(2015, 12, 2, Some("87842"), || d12::quiz2().into()),
+        Solver::new(2015, 13, 1, Some("664"), || d13::quiz1().into()),
+        Solver::new(2015, 13, 2, Some("640"), || d13::quiz2().into()),
+        Solver::new(2015, 14, 1, Some("2655"), || d14::quiz1().into()),
+        Solver::new(2015, 14, 2, Some("1059"), || d14::quiz2().into()),
+        Solver::new(2015, 15, 1, Some("18965440"), || d15::quiz1().into()),
+        Solver::new(2015, 15, 2, Some("15862900"), || d15::quiz2().into()),
+        Solver::new(2015, 16, 1, Some("40"), || d16::quiz1().into()),
+        Solver::new(2015, 16, 2, Some("241"), || d16::quiz2().into()),
+        Solver::new(2015, 17, 1, Some("4372"), || d17::quiz1().into()),
+        Solver::new(2015, 17, 2, None, || d17::quiz2().into()),
+        Solver::new(2015, 18, 1, Some("821"), || d18::quiz1().into()),
+        Solver::new(2015, 18, 2, Some("886"), || d18::quiz2().into()),
+        Solver::new(2015, 19, 1, Some("509"), || d19::quiz1().into()),
+        Solver::new(2015, 19, 2, Some("195"), || d19::quiz2().into()),
+        Solver::new(2015, 20, 1, Some("831600"), || d20::quiz1().into()),
+        Solver::new(2015, 20, 2, Some("884520"), || d20::quiz2().into()),
+        Solver::new(2015, 21, 1, Some("78"), || d21::quiz1().into()),
+        Solver::new(2015, 21, 2, Some("148"), || d21::quiz2().into()),
+        Solver::new(2015, 22, 1, Some("1824"), || d22::quiz1().into()),
+        Solver::new(2015, 22, 2, Some("1937"), || d22::quiz2().into()),
+    ]
+}
@@ -0,0 +1,55 @@
+use itertools::Itertools;
+
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input1.txt";
+
+fn load(input: &str) -> Vec<u32> {
+    input.lines().map(|line| line.parse().unwrap()).collect()
+}
+
+// product of the first n entries summing to 2020
+fn proc(input: &str, n: usize) -> u32 {
+    load(input)
+        .into_iter()
+        .combinations(n)
+        .find(|c| c.iter().sum::<u32>() == 2020)
+        .map(|c| c.iter().product())
+        .unwrap()
+}
+
+pub(crate) fn quiz1() -> u32 {
+    proc(&read_file(FILE_NAME), 2)
+}
+
+pub(crate) fn quiz2() -> u32 {
+    proc(&read_file(FILE_NAME), 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "1721
+979
+366
+299
+675
+1456";
+
+    #[test]
+    fn test_proc() {
+        assert_eq!(proc(INPUT, 2), 514579);
+        assert_eq!(proc(INPUT, 3), 241861950);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 259716);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 120637440);
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input10.txt";
+
+// the outlet, every adapter and the device, in joltage order
+fn chain(input: &str) -> Vec<u64> {
+    let mut joltages = input
+        .lines()
+        .map(|line| line.parse().unwrap())
+        .collect::<Vec<u64>>();
+    joltages.push(0);
+    joltages.sort();
+    joltages.push(joltages.last().unwrap() + 3);
+    joltages
+}
+
+fn diffs(input: &str) -> HashMap<u64, usize> {
+    let mut counts = HashMap::new();
+    for w in chain(input).windows(2) {
+        *counts.entry(w[1] - w[0]).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn proc1(input: &str) -> usize {
+    let counts = diffs(input);
+    counts.get(&1).unwrap_or(&0) * counts.get(&3).unwrap_or(&0)
+}
+
+pub(crate) fn quiz1() -> usize {
+    proc1(&read_file(FILE_NAME))
+}
+
+// ways[i] counts the arrangements ending at the i-th adapter
+fn proc2(input: &str) -> u64 {
+    let joltages = chain(input);
+    let mut ways = vec![0u64; joltages.len()];
+    ways[0] = 1;
+    for i in 1..joltages.len() {
+        ways[i] = (i.saturating_sub(3)..i)
+            .filter(|j| joltages[i] - joltages[*j] <= 3)
+            .map(|j| ways[j])
+            .sum();
+    }
+    *ways.last().unwrap()
+}
+
+pub(crate) fn quiz2() -> u64 {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT1: &str = "16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4";
+
+    const INPUT2: &str = "28\n33\n18\n42\n31\n14\n46\n20\n48\n47\n24\n23\n49\n45\n19\n38
+39\n11\n1\n32\n25\n35\n8\n17\n7\n9\n4\n2\n34\n10\n3";
+
+    #[test]
+    fn test_diffs() {
+        assert_eq!(diffs(INPUT1), HashMap::from([(1, 7), (3, 5)]));
+        assert_eq!(proc1(INPUT2), 22 * 10);
+    }
+
+    #[test]
+    fn test_proc2() {
+        assert_eq!(proc2("1\n2\n3"), 4);
+        assert_eq!(proc2("2\n4"), 1);
+        assert_eq!(proc2(INPUT1), 8);
+        assert_eq!(proc2(INPUT2), 19208);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 1980);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 4628074479616);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input11.txt";
+
+const DIRS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Layout {
+    seats: Vec<u8>,
+    rows: i32,
+    cols: i32,
+}
+
+impl Layout {
+    fn parse(input: &str) -> Self {
+        let lines = input.lines().collect::<Vec<_>>();
+        Layout {
+            seats: lines.iter().flat_map(|l| l.bytes()).collect(),
+            rows: lines.len() as i32,
+            cols: lines[0].len() as i32,
+        }
+    }
+
+    fn get(&self, row: i32, col: i32) -> Option<u8> {
+        if (0..self.rows).contains(&row) && (0..self.cols).contains(&col) {
+            Some(self.seats[(row * self.cols + col) as usize])
+        } else {
+            None
+        }
+    }
+
+    // the first seat seen in each direction, looking at most `reach` cells away
+    fn visible(&self, row: i32, col: i32, reach: i32) -> usize {
+        DIRS.iter()
+            .filter(|(dr, dc)| {
+                (1..=reach)
+                    .map(|k| self.get(row + dr * k, col + dc * k))
+                    .take_while(|cell| cell.is_some())
+                    .flatten()
+                    .find(|cell| *cell != b'.')
+                    == Some(b'#')
+            })
+            .count()
+    }
+
+    fn next(&self, reach: i32, tolerance: usize) -> Layout {
+        let mut seats = self.seats.clone();
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let idx = (row * self.cols + col) as usize;
+                seats[idx] = match self.seats[idx] {
+                    b'L' if self.visible(row, col, reach) == 0 => b'#',
+                    b'#' if self.visible(row, col, reach) >= tolerance => b'L',
+                    seat => seat,
+                };
+            }
+        }
+        Layout { seats, ..*self }
+    }
+
+    fn occupied(&self) -> usize {
+        self.seats.iter().filter(|s| **s == b'#').count()
+    }
+}
+
+fn evolve(input: &str, reach: i32, tolerance: usize) -> usize {
+    let mut layout = Layout::parse(input);
+    loop {
+        let next = layout.next(reach, tolerance);
+        if next == layout {
+            return layout.occupied();
+        }
+        layout = next;
+    }
+}
+
+fn proc1(input: &str) -> usize {
+    evolve(input, 1, 4)
+}
+
+pub(crate) fn quiz1() -> usize {
+    proc1(&read_file(FILE_NAME))
+}
+
+fn proc2(input: &str) -> usize {
+    evolve(input, i32::MAX, 5)
+}
+
+pub(crate) fn quiz2() -> usize {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+    #[test]
+    fn test_next() {
+        let empty = Layout::parse("LLL\nLLL\nLLL");
+        let full = Layout::parse("###\n###\n###");
+        assert_eq!(empty.next(1, 4), full);
+        assert_eq!(full.next(1, 4), Layout::parse("#L#\nLLL\n#L#"));
+        assert_eq!(
+            Layout::parse("L.L\nLLL\nL.L").next(1, 4),
+            Layout::parse("#.#\n###\n#.#")
+        );
+    }
+
+    #[test]
+    fn test_visible() {
+        let layout = Layout::parse(
+            ".......#.
+...#.....
+.#.......
+.........
+..#L....#
+....#....
+.........
+#........
+...#.....",
+        );
+        assert_eq!(layout.visible(4, 3, i32::MAX), 8);
+        assert_eq!(layout.visible(4, 3, 1), 2);
+    }
+
+    #[test]
+    fn test_proc() {
+        assert_eq!(proc1(INPUT), 37);
+        assert_eq!(proc2(INPUT), 26);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 2113);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 1865);
+    }
+}
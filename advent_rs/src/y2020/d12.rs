@@ -0,0 +1,148 @@
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input12.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Ship {
+    east: i32,
+    north: i32,
+    // heading for part 1, waypoint for part 2
+    we: i32,
+    wn: i32,
+}
+
+const SHIP: Ship = Ship {
+    east: 0,
+    north: 0,
+    we: 10,
+    wn: 1,
+};
+
+fn parse_line(line: &str) -> (char, i32) {
+    let (action, value) = line.split_at(1);
+    (action.chars().next().unwrap(), value.parse().unwrap())
+}
+
+// clockwise by a multiple of 90 degrees
+fn rotate((e, n): (i32, i32), degrees: i32) -> (i32, i32) {
+    match degrees.rem_euclid(360) {
+        0 => (e, n),
+        90 => (n, -e),
+        180 => (-e, -n),
+        270 => (-n, e),
+        _ => panic!("can't turn {} degrees", degrees),
+    }
+}
+
+// N/S/E/W move the ship itself when `waypoint` is false
+fn navigate(ship: Ship, (action, value): (char, i32), waypoint: bool) -> Ship {
+    let shift = |ship: Ship, de: i32, dn: i32| {
+        if waypoint {
+            Ship {
+                we: ship.we + de,
+                wn: ship.wn + dn,
+                ..ship
+            }
+        } else {
+            Ship {
+                east: ship.east + de,
+                north: ship.north + dn,
+                ..ship
+            }
+        }
+    };
+    let turn = |ship: Ship, degrees: i32| {
+        let (we, wn) = rotate((ship.we, ship.wn), degrees);
+        Ship { we, wn, ..ship }
+    };
+    match action {
+        'N' => shift(ship, 0, value),
+        'S' => shift(ship, 0, -value),
+        'E' => shift(ship, value, 0),
+        'W' => shift(ship, -value, 0),
+        'R' => turn(ship, value),
+        'L' => turn(ship, -value),
+        'F' => Ship {
+            east: ship.east + ship.we * value,
+            north: ship.north + ship.wn * value,
+            ..ship
+        },
+        _ => panic!("unknown action {}", action),
+    }
+}
+
+fn proc(input: &str, start: Ship, waypoint: bool) -> i32 {
+    let ship = input
+        .lines()
+        .map(parse_line)
+        .fold(start, |ship, action| navigate(ship, action, waypoint));
+    ship.east.abs() + ship.north.abs()
+}
+
+fn proc1(input: &str) -> i32 {
+    proc(
+        input,
+        Ship {
+            we: 1,
+            wn: 0,
+            ..SHIP
+        },
+        false,
+    )
+}
+
+pub(crate) fn quiz1() -> i32 {
+    proc1(&read_file(FILE_NAME))
+}
+
+fn proc2(input: &str) -> i32 {
+    proc(input, SHIP, true)
+}
+
+pub(crate) fn quiz2() -> i32 {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "F10
+N3
+F7
+R90
+F11";
+
+    #[test]
+    fn test_rotate() {
+        assert_eq!(rotate((10, 4), 90), (4, -10));
+        assert_eq!(rotate((10, 4), -270), (4, -10));
+        assert_eq!(rotate(rotate((10, 4), 90), 90), (-10, -4));
+    }
+
+    #[test]
+    fn test_navigate() {
+        let ship = navigate(SHIP, ('F', 10), true);
+        assert_eq!((ship.east, ship.north), (100, 10));
+        let ship = navigate(ship, ('N', 3), true);
+        assert_eq!((ship.we, ship.wn), (10, 4));
+        let ship = navigate(navigate(ship, ('F', 7), true), ('R', 90), true);
+        assert_eq!((ship.east, ship.north, ship.we, ship.wn), (170, 38, 4, -10));
+    }
+
+    #[test]
+    fn test_proc() {
+        assert_eq!(proc1(INPUT), 25);
+        assert_eq!(proc2(INPUT), 286);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 1152);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 58637);
+    }
+}
@@ -0,0 +1,71 @@
+// the notes were never saved to a file, they live in the Clojure solution
+const INPUT: &str = "1000495
+19,x,x,x,x,x,x,x,x,41,x,x,x,x,x,x,x,x,x,521,x,x,x,x,x,x,x,23,x,x,x,x,x,x,x,x,17,x,x,x,x,x,x,x,x,x,x,x,29,x,523,x,x,x,x,x,37,x,x,x,x,x,x,13";
+
+// (offset in the list, bus id)
+fn load(input: &str) -> (u64, Vec<(u64, u64)>) {
+    let mut lines = input.lines();
+    let depart = lines.next().unwrap().parse().unwrap();
+    let buses = lines
+        .next()
+        .unwrap()
+        .split(',')
+        .enumerate()
+        .filter_map(|(i, id)| id.parse().ok().map(|id| (i as u64, id)))
+        .collect();
+    (depart, buses)
+}
+
+fn proc1(input: &str) -> u64 {
+    let (depart, buses) = load(input);
+    let (wait, id) = buses
+        .iter()
+        .map(|(_, id)| ((id - depart % id) % id, *id))
+        .min()
+        .unwrap();
+    wait * id
+}
+
+pub(crate) fn quiz1() -> u64 {
+    proc1(INPUT)
+}
+
+// sieve one bus at a time, stepping by the product of the ids already lined up
+fn proc2(input: &str) -> u64 {
+    let (_, buses) = load(input);
+    let (t, _) = buses.iter().fold((0, 1), |(mut t, step), (offset, id)| {
+        while (t + offset) % id != 0 {
+            t += step;
+        }
+        (t, step * id)
+    });
+    t
+}
+
+pub(crate) fn quiz2() -> u64 {
+    proc2(INPUT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proc() {
+        let input = "939\n7,13,x,x,59,x,31,19";
+        assert_eq!(proc1(input), 295);
+        assert_eq!(proc2(input), 1068781);
+        assert_eq!(proc2("0\n17,x,13,19"), 3417);
+        assert_eq!(proc2("0\n1789,37,47,1889"), 1202161486);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 2092);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 702970661767766);
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input14.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ins {
+    // (bits forced to 1, bits forced to 0, floating bits)
+    Mask(u64, u64, u64),
+    Mem(u64, u64),
+}
+
+fn parse_mask(mask: &str) -> Ins {
+    let bits = |c: char| mask.chars().fold(0, |acc, m| acc << 1 | u64::from(m == c));
+    Ins::Mask(bits('1'), bits('0'), bits('X'))
+}
+
+fn load(input: &str) -> Vec<Ins> {
+    let mem = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
+    input
+        .lines()
+        .map(|line| match line.strip_prefix("mask = ") {
+            Some(mask) => parse_mask(mask),
+            None => {
+                let cas = mem.captures(line).unwrap();
+                Ins::Mem(cas[1].parse().unwrap(), cas[2].parse().unwrap())
+            }
+        })
+        .collect()
+}
+
+// every address the floating bits can produce
+fn addresses(addr: u64, floating: u64) -> Vec<u64> {
+    (0..64)
+        .filter(|bit| floating >> bit & 1 == 1)
+        .fold(vec![addr & !floating], |addrs, bit| {
+            addrs.iter().flat_map(|a| [*a, *a | 1 << bit]).collect()
+        })
+}
+
+fn run(input: &str, decoder_v2: bool) -> u64 {
+    let mut memory = HashMap::new();
+    let mut mask = (0, 0, 0);
+    for ins in load(input) {
+        match ins {
+            Ins::Mask(ones, zeros, floating) => mask = (ones, zeros, floating),
+            Ins::Mem(addr, value) if decoder_v2 => {
+                for a in addresses(addr | mask.0, mask.2) {
+                    memory.insert(a, value);
+                }
+            }
+            Ins::Mem(addr, value) => {
+                memory.insert(addr, (value | mask.0) & !mask.1);
+            }
+        }
+    }
+    memory.values().sum()
+}
+
+fn proc1(input: &str) -> u64 {
+    run(input, false)
+}
+
+pub(crate) fn quiz1() -> u64 {
+    proc1(&read_file(FILE_NAME))
+}
+
+fn proc2(input: &str) -> u64 {
+    run(input, true)
+}
+
+pub(crate) fn quiz2() -> u64 {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proc1() {
+        let input = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+        assert_eq!(proc1(input), 165);
+    }
+
+    #[test]
+    fn test_addresses() {
+        let mut addrs = addresses(0b011010, 0b100001);
+        addrs.sort();
+        assert_eq!(addrs, vec![26, 27, 58, 59]);
+    }
+
+    #[test]
+    fn test_proc2() {
+        let input = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+        assert_eq!(proc2(input), 208);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 11884151942312);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 2625449018811);
+    }
+}
@@ -0,0 +1,50 @@
+const INPUT: [u32; 6] = [1, 12, 0, 20, 8, 16];
+
+// last[n] is the 1-based turn n was last spoken on, 0 if never
+fn play(numbers: &[u32], turns: usize) -> u32 {
+    let size = numbers.iter().map(|n| *n as usize + 1).max().unwrap();
+    let mut last = vec![0u32; turns.max(size)];
+    for (turn, n) in numbers[..numbers.len() - 1].iter().enumerate() {
+        last[*n as usize] = turn as u32 + 1;
+    }
+
+    let mut spoken = *numbers.last().unwrap();
+    for turn in numbers.len()..turns {
+        let prev = last[spoken as usize];
+        last[spoken as usize] = turn as u32;
+        spoken = if prev == 0 { 0 } else { turn as u32 - prev };
+    }
+    spoken
+}
+
+pub(crate) fn quiz1() -> u32 {
+    play(&INPUT, 2020)
+}
+
+pub(crate) fn quiz2() -> u32 {
+    play(&INPUT, 30000000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_play() {
+        let spoken = (4..=10).map(|t| play(&[0, 3, 6], t)).collect::<Vec<_>>();
+        assert_eq!(spoken, vec![0, 3, 3, 1, 0, 4, 0]);
+        assert_eq!(play(&[0, 3, 6], 2020), 436);
+        assert_eq!(play(&[1, 3, 2], 2020), 1);
+        assert_eq!(play(&[3, 1, 2], 2020), 1836);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 273);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 47205);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input16.txt";
+
+#[derive(Debug)]
+struct Rule {
+    name: String,
+    ranges: [(u64, u64); 2],
+}
+
+impl Rule {
+    fn allows(&self, n: u64) -> bool {
+        self.ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&n))
+    }
+}
+
+type Ticket = Vec<u64>;
+
+fn parse_ints(line: &str) -> Vec<u64> {
+    line.split(|c: char| !c.is_ascii_digit())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().unwrap())
+        .collect()
+}
+
+fn load(input: &str) -> (Vec<Rule>, Ticket, Vec<Ticket>) {
+    let blocks = input.split("\n\n").collect::<Vec<_>>();
+    let rules = blocks[0]
+        .lines()
+        .map(|line| {
+            let (name, ranges) = line.split_once(':').unwrap();
+            let ns = parse_ints(ranges);
+            Rule {
+                name: name.to_string(),
+                ranges: [(ns[0], ns[1]), (ns[2], ns[3])],
+            }
+        })
+        .collect();
+    let yours = parse_ints(blocks[1].lines().nth(1).unwrap());
+    let nearby = blocks[2].lines().skip(1).map(parse_ints).collect();
+    (rules, yours, nearby)
+}
+
+fn proc1(input: &str) -> u64 {
+    let (rules, _, nearby) = load(input);
+    nearby
+        .iter()
+        .flatten()
+        .filter(|n| !rules.iter().any(|r| r.allows(**n)))
+        .sum()
+}
+
+pub(crate) fn quiz1() -> u64 {
+    proc1(&read_file(FILE_NAME))
+}
+
+// the rule name for every ticket column
+fn field_order(input: &str) -> Vec<String> {
+    let (rules, yours, nearby) = load(input);
+    let valid = nearby
+        .into_iter()
+        .filter(|t| t.iter().all(|n| rules.iter().any(|r| r.allows(*n))))
+        .chain(std::iter::once(yours.clone()))
+        .collect::<Vec<_>>();
+
+    // candidates[col] holds the rules every value in the column satisfies
+    let mut candidates = (0..yours.len())
+        .map(|col| {
+            (0..rules.len())
+                .filter(|r| valid.iter().all(|t| rules[*r].allows(t[col])))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut order = vec![None; yours.len()];
+    while let Some(col) = (0..candidates.len()).find(|c| candidates[*c].len() == 1) {
+        let rule = candidates[col][0];
+        order[col] = Some(rules[rule].name.clone());
+        candidates.iter_mut().for_each(|c| c.retain(|r| *r != rule));
+    }
+    order.into_iter().map(|name| name.unwrap()).collect()
+}
+
+fn proc2(input: &str) -> u64 {
+    let (_, yours, _) = load(input);
+    field_order(input)
+        .iter()
+        .zip(yours)
+        .filter(|(name, _)| name.starts_with("departure"))
+        .map(|(_, n)| n)
+        .product()
+}
+
+pub(crate) fn quiz2() -> u64 {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proc1() {
+        let input = "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+        assert_eq!(proc1(input), 71);
+    }
+
+    #[test]
+    fn test_field_order() {
+        let input = "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+        assert_eq!(field_order(input), vec!["row", "class", "seat"]);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 27870);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 3173135507987);
+    }
+}
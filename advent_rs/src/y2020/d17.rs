@@ -0,0 +1,110 @@
+use std::collections::{HashMap, HashSet};
+
+const INPUT: &str = "..##.#.#
+.#####..
+#.....##
+##.##.#.
+..#...#.
+.#..##..
+.#...#.#
+#..##.##";
+
+type Cube = Vec<i32>;
+
+// active cubes of the slice, lifted into `dims` dimensions
+fn load(input: &str, dims: usize) -> HashSet<Cube> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| {
+                    let mut cube = vec![0; dims];
+                    cube[0] = x as i32;
+                    cube[1] = y as i32;
+                    cube
+                })
+        })
+        .collect()
+}
+
+fn neighbors(cube: &Cube) -> Vec<Cube> {
+    let mut all = vec![vec![]];
+    for c in cube {
+        all = all
+            .iter()
+            .flat_map(|prefix: &Vec<i32>| {
+                (c - 1..=c + 1).map(move |v| {
+                    let mut next = prefix.clone();
+                    next.push(v);
+                    next
+                })
+            })
+            .collect();
+    }
+    all.retain(|n| n != cube);
+    all
+}
+
+fn cycle(active: &HashSet<Cube>) -> HashSet<Cube> {
+    let mut counts: HashMap<Cube, usize> = HashMap::new();
+    for cube in active {
+        for n in neighbors(cube) {
+            *counts.entry(n).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .filter(|(cube, n)| *n == 3 || (*n == 2 && active.contains(cube)))
+        .map(|(cube, _)| cube)
+        .collect()
+}
+
+fn boot(input: &str, dims: usize) -> usize {
+    let mut active = load(input, dims);
+    for _ in 0..6 {
+        active = cycle(&active);
+    }
+    active.len()
+}
+
+pub(crate) fn quiz1() -> usize {
+    boot(INPUT, 3)
+}
+
+pub(crate) fn quiz2() -> usize {
+    boot(INPUT, 4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = ".#.
+..#
+###";
+
+    #[test]
+    fn test_neighbors() {
+        assert_eq!(neighbors(&vec![0, 0, 0]).len(), 26);
+        assert_eq!(neighbors(&vec![0, 0, 0, 0]).len(), 80);
+    }
+
+    #[test]
+    fn test_boot() {
+        assert_eq!(boot(SAMPLE, 3), 112);
+        assert_eq!(boot(SAMPLE, 4), 848);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 213);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 1624);
+    }
+}
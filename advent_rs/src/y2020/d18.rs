@@ -0,0 +1,146 @@
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input18.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Num(u64),
+    Add,
+    Mul,
+    Open,
+    Close,
+}
+
+fn tokenize(line: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    for c in line.chars() {
+        match c {
+            '+' => tokens.push(Token::Add),
+            '*' => tokens.push(Token::Mul),
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            ' ' => {}
+            _ => {
+                let d = c.to_digit(10).unwrap() as u64;
+                match tokens.last_mut() {
+                    Some(Token::Num(n)) => *n = *n * 10 + d,
+                    _ => tokens.push(Token::Num(d)),
+                }
+            }
+        }
+    }
+    tokens
+}
+
+// precedence climbing; `add_first` gives + a higher precedence than *
+struct Evaluator {
+    tokens: Vec<Token>,
+    pos: usize,
+    add_first: bool,
+}
+
+impl Evaluator {
+    fn precedence(&self, op: Token) -> Option<u8> {
+        match op {
+            Token::Add => Some(if self.add_first { 2 } else { 1 }),
+            Token::Mul => Some(1),
+            _ => None,
+        }
+    }
+
+    fn operand(&mut self) -> u64 {
+        let token = self.tokens[self.pos];
+        self.pos += 1;
+        match token {
+            Token::Num(n) => n,
+            Token::Open => {
+                let v = self.expr(0);
+                self.pos += 1;
+                v
+            }
+            _ => panic!("unexpected token {:?}", token),
+        }
+    }
+
+    fn expr(&mut self, min: u8) -> u64 {
+        let mut lhs = self.operand();
+        while let Some(&op) = self.tokens.get(self.pos) {
+            match self.precedence(op) {
+                Some(p) if p > min => {
+                    self.pos += 1;
+                    let rhs = self.expr(p);
+                    lhs = if op == Token::Add {
+                        lhs + rhs
+                    } else {
+                        lhs * rhs
+                    };
+                }
+                _ => break,
+            }
+        }
+        lhs
+    }
+}
+
+fn eval(line: &str, add_first: bool) -> u64 {
+    let mut ev = Evaluator {
+        tokens: tokenize(line),
+        pos: 0,
+        add_first,
+    };
+    ev.expr(0)
+}
+
+fn proc1(input: &str) -> u64 {
+    input.lines().map(|line| eval(line, false)).sum()
+}
+
+pub(crate) fn quiz1() -> u64 {
+    proc1(&read_file(FILE_NAME))
+}
+
+fn proc2(input: &str) -> u64 {
+    input.lines().map(|line| eval(line, true)).sum()
+}
+
+pub(crate) fn quiz2() -> u64 {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        assert_eq!(eval("1 + 2 * 3 + 4 * 5 + 6", false), 71);
+        assert_eq!(eval("1 + (2 * 3) + (4 * (5 + 6))", false), 51);
+        assert_eq!(eval("2 * 3 + (4 * 5)", false), 26);
+        assert_eq!(eval("5 + (8 * 3 + 9 + 3 * 4 * 3)", false), 437);
+        assert_eq!(
+            eval("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", false),
+            13632
+        );
+
+        assert_eq!(eval("1 + 2 * 3 + 4 * 5 + 6", true), 231);
+        assert_eq!(eval("2 * 3 + (4 * 5)", true), 46);
+        assert_eq!(
+            eval("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", true),
+            669060
+        );
+        assert_eq!(
+            eval("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", true),
+            23340
+        );
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 4696493914530);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 362880372308125);
+    }
+}
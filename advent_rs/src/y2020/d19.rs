@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input19.txt";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Rule {
+    Char(u8),
+    Alt(Vec<Vec<usize>>),
+}
+
+fn parse_rule(line: &str) -> (usize, Rule) {
+    let (id, pattern) = line.split_once(": ").unwrap();
+    let rule = match pattern.strip_prefix('"') {
+        Some(c) => Rule::Char(c.as_bytes()[0]),
+        None => Rule::Alt(
+            pattern
+                .split(" | ")
+                .map(|seq| seq.split(' ').map(|n| n.parse().unwrap()).collect())
+                .collect(),
+        ),
+    };
+    (id.parse().unwrap(), rule)
+}
+
+fn load(input: &str) -> (HashMap<usize, Rule>, Vec<&str>) {
+    let (book, messages) = input.split_once("\n\n").unwrap();
+    (
+        book.lines().map(parse_rule).collect(),
+        messages.lines().collect(),
+    )
+}
+
+// every position a match of `rule` starting at `pos` can end at;
+// following all alternatives keeps looping rules like 8 and 11 finite
+fn ends(book: &HashMap<usize, Rule>, rule: usize, msg: &[u8], pos: usize) -> Vec<usize> {
+    match &book[&rule] {
+        Rule::Char(c) => {
+            if msg.get(pos) == Some(c) {
+                vec![pos + 1]
+            } else {
+                vec![]
+            }
+        }
+        Rule::Alt(alts) => alts
+            .iter()
+            .flat_map(|seq| {
+                seq.iter().fold(vec![pos], |starts, r| {
+                    starts
+                        .into_iter()
+                        .filter(|p| *p < msg.len())
+                        .flat_map(|p| ends(book, *r, msg, p))
+                        .collect()
+                })
+            })
+            .collect(),
+    }
+}
+
+fn matches(book: &HashMap<usize, Rule>, msg: &str) -> bool {
+    ends(book, 0, msg.as_bytes(), 0).contains(&msg.len())
+}
+
+fn count_matches(book: &HashMap<usize, Rule>, messages: &[&str]) -> usize {
+    messages.iter().filter(|msg| matches(book, msg)).count()
+}
+
+fn proc1(input: &str) -> usize {
+    let (book, messages) = load(input);
+    count_matches(&book, &messages)
+}
+
+pub(crate) fn quiz1() -> usize {
+    proc1(&read_file(FILE_NAME))
+}
+
+fn proc2(input: &str) -> usize {
+    let (mut book, messages) = load(input);
+    book.insert(8, parse_rule("8: 42 | 42 8").1);
+    book.insert(11, parse_rule("11: 42 31 | 42 11 31").1);
+    count_matches(&book, &messages)
+}
+
+pub(crate) fn quiz2() -> usize {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proc1() {
+        let input = r#"0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: "a"
+5: "b"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb"#;
+        assert_eq!(proc1(input), 2);
+    }
+
+    #[test]
+    fn test_proc2() {
+        let input = r#"42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: "a"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: "b"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#;
+        assert_eq!(proc1(input), 3);
+        assert_eq!(proc2(input), 12);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 178);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 346);
+    }
+}
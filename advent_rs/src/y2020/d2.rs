@@ -0,0 +1,78 @@
+use regex::Regex;
+
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input2.txt";
+
+#[derive(Debug, PartialEq)]
+struct Entry {
+    low: usize,
+    high: usize,
+    ch: char,
+    password: String,
+}
+
+fn load(input: &str) -> Vec<Entry> {
+    let regex = Regex::new(r"^(\d+)-(\d+)\s+([a-z]):\s+([a-z]+)$").unwrap();
+    input
+        .lines()
+        .map(|line| {
+            let cas = regex.captures(line).unwrap();
+            Entry {
+                low: cas[1].parse().unwrap(),
+                high: cas[2].parse().unwrap(),
+                ch: cas[3].chars().next().unwrap(),
+                password: cas[4].to_string(),
+            }
+        })
+        .collect()
+}
+
+fn policy1(e: &Entry) -> bool {
+    let count = e.password.chars().filter(|c| *c == e.ch).count();
+    (e.low..=e.high).contains(&count)
+}
+
+// exactly one of the two 1-based positions holds the letter
+fn policy2(e: &Entry) -> bool {
+    let at = |i: usize| e.password.chars().nth(i - 1) == Some(e.ch);
+    at(e.low) != at(e.high)
+}
+
+fn proc(input: &str, policy: fn(&Entry) -> bool) -> usize {
+    load(input).iter().filter(|e| policy(e)).count()
+}
+
+pub(crate) fn quiz1() -> usize {
+    proc(&read_file(FILE_NAME), policy1)
+}
+
+pub(crate) fn quiz2() -> usize {
+    proc(&read_file(FILE_NAME), policy2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc";
+
+    #[test]
+    fn test_proc() {
+        assert_eq!(proc(INPUT, policy1), 2);
+        assert_eq!(proc(INPUT, policy2), 1);
+        assert!(!policy2(&load("1-3 q: cqbm")[0]));
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 500);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 313);
+    }
+}
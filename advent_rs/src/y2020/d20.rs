@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input20.txt";
+
+type Image = Vec<Vec<u8>>;
+
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+fn load(input: &str) -> Vec<(u64, Image)> {
+    input
+        .trim()
+        .split("\n\n")
+        .map(|block| {
+            let mut lines = block.lines();
+            let header = lines.next().unwrap();
+            let id = header
+                .trim_start_matches("Tile ")
+                .trim_end_matches(':')
+                .parse()
+                .unwrap();
+            (id, lines.map(|l| l.bytes().collect()).collect())
+        })
+        .collect()
+}
+
+fn rotate(img: &Image) -> Image {
+    let n = img.len();
+    (0..img[0].len())
+        .map(|x| (0..n).map(|y| img[n - 1 - y][x]).collect())
+        .collect()
+}
+
+fn flip(img: &Image) -> Image {
+    img.iter().rev().cloned().collect()
+}
+
+// the 8 rotations and reflections of an image
+fn orientations(img: &Image) -> Vec<Image> {
+    let mut all = vec![];
+    for start in [img.clone(), flip(img)] {
+        let mut cur = start;
+        for _ in 0..4 {
+            let next = rotate(&cur);
+            all.push(cur);
+            cur = next;
+        }
+    }
+    all
+}
+
+fn top(img: &Image) -> Vec<u8> {
+    img[0].clone()
+}
+
+fn bottom(img: &Image) -> Vec<u8> {
+    img[img.len() - 1].clone()
+}
+
+fn left(img: &Image) -> Vec<u8> {
+    img.iter().map(|row| row[0]).collect()
+}
+
+fn right(img: &Image) -> Vec<u8> {
+    img.iter().map(|row| row[row.len() - 1]).collect()
+}
+
+// how many tiles carry each edge, either way round
+fn edge_counts(tiles: &[(u64, Image)]) -> HashMap<Vec<u8>, usize> {
+    let mut counts = HashMap::new();
+    for (_, img) in tiles {
+        for edge in [top(img), bottom(img), left(img), right(img)] {
+            let rev = edge.iter().rev().cloned().collect::<Vec<_>>();
+            *counts.entry(edge.min(rev)).or_default() += 1;
+        }
+    }
+    counts
+}
+
+fn is_border(counts: &HashMap<Vec<u8>, usize>, edge: &[u8]) -> bool {
+    let rev = edge.iter().rev().cloned().collect::<Vec<_>>();
+    counts[&edge.to_vec().min(rev)] == 1
+}
+
+fn corners(tiles: &[(u64, Image)]) -> Vec<u64> {
+    let counts = edge_counts(tiles);
+    tiles
+        .iter()
+        .filter(|(_, img)| {
+            [top(img), bottom(img), left(img), right(img)]
+                .iter()
+                .filter(|e| is_border(&counts, e))
+                .count()
+                == 2
+        })
+        .map(|(id, _)| *id)
+        .collect()
+}
+
+fn proc1(input: &str) -> u64 {
+    corners(&load(input)).iter().product()
+}
+
+pub(crate) fn quiz1() -> u64 {
+    proc1(&read_file(FILE_NAME))
+}
+
+// lay the tiles out row by row starting from a corner turned so its
+// border edges face up and left; every inner edge pairs exactly two tiles
+fn assemble(tiles: &[(u64, Image)]) -> Vec<Vec<Image>> {
+    let counts = edge_counts(tiles);
+    let side = (tiles.len() as f64).sqrt() as usize;
+    let corner = corners(tiles)[0];
+    let mut left_over = tiles
+        .iter()
+        .filter(|(id, _)| *id != corner)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let first = orientations(&tiles.iter().find(|(id, _)| *id == corner).unwrap().1)
+        .into_iter()
+        .find(|img| is_border(&counts, &top(img)) && is_border(&counts, &left(img)))
+        .unwrap();
+
+    let mut grid: Vec<Vec<Image>> = vec![];
+    for y in 0..side {
+        let mut row: Vec<Image> = vec![];
+        for x in 0..side {
+            if x == 0 && y == 0 {
+                row.push(first.clone());
+                continue;
+            }
+            let fits = |img: &Image| {
+                (x == 0 || left(img) == right(&row[x - 1]))
+                    && (y == 0 || top(img) == bottom(&grid[y - 1][x]))
+            };
+            let (i, img) = left_over
+                .iter()
+                .enumerate()
+                .find_map(|(i, (_, tile))| {
+                    orientations(tile)
+                        .into_iter()
+                        .find(fits)
+                        .map(|img| (i, img))
+                })
+                .unwrap();
+            left_over.swap_remove(i);
+            row.push(img);
+        }
+        grid.push(row);
+    }
+    grid
+}
+
+// stitch the tiles together without their borders
+fn stitch(grid: &[Vec<Image>]) -> Image {
+    grid.iter()
+        .flat_map(|row| {
+            let h = row[0].len();
+            (1..h - 1).map(move |y| {
+                row.iter()
+                    .flat_map(|img| img[y][1..img[y].len() - 1].to_vec())
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+fn count_monsters(img: &Image) -> usize {
+    let cells = MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.bytes()
+                .enumerate()
+                .filter(|(_, c)| *c == b'#')
+                .map(move |(x, _)| (y, x))
+        })
+        .collect::<Vec<_>>();
+    let (h, w) = (MONSTER.len(), MONSTER[0].len());
+    (0..=img.len() - h)
+        .flat_map(|y| (0..=img[0].len() - w).map(move |x| (y, x)))
+        .filter(|(y, x)| cells.iter().all(|(dy, dx)| img[y + dy][x + dx] == b'#'))
+        .count()
+}
+
+fn roughness(img: &Image) -> usize {
+    let monster_size = MONSTER
+        .iter()
+        .map(|l| l.matches('#').count())
+        .sum::<usize>();
+    let monsters = orientations(img).iter().map(count_monsters).max().unwrap();
+    let rough = img.iter().flatten().filter(|c| **c == b'#').count();
+    rough - monsters * monster_size
+}
+
+fn proc2(input: &str) -> usize {
+    roughness(&stitch(&assemble(&load(input))))
+}
+
+pub(crate) fn quiz2() -> usize {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...";
+
+    #[test]
+    fn test_orientations() {
+        let img = vec![b"ab".to_vec(), b"cd".to_vec()];
+        let all = orientations(&img);
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[1], vec![b"ca".to_vec(), b"db".to_vec()]);
+    }
+
+    #[test]
+    fn test_proc1() {
+        let mut ids = corners(&load(SAMPLE));
+        ids.sort();
+        assert_eq!(ids, vec![1171, 1951, 2971, 3079]);
+        assert_eq!(proc1(SAMPLE), 20899048083289);
+    }
+
+    #[test]
+    fn test_proc2() {
+        let img = stitch(&assemble(&load(SAMPLE)));
+        assert_eq!(img.len(), 24);
+        assert_eq!(proc2(SAMPLE), 273);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 15670959891893);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 1964);
+    }
+}
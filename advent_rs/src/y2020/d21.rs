@@ -0,0 +1,103 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input21.txt";
+
+struct Food<'a> {
+    ingredients: HashSet<&'a str>,
+    allergens: Vec<&'a str>,
+}
+
+fn parse_food(line: &str) -> Food<'_> {
+    let (ingredients, allergens) = line
+        .trim_end_matches(')')
+        .split_once(" (contains ")
+        .unwrap();
+    Food {
+        ingredients: ingredients.split(' ').collect(),
+        allergens: allergens.split(", ").collect(),
+    }
+}
+
+fn load(input: &str) -> Vec<Food<'_>> {
+    input.lines().map(parse_food).collect()
+}
+
+// allergen -> the ingredient containing it, alphabetical by allergen
+fn identify<'a>(foods: &[Food<'a>]) -> BTreeMap<&'a str, &'a str> {
+    // an allergen can only hide in ingredients every food listing it shares
+    let mut candidates: BTreeMap<&str, HashSet<&str>> = BTreeMap::new();
+    for food in foods {
+        for allergen in &food.allergens {
+            candidates
+                .entry(allergen)
+                .and_modify(|c| c.retain(|i| food.ingredients.contains(i)))
+                .or_insert_with(|| food.ingredients.clone());
+        }
+    }
+
+    let mut found = BTreeMap::new();
+    while let Some((&allergen, ingredients)) = candidates.iter().find(|(_, c)| c.len() == 1) {
+        let ingredient = *ingredients.iter().next().unwrap();
+        found.insert(allergen, ingredient);
+        candidates.remove(allergen);
+        candidates.values_mut().for_each(|c| {
+            c.remove(ingredient);
+        });
+    }
+    found
+}
+
+fn proc1(input: &str) -> usize {
+    let foods = load(input);
+    let unsafe_ = identify(&foods).into_values().collect::<HashSet<_>>();
+    foods
+        .iter()
+        .flat_map(|f| &f.ingredients)
+        .filter(|i| !unsafe_.contains(*i))
+        .count()
+}
+
+pub(crate) fn quiz1() -> usize {
+    proc1(&read_file(FILE_NAME))
+}
+
+fn proc2(input: &str) -> String {
+    let foods = load(input);
+    identify(&foods).into_values().collect::<Vec<_>>().join(",")
+}
+
+pub(crate) fn quiz2() -> String {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+
+    #[test]
+    fn test_proc1() {
+        assert_eq!(proc1(SAMPLE), 5);
+    }
+
+    #[test]
+    fn test_proc2() {
+        assert_eq!(proc2(SAMPLE), "mxmxvkd,sqjhc,fvjkl");
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 2786);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), "prxmdlz,ncjv,knprxg,lxjtns,vzzz,clg,cxfz,qdfpq");
+    }
+}
@@ -0,0 +1,68 @@
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input3.txt";
+
+const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+fn trees(input: &str, (right, down): (usize, usize)) -> usize {
+    input
+        .lines()
+        .step_by(down)
+        .enumerate()
+        .filter(|(i, line)| line.as_bytes()[i * right % line.len()] == b'#')
+        .count()
+}
+
+fn proc1(input: &str) -> usize {
+    trees(input, (3, 1))
+}
+
+pub(crate) fn quiz1() -> usize {
+    proc1(&read_file(FILE_NAME))
+}
+
+fn proc2(input: &str) -> usize {
+    SLOPES.iter().map(|slope| trees(input, *slope)).product()
+}
+
+pub(crate) fn quiz2() -> usize {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    #[test]
+    fn test_proc1() {
+        assert_eq!(proc1(INPUT), 7);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 230);
+    }
+
+    #[test]
+    fn test_proc2() {
+        assert_eq!(trees(INPUT, (1, 2)), 2);
+        assert_eq!(proc2(INPUT), 336);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 9533698720);
+    }
+}
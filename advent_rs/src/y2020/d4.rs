@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input4.txt";
+
+const REQUIRED: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+type Passport<'a> = HashMap<&'a str, &'a str>;
+
+fn load(input: &str) -> Vec<Passport<'_>> {
+    input
+        .split("\n\n")
+        .map(|block| {
+            block
+                .split_whitespace()
+                .filter_map(|field| field.split_once(':'))
+                .collect()
+        })
+        .collect()
+}
+
+fn rule1(passport: &Passport) -> bool {
+    REQUIRED.iter().all(|f| passport.contains_key(f))
+}
+
+fn in_range(s: &str, low: u32, high: u32) -> bool {
+    s.parse().is_ok_and(|n: u32| (low..=high).contains(&n))
+}
+
+fn valid_field(key: &str, value: &str) -> bool {
+    match key {
+        "byr" => in_range(value, 1920, 2002),
+        "iyr" => in_range(value, 2010, 2020),
+        "eyr" => in_range(value, 2020, 2030),
+        "hgt" => match (value.strip_suffix("cm"), value.strip_suffix("in")) {
+            (Some(cm), _) => in_range(cm, 150, 193),
+            (_, Some(inch)) => in_range(inch, 59, 76),
+            _ => false,
+        },
+        "hcl" => Regex::new(r"^#[0-9a-f]{6}$").unwrap().is_match(value),
+        "ecl" => ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value),
+        "pid" => value.len() == 9 && value.bytes().all(|b| b.is_ascii_digit()),
+        _ => true,
+    }
+}
+
+fn rule2(passport: &Passport) -> bool {
+    rule1(passport) && passport.iter().all(|(k, v)| valid_field(k, v))
+}
+
+fn proc(input: &str, rule: fn(&Passport) -> bool) -> usize {
+    load(input).iter().filter(|p| rule(p)).count()
+}
+
+pub(crate) fn quiz1() -> usize {
+    proc(&read_file(FILE_NAME), rule1)
+}
+
+pub(crate) fn quiz2() -> usize {
+    proc(&read_file(FILE_NAME), rule2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+
+    #[test]
+    fn test_proc1() {
+        assert_eq!(proc(INPUT, rule1), 2);
+    }
+
+    #[test]
+    fn test_valid_field() {
+        assert!(valid_field("byr", "2002"));
+        assert!(!valid_field("byr", "2003"));
+        assert!(valid_field("hgt", "60in"));
+        assert!(valid_field("hgt", "190cm"));
+        assert!(!valid_field("hgt", "190in"));
+        assert!(!valid_field("hgt", "190"));
+        assert!(valid_field("hcl", "#123abc"));
+        assert!(!valid_field("hcl", "#123abz"));
+        assert!(!valid_field("hcl", "123abc"));
+        assert!(valid_field("ecl", "brn"));
+        assert!(!valid_field("ecl", "wat"));
+        assert!(valid_field("pid", "000000001"));
+        assert!(!valid_field("pid", "0123456789"));
+    }
+
+    #[test]
+    fn test_proc2() {
+        let invalid = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946";
+        let valid = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm";
+        assert_eq!(proc(invalid, rule2), 0);
+        assert_eq!(proc(valid, rule2), 2);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 237);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 172);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input5.txt";
+
+// the boarding pass is the seat id written in binary with F/L as 0 and B/R as 1
+fn seat_id(pass: &str) -> u32 {
+    pass.chars()
+        .fold(0, |id, c| id * 2 + u32::from(c == 'B' || c == 'R'))
+}
+
+fn seat_ids(input: &str) -> Vec<u32> {
+    input.lines().map(seat_id).collect()
+}
+
+fn proc1(input: &str) -> u32 {
+    seat_ids(input).into_iter().max().unwrap()
+}
+
+pub(crate) fn quiz1() -> u32 {
+    proc1(&read_file(FILE_NAME))
+}
+
+// the only gap in the sorted ids
+fn proc2(input: &str) -> u32 {
+    let mut ids = seat_ids(input);
+    ids.sort();
+    ids.windows(2).find(|w| w[1] - w[0] == 2).unwrap()[0] + 1
+}
+
+pub(crate) fn quiz2() -> u32 {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seat_id() {
+        assert_eq!(seat_id("FBFBBFFRLR"), 357);
+        assert_eq!(seat_id("BFFFBBFRRR"), 567);
+        assert_eq!(seat_id("FFFBBBFRRR"), 119);
+        assert_eq!(seat_id("BBFFBBFRLL"), 820);
+    }
+
+    #[test]
+    fn test_proc2() {
+        assert_eq!(proc2("FFFFFFFLLL\nFFFFFFFLLR\nFFFFFFFLRR"), 2);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 864);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 739);
+    }
+}
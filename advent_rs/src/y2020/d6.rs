@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input6.txt";
+
+fn groups(input: &str) -> Vec<Vec<HashSet<char>>> {
+    input
+        .split("\n\n")
+        .map(|group| group.lines().map(|line| line.chars().collect()).collect())
+        .collect()
+}
+
+// questions anyone in the group answered
+fn anyone(group: &[HashSet<char>]) -> usize {
+    group.iter().flatten().collect::<HashSet<_>>().len()
+}
+
+// questions everyone in the group answered
+fn everyone(group: &[HashSet<char>]) -> usize {
+    group[0]
+        .iter()
+        .filter(|q| group.iter().all(|answers| answers.contains(q)))
+        .count()
+}
+
+fn proc(input: &str, counter: fn(&[HashSet<char>]) -> usize) -> usize {
+    groups(input).iter().map(|g| counter(g)).sum()
+}
+
+pub(crate) fn quiz1() -> usize {
+    proc(&read_file(FILE_NAME), anyone)
+}
+
+pub(crate) fn quiz2() -> usize {
+    proc(&read_file(FILE_NAME), everyone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+    #[test]
+    fn test_proc() {
+        assert_eq!(proc(INPUT, anyone), 11);
+        assert_eq!(proc(INPUT, everyone), 6);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 6534);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 3402);
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input7.txt";
+
+const TARGET: &str = "shiny gold";
+
+type Rules = HashMap<String, HashMap<String, usize>>;
+
+fn parse_rule(line: &str) -> (String, HashMap<String, usize>) {
+    let bag = Regex::new(r"^(\w+ \w+) bags contain ").unwrap();
+    let contents = Regex::new(r"(\d+) (\w+ \w+) bags?[,.]").unwrap();
+    (
+        bag.captures(line).unwrap()[1].to_string(),
+        contents
+            .captures_iter(line)
+            .map(|cas| (cas[2].to_string(), cas[1].parse().unwrap()))
+            .collect(),
+    )
+}
+
+fn load(input: &str) -> Rules {
+    input.lines().map(parse_rule).collect()
+}
+
+fn can_contain(rules: &Rules, bag: &str, content: &str) -> bool {
+    rules[bag]
+        .keys()
+        .any(|b| b == content || can_contain(rules, b, content))
+}
+
+// the bag itself plus everything inside it
+fn bags(rules: &Rules, bag: &str) -> usize {
+    1 + rules[bag]
+        .iter()
+        .map(|(b, n)| n * bags(rules, b))
+        .sum::<usize>()
+}
+
+fn proc1(input: &str) -> usize {
+    let rules = load(input);
+    rules
+        .keys()
+        .filter(|bag| can_contain(&rules, bag, TARGET))
+        .count()
+}
+
+pub(crate) fn quiz1() -> usize {
+    proc1(&read_file(FILE_NAME))
+}
+
+fn proc2(input: &str) -> usize {
+    bags(&load(input), TARGET) - 1
+}
+
+pub(crate) fn quiz2() -> usize {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+    #[test]
+    fn test_parse_rule() {
+        assert_eq!(
+            parse_rule("dotted black bags contain no other bags."),
+            ("dotted black".to_string(), HashMap::new())
+        );
+        assert_eq!(
+            parse_rule("light red bags contain 1 bright white bag, 2 muted yellow bags."),
+            (
+                "light red".to_string(),
+                HashMap::from([
+                    ("bright white".to_string(), 1),
+                    ("muted yellow".to_string(), 2)
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn test_proc1() {
+        assert_eq!(proc1(INPUT), 4);
+    }
+
+    #[test]
+    fn test_proc2() {
+        assert_eq!(proc2(INPUT), 32);
+        let nested = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+        assert_eq!(proc2(nested), 126);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 161);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 30899);
+    }
+}
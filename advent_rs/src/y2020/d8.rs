@@ -0,0 +1,156 @@
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input8.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ins {
+    Nop(i32),
+    Acc(i32),
+    Jmp(i32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct State {
+    pc: usize,
+    acc: i32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Halt {
+    // the state right before an instruction would run a second time
+    Loop(State),
+    Terminated(State),
+}
+
+fn parse_line(line: &str) -> Ins {
+    let (ins, arg) = line.split_once(' ').unwrap();
+    let arg = arg.parse().unwrap();
+    match ins {
+        "nop" => Ins::Nop(arg),
+        "acc" => Ins::Acc(arg),
+        "jmp" => Ins::Jmp(arg),
+        _ => panic!("unknown instruction {}", ins),
+    }
+}
+
+fn load(input: &str) -> Vec<Ins> {
+    input.lines().map(parse_line).collect()
+}
+
+fn step(state: State, ins: Ins) -> State {
+    match ins {
+        Ins::Nop(_) => State {
+            pc: state.pc + 1,
+            ..state
+        },
+        Ins::Acc(n) => State {
+            pc: state.pc + 1,
+            acc: state.acc + n,
+        },
+        Ins::Jmp(n) => State {
+            pc: (state.pc as i32 + n) as usize,
+            ..state
+        },
+    }
+}
+
+fn run(program: &[Ins]) -> Halt {
+    let mut visited = vec![false; program.len()];
+    let mut state = State::default();
+    while state.pc < program.len() {
+        if visited[state.pc] {
+            return Halt::Loop(state);
+        }
+        visited[state.pc] = true;
+        state = step(state, program[state.pc]);
+    }
+    Halt::Terminated(state)
+}
+
+fn proc1(input: &str) -> i32 {
+    match run(&load(input)) {
+        Halt::Loop(state) => state.acc,
+        Halt::Terminated(_) => panic!("the program terminates"),
+    }
+}
+
+pub(crate) fn quiz1() -> i32 {
+    proc1(&read_file(FILE_NAME))
+}
+
+// swap one nop/jmp so the program terminates
+fn proc2(input: &str) -> i32 {
+    let program = load(input);
+    (0..program.len())
+        .find_map(|i| {
+            let mut fixed = program.clone();
+            fixed[i] = match program[i] {
+                Ins::Nop(n) => Ins::Jmp(n),
+                Ins::Jmp(n) => Ins::Nop(n),
+                Ins::Acc(_) => return None,
+            };
+            match run(&fixed) {
+                Halt::Terminated(state) => Some(state.acc),
+                Halt::Loop(_) => None,
+            }
+        })
+        .unwrap()
+}
+
+pub(crate) fn quiz2() -> i32 {
+    proc2(&read_file(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+    #[test]
+    fn test_step() {
+        let init = State::default();
+        assert_eq!(step(init, Ins::Nop(0)), State { pc: 1, acc: 0 });
+        assert_eq!(step(init, Ins::Acc(2)), State { pc: 1, acc: 2 });
+        assert_eq!(
+            step(State { pc: 2, acc: 0 }, Ins::Jmp(4)),
+            State { pc: 6, acc: 0 }
+        );
+        assert_eq!(parse_line("acc +8"), Ins::Acc(8));
+        assert_eq!(parse_line("jmp -3"), Ins::Jmp(-3));
+    }
+
+    #[test]
+    fn test_run() {
+        let program = load(INPUT);
+        assert_eq!(run(&program), Halt::Loop(State { pc: 1, acc: 5 }));
+
+        let mut fixed = program.clone();
+        fixed[7] = Ins::Nop(-4);
+        assert_eq!(run(&fixed), Halt::Terminated(State { pc: 9, acc: 8 }));
+    }
+
+    #[test]
+    fn test_proc() {
+        assert_eq!(proc1(INPUT), 5);
+        assert_eq!(proc2(INPUT), 8);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 1563);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 767);
+    }
+}
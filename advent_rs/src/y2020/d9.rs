@@ -0,0 +1,108 @@
+use crate::read_file;
+
+const FILE_NAME: &str = "data/2020/input9.txt";
+
+fn load(input: &str) -> Vec<u64> {
+    input.lines().map(|line| line.parse().unwrap()).collect()
+}
+
+fn is_sum_of_two(numbers: &[u64], value: u64) -> bool {
+    numbers
+        .iter()
+        .enumerate()
+        .any(|(i, a)| numbers[i + 1..].iter().any(|b| a + b == value))
+}
+
+fn invalid_first_number(window: usize, numbers: &[u64]) -> u64 {
+    numbers
+        .windows(window + 1)
+        .find(|w| !is_sum_of_two(&w[..window], w[window]))
+        .unwrap()[window]
+}
+
+// a run of at least two numbers adding up to the value
+fn contiguous_sum(numbers: &[u64], value: u64) -> &[u64] {
+    let (mut start, mut sum) = (0, 0);
+    for end in 0..numbers.len() {
+        sum += numbers[end];
+        while sum > value && start < end {
+            sum -= numbers[start];
+            start += 1;
+        }
+        if sum == value && end > start {
+            return &numbers[start..=end];
+        }
+    }
+    panic!("no run adds up to {}", value)
+}
+
+fn proc1(input: &str, window: usize) -> u64 {
+    invalid_first_number(window, &load(input))
+}
+
+pub(crate) fn quiz1() -> u64 {
+    proc1(&read_file(FILE_NAME), 25)
+}
+
+fn proc2(input: &str, window: usize) -> u64 {
+    let numbers = load(input);
+    let run = contiguous_sum(&numbers, invalid_first_number(window, &numbers));
+    run.iter().min().unwrap() + run.iter().max().unwrap()
+}
+
+pub(crate) fn quiz2() -> u64 {
+    proc2(&read_file(FILE_NAME), 25)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+
+    #[test]
+    fn test_is_sum_of_two() {
+        let numbers = (1..26).collect::<Vec<_>>();
+        assert!(!is_sum_of_two(&numbers, 1));
+        assert!(is_sum_of_two(&numbers, 3));
+        assert!(is_sum_of_two(&numbers, 24 + 25));
+        assert!(!is_sum_of_two(&numbers, 50));
+        assert!(is_sum_of_two(&[35, 20, 15, 25, 47], 40));
+    }
+
+    #[test]
+    fn test_proc() {
+        assert_eq!(proc1(INPUT, 5), 127);
+        assert_eq!(contiguous_sum(&load(INPUT), 127), &[15, 25, 47, 40]);
+        assert_eq!(proc2(INPUT, 5), 62);
+    }
+
+    #[test]
+    fn test_quiz1() {
+        assert_eq!(quiz1(), 57195069);
+    }
+
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(quiz2(), 7409241);
+    }
+}
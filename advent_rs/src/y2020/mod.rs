@@ -0,0 +1,76 @@
+mod d1;
+mod d10;
+mod d11;
+mod d12;
+mod d13;
+mod d14;
+mod d15;
+mod d16;
+mod d17;
+mod d18;
+mod d19;
+mod d2;
+mod d20;
+mod d21;
+mod d3;
+mod d4;
+mod d5;
+mod d6;
+mod d7;
+mod d8;
+mod d9;
+
+use crate::runner::Solver;
+
+pub(crate) fn solvers() -> Vec<Solver> {
+    vec![
+        Solver::new(2020, 1, 1, Some("259716"), || d1::quiz1().into()),
+        Solver::new(2020, 1, 2, Some("120637440"), || d1::quiz2().into()),
+        Solver::new(2020, 2, 1, Some("500"), || d2::quiz1().into()),
+        Solver::new(2020, 2, 2, Some("313"), || d2::quiz2().into()),
+        Solver::new(2020, 3, 1, Some("230"), || d3::quiz1().into()),
+        Solver::new(2020, 3, 2, Some("9533698720"), || d3::quiz2().into()),
+        Solver::new(2020, 4, 1, Some("237"), || d4::quiz1().into()),
+        Solver::new(2020, 4, 2, Some("172"), || d4::quiz2().into()),
+        Solver::new(2020, 5, 1, Some("864"), || d5::quiz1().into()),
+        Solver::new(2020, 5, 2, Some("739"), || d5::quiz2().into()),
+        Solver::new(2020, 6, 1, Some("6534"), || d6::quiz1().into()),
+        Solver::new(2020, 6, 2, Some("3402"), || d6::quiz2().into()),
+        Solver::new(2020, 7, 1, Some("161"), || d7::quiz1().into()),
+        Solver::new(2020, 7, 2, Some("30899"), || d7::quiz2().into()),
+        Solver::new(2020, 8, 1, Some("1563"), || d8::quiz1().into()),
+        Solver::new(2020, 8, 2, Some("767"), || d8::quiz2().into()),
+        Solver::new(2020, 9, 1, Some("57195069"), || d9::quiz1().into()),
+        Solver::new(2020, 9, 2, Some("7409241"), || d9::quiz2().into()),
+        Solver::new(2020, 10, 1, Some("1980"), || d10::quiz1().into()),
+        Solver::new(2020, 10, 2, Some("4628074479616"), || d10::quiz2().into()),
+        Solver::new(2020, 11, 1, Some("2113"), || d11::quiz1().into()),
+        Solver::new(2020, 11, 2, Some("1865"), || d11::quiz2().into()),
+        Solver::new(2020, 12, 1, Some("1152"), || d12::quiz1().into()),
+        Solver::new(2020, 12, 2, Some("58637"), || d12::quiz2().into()),
+        Solver::new(2020, 13, 1, Some("2092"), || d13::quiz1().into()),
+        Solver::new(2020, 13, 2, Some("702970661767766"), || d13::quiz2().into()),
+        Solver::new(2020, 14, 1, Some("11884151942312"), || d14::quiz1().into()),
+        Solver::new(2020, 14, 2, Some("2625449018811"), || d14::quiz2().into()),
+        Solver::new(2020, 15, 1, Some("273"), || d15::quiz1().into()),
+        Solver::new(2020, 15, 2, Some("47205"), || d15::quiz2().into()),
+        Solver::new(2020, 16, 1, Some("27870"), || d16::quiz1().into()),
+        Solver::new(2020, 16, 2, Some("3173135507987"), || d16::quiz2().into()),
+        Solver::new(2020, 17, 1, Some("213"), || d17::quiz1().into()),
+        Solver::new(2020, 17, 2, Some("1624"), || d17::quiz2().into()),
+        Solver::new(2020, 18, 1, Some("4696493914530"), || d18::quiz1().into()),
+        Solver::new(2020, 18, 2, Some("362880372308125"), || d18::quiz2().into()),
+        Solver::new(2020, 19, 1, Some("178"), || d19::quiz1().into()),
+        Solver::new(2020, 19, 2, Some("346"), || d19::quiz2().into()),
+        Solver::new(2020, 20, 1, Some("15670959891893"), || d20::quiz1().into()),
+        Solver::new(2020, 20, 2, Some("1964"), || d20::quiz2().into()),
+        Solver::new(2020, 21, 1, Some("2786"), || d21::quiz1().into()),
+        Solver::new(
+            2020,
+            21,
+            2,
+            Some("prxmdlz,ncjv,knprxg,lxjtns,vzzz,clg,cxfz,qdfpq"),
+            || d21::quiz2().into(),
+        ),
+    ]
+}
@@ -1,6 +1,8 @@
 use regex::Regex;
 use std::collections::HashSet;
 
+use crate::answer::Screen;
+
 enum Folding {
     X(i32),
     Y(i32),
@@ -82,7 +84,7 @@ pub fn quiz1(text: &str) -> usize {
     coords.len()
 }
 
-pub fn quiz2(text: &str) {
+pub fn quiz2(text: &str) -> Screen {
     let (mut coords, mut width, mut height, foldings) = load_data(text.trim());
     for folding in foldings.iter() {
         let n = fold(&coords, width, height, folding);
@@ -91,22 +93,15 @@ pub fn quiz2(text: &str) {
         height = n.2;
     }
 
-    print_paper(&coords, width, height);
+    paper(&coords, width, height)
 }
 
-fn print_paper(coords: &HashSet<(i32, i32)>, width: i32, height: i32) {
-    let mut msg = String::new();
-    for y in 0..height {
-        for x in 0..width {
-            if coords.contains(&(x, y)) {
-                msg.push('#');
-            } else {
-                msg.push('.');
-            }
-        }
-        msg.push('\n');
-    }
-    println!("{}", msg);
+fn paper(coords: &HashSet<(i32, i32)>, width: i32, height: i32) -> Screen {
+    Screen::from_points(
+        width as usize,
+        height as usize,
+        coords.iter().map(|(x, y)| (*x as usize, *y as usize)),
+    )
 }
 
 #[cfg(test)]
@@ -161,6 +156,20 @@ fold along x=5";
         assert_eq!(height, 7);
     }
 
+    #[test]
+    fn test_quiz2() {
+        assert_eq!(
+            quiz2(DATA1.trim()).to_string(),
+            "#####
+#...#
+#...#
+#...#
+#####
+.....
+....."
+        );
+    }
+
     #[test]
     fn run_2021_d13_quiz1() {
         let text = read_file("data/2021/input13.txt");
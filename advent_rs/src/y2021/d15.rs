@@ -1,3 +1,7 @@
+use crate::dashboard::save_visual;
+use crate::heatmap::Heatmap;
+use crate::trace;
+
 fn load_data(text: &str) -> (Vec<i32>, usize, usize) {
     let rows = text.lines().count();
     let cols = text.lines().next().unwrap().chars().count();
@@ -62,10 +66,19 @@ fn gen_dist_map(levels: Vec<i32>, rows: usize, cols: usize) -> Vec<i32> {
     dist
 }
 
+fn draw_map(dist: &[i32], cols: usize, name: &str) {
+    let map = Heatmap::from_grid(dist, cols, |v| v != i32::MAX)
+        .start(0)
+        .end(dist.len() - 1);
+    trace!("\n{}", map.render_terminal());
+    save_visual(2021, 15, name, map.to_svg(4).as_bytes());
+}
+
 pub fn quiz1(text: &str) -> i32 {
     let (levels, rows, cols) = load_data(text);
     let start = levels[0];
     let dist = gen_dist_map(levels, rows, cols);
+    draw_map(&dist, cols, "risk.svg");
     dist[rows * cols - 1] - start
 }
 
@@ -93,6 +106,7 @@ pub fn quiz2(text: &str) -> i32 {
     let start = levels[0];
     let (full_map, rows, cols) = gen_full_map(levels, rows, cols);
     let dist = gen_dist_map(full_map, rows, cols);
+    draw_map(&dist, cols, "full-risk.svg");
     dist[rows * cols - 1] - start
 }
 
@@ -1,190 +1,417 @@
-fn hex2bins(c: char) -> Vec<u8> {
-    let mut v = c.to_digit(16).unwrap();
-    let mut bins = vec![0_u8; 4];
-    let mut c = 3;
-
-    loop {
-        bins[c] = (v & 1) as u8;
-
-        v >>= 1;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidHex {
+        pos: usize,
+        c: char,
+    },
+    Truncated {
+        bit: usize,
+        needed: usize,
+    },
+    LengthMismatch {
+        bit: usize,
+        expected: usize,
+        actual: usize,
+    },
+    Arity {
+        bit: usize,
+        op: OpKind,
+        count: usize,
+    },
+    LiteralOverflow {
+        bit: usize,
+    },
+}
 
-        if v == 0 {
-            return bins;
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidHex { pos, c } => write!(f, "invalid hex digit {c:?} at {pos}"),
+            ParseError::Truncated { bit, needed } => {
+                write!(f, "truncated input: {needed} more bits needed at bit {bit}")
+            }
+            ParseError::LengthMismatch {
+                bit,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "sub-packets at bit {bit} take {actual} bits, header says {expected}"
+            ),
+            ParseError::Arity { bit, op, count } => {
+                write!(f, "{op} at bit {bit} cannot take {count} sub-packets")
+            }
+            ParseError::LiteralOverflow { bit } => {
+                write!(f, "literal at bit {bit} does not fit in 64 bits")
+            }
         }
-
-        c -= 1;
     }
 }
 
-pub fn hexs2bins(hs: &str) -> Vec<u8> {
-    hs.chars().flat_map(|c| hex2bins(c)).collect::<Vec<u8>>()
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    Version(u8),
+    BitLength(usize),
+    Count(usize),
 }
 
-fn bins2num(bins: &Vec<u8>, offset: usize, len: usize) -> u32 {
-    let mut v = 0;
-    for i in offset..(offset + len) {
-        v = (v << 1) + bins[i] as u32;
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::Version(v) => write!(f, "version {v} does not fit in 3 bits"),
+            EncodeError::BitLength(n) => {
+                write!(
+                    f,
+                    "sub-packets take {n} bits, more than a 15-bit length holds"
+                )
+            }
+            EncodeError::Count(n) => {
+                write!(f, "{n} sub-packets, more than an 11-bit count holds")
+            }
+        }
     }
-    v
-}
-
-fn packet_version(packet: &Vec<u8>, offset: usize) -> u32 {
-    bins2num(packet, offset, 3)
 }
 
-fn packet_type(packet: &Vec<u8>, offset: usize) -> u32 {
-    bins2num(packet, offset + 3, 3)
+struct BitReader {
+    bytes: Vec<u8>,
+    len: usize,
+    pos: usize,
 }
 
-fn length_of_sub_packet(packet: &Vec<u8>, offset: usize) -> u32 {
-    bins2num(packet, offset + 7, 15)
-}
-
-fn count_of_sub_packet(packet: &Vec<u8>, offset: usize) -> u32 {
-    bins2num(packet, offset + 7, 11)
-}
+impl BitReader {
+    fn from_hex(hex: &str) -> Result<Self, ParseError> {
+        let digits = hex
+            .chars()
+            .enumerate()
+            .map(|(pos, c)| {
+                c.to_digit(16)
+                    .map(|d| d as u8)
+                    .ok_or(ParseError::InvalidHex { pos, c })
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        let bytes = digits
+            .chunks(2)
+            .map(|ds| (ds[0] << 4) | ds.get(1).copied().unwrap_or(0))
+            .collect();
+
+        Ok(BitReader {
+            bytes,
+            len: digits.len() * 4,
+            pos: 0,
+        })
+    }
 
-fn end_of_literal_packet(packet: &Vec<u8>, offset: usize) -> usize {
-    let mut offset = offset + 6;
-    loop {
-        if packet[offset] == 0 {
-            return offset + 5;
+    fn read(&mut self, n: usize) -> Result<u64, ParseError> {
+        if self.pos + n > self.len {
+            return Err(ParseError::Truncated {
+                bit: self.pos,
+                needed: self.pos + n - self.len,
+            });
+        }
+        let mut v = 0;
+        for i in self.pos..self.pos + n {
+            v = (v << 1) | ((self.bytes[i / 8] >> (7 - i % 8)) & 1) as u64;
         }
-        offset += 5;
+        self.pos += n;
+        Ok(v)
     }
 }
 
-const LITERAL: u32 = 4;
+#[derive(Default)]
+struct BitWriter {
+    bits: Vec<bool>,
+}
 
-pub fn count_versions(packet: &Vec<u8>, offset: usize) -> (u32, usize) {
-    let v = packet_version(packet, offset);
-    let t = packet_type(packet, offset);
+impl BitWriter {
+    fn write(&mut self, v: u64, n: usize) {
+        (0..n).rev().for_each(|i| self.bits.push((v >> i) & 1 == 1));
+    }
 
-    if t == LITERAL {
-        return (v, end_of_literal_packet(packet, offset));
+    fn append(&mut self, other: BitWriter) {
+        self.bits.extend(other.bits);
     }
 
-    let bit_i = packet[offset + 6];
+    fn to_hex(&self) -> String {
+        self.bits
+            .chunks(4)
+            .map(|bs| {
+                let v = (0..4).fold(0, |v, i| (v << 1) | *bs.get(i).unwrap_or(&false) as u32);
+                std::char::from_digit(v, 16).unwrap().to_ascii_uppercase()
+            })
+            .collect()
+    }
+}
 
-    let mut count = 0;
-    let mut current_offset = offset;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpKind {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
 
-    if bit_i == 0 {
-        let sub_packet_len = length_of_sub_packet(packet, offset);
-        let end_of_packet = offset + 22 + sub_packet_len as usize;
+impl OpKind {
+    fn from_type_id(id: u64) -> Option<Self> {
+        match id {
+            0 => Some(OpKind::Sum),
+            1 => Some(OpKind::Product),
+            2 => Some(OpKind::Minimum),
+            3 => Some(OpKind::Maximum),
+            5 => Some(OpKind::GreaterThan),
+            6 => Some(OpKind::LessThan),
+            7 => Some(OpKind::EqualTo),
+            _ => None,
+        }
+    }
 
-        current_offset += 22;
+    fn type_id(&self) -> u64 {
+        match self {
+            OpKind::Sum => 0,
+            OpKind::Product => 1,
+            OpKind::Minimum => 2,
+            OpKind::Maximum => 3,
+            OpKind::GreaterThan => 5,
+            OpKind::LessThan => 6,
+            OpKind::EqualTo => 7,
+        }
+    }
 
-        while current_offset < end_of_packet {
-            let (c, next_offset) = count_versions(packet, current_offset);
-            count += c;
-            current_offset = next_offset;
+    fn accepts(&self, count: usize) -> bool {
+        match self {
+            OpKind::Sum | OpKind::Product => true,
+            OpKind::Minimum | OpKind::Maximum => count > 0,
+            OpKind::GreaterThan | OpKind::LessThan | OpKind::EqualTo => count == 2,
         }
-    } else {
-        let sub_packet_count = count_of_sub_packet(packet, offset);
-        current_offset += 18;
-
-        for _ in 0..sub_packet_count {
-            let (c, next_offset) = count_versions(packet, current_offset);
-            count += c;
-            current_offset = next_offset;
+    }
+
+    // None when a sum or product overflows u64
+    fn apply(&self, values: &[u64]) -> Option<u64> {
+        match self {
+            OpKind::Sum => values.iter().try_fold(0u64, |acc, v| acc.checked_add(*v)),
+            OpKind::Product => values.iter().try_fold(1u64, |acc, v| acc.checked_mul(*v)),
+            OpKind::Minimum => values.iter().min().copied(),
+            OpKind::Maximum => values.iter().max().copied(),
+            OpKind::GreaterThan => Some((values[0] > values[1]) as u64),
+            OpKind::LessThan => Some((values[0] < values[1]) as u64),
+            OpKind::EqualTo => Some((values[0] == values[1]) as u64),
         }
     }
-    return (count + v, current_offset);
 }
 
-pub fn quiz1(text: &str) -> u32 {
-    let packet = hexs2bins(text);
-    let (count, _) = count_versions(&packet, 0);
-    count
+impl fmt::Display for OpKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            OpKind::Sum => "+",
+            OpKind::Product => "*",
+            OpKind::Minimum => "min",
+            OpKind::Maximum => "max",
+            OpKind::GreaterThan => ">",
+            OpKind::LessThan => "<",
+            OpKind::EqualTo => "=",
+        };
+        write!(f, "{symbol}")
+    }
 }
 
-fn value_of_literal_packet(packet: &Vec<u8>, offset: usize) -> (u64, usize) {
-    let mut offset = offset + 6;
-    let mut value = 0;
-    loop {
-        value = value * 16 + bins2num(packet, offset + 1, 4) as u64;
-        if packet[offset] == 0 {
-            return (value, offset + 5);
-        }
-        offset += 5;
-    }
+const LITERAL: u64 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Length {
+    Bits,
+    Count,
 }
 
-pub fn count_values(packet: &Vec<u8>, offset: usize) -> (u64, usize) {
-    let _v = packet_version(packet, offset);
-    let t = packet_type(packet, offset);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    Literal {
+        version: u8,
+        value: u64,
+    },
+    Operator {
+        version: u8,
+        op: OpKind,
+        length: Length,
+        packets: Vec<Packet>,
+    },
+}
 
-    if t == LITERAL {
-        return value_of_literal_packet(packet, offset);
+impl Packet {
+    pub fn from_hex(hex: &str) -> Result<Self, ParseError> {
+        let mut reader = BitReader::from_hex(hex.trim())?;
+        Packet::parse(&mut reader)
     }
 
-    let bit_i = packet[offset + 6];
-
-    let mut values = vec![];
-    let mut current_offset = offset;
+    fn parse(reader: &mut BitReader) -> Result<Self, ParseError> {
+        let start = reader.pos;
+        let version = reader.read(3)? as u8;
+        let type_id = reader.read(3)?;
+
+        let op = match OpKind::from_type_id(type_id) {
+            Some(op) => op,
+            None => {
+                let mut value = 0;
+                loop {
+                    let group = reader.read(5)?;
+                    if value >> 60 != 0 {
+                        return Err(ParseError::LiteralOverflow { bit: start });
+                    }
+                    value = (value << 4) | (group & 0xf);
+                    if group & 0x10 == 0 {
+                        return Ok(Packet::Literal { version, value });
+                    }
+                }
+            }
+        };
+
+        let mut packets = vec![];
+        let length = if reader.read(1)? == 0 {
+            let bits = reader.read(15)? as usize;
+            let end = reader.pos + bits;
+            while reader.pos < end {
+                packets.push(Packet::parse(reader)?);
+            }
+            if reader.pos != end {
+                return Err(ParseError::LengthMismatch {
+                    bit: start,
+                    expected: bits,
+                    actual: bits + reader.pos - end,
+                });
+            }
+            Length::Bits
+        } else {
+            for _ in 0..reader.read(11)? {
+                packets.push(Packet::parse(reader)?);
+            }
+            Length::Count
+        };
+
+        if !op.accepts(packets.len()) {
+            return Err(ParseError::Arity {
+                bit: start,
+                op,
+                count: packets.len(),
+            });
+        }
 
-    if bit_i == 0 {
-        let sub_packet_len = length_of_sub_packet(packet, offset);
-        let end_of_packet = offset + 22 + sub_packet_len as usize;
+        Ok(Packet::Operator {
+            version,
+            op,
+            length,
+            packets,
+        })
+    }
 
-        current_offset += 22;
+    pub fn to_hex(&self) -> Result<String, EncodeError> {
+        let mut writer = BitWriter::default();
+        self.encode(&mut writer)?;
+        Ok(writer.to_hex())
+    }
 
-        while current_offset < end_of_packet {
-            let (c, next_offset) = count_values(packet, current_offset);
-            values.push(c);
-            current_offset = next_offset;
-        }
-    } else {
-        let sub_packet_count = count_of_sub_packet(packet, offset);
-        current_offset += 18;
-
-        for _ in 0..sub_packet_count {
-            let (c, next_offset) = count_values(packet, current_offset);
-            values.push(c);
-            current_offset = next_offset;
+    fn encode(&self, writer: &mut BitWriter) -> Result<(), EncodeError> {
+        match self {
+            Packet::Literal { version, value } => {
+                if *version > 7 {
+                    return Err(EncodeError::Version(*version));
+                }
+                writer.write(*version as u64, 3);
+                writer.write(LITERAL, 3);
+                let groups = (64 - value.leading_zeros() as usize).div_ceil(4);
+                for g in (0..groups.max(1)).rev() {
+                    writer.write((g > 0) as u64, 1);
+                    writer.write((value >> (g * 4)) & 0xf, 4);
+                }
+            }
+            Packet::Operator {
+                version,
+                op,
+                length,
+                packets,
+            } => {
+                if *version > 7 {
+                    return Err(EncodeError::Version(*version));
+                }
+                writer.write(*version as u64, 3);
+                writer.write(op.type_id(), 3);
+
+                let mut sub = BitWriter::default();
+                for p in packets {
+                    p.encode(&mut sub)?;
+                }
+                match length {
+                    Length::Bits => {
+                        if sub.bits.len() >= 1 << 15 {
+                            return Err(EncodeError::BitLength(sub.bits.len()));
+                        }
+                        writer.write(0, 1);
+                        writer.write(sub.bits.len() as u64, 15);
+                    }
+                    Length::Count => {
+                        if packets.len() >= 1 << 11 {
+                            return Err(EncodeError::Count(packets.len()));
+                        }
+                        writer.write(1, 1);
+                        writer.write(packets.len() as u64, 11);
+                    }
+                }
+                writer.append(sub);
+            }
         }
+        Ok(())
     }
 
-    let value = match t {
-        0 => values.iter().sum::<u64>(),
-        1 => values.iter().product::<u64>(),
-        2 => *values.iter().min().unwrap(),
-        3 => *values.iter().max().unwrap(),
-        5 => {
-            assert!(values.len() == 2);
-            if values[0] > values[1] {
-                1
-            } else {
-                0
-            }
+    pub fn version_sum(&self) -> u32 {
+        match self {
+            Packet::Literal { version, .. } => *version as u32,
+            Packet::Operator {
+                version, packets, ..
+            } => *version as u32 + packets.iter().map(|p| p.version_sum()).sum::<u32>(),
         }
-        6 => {
-            assert!(values.len() == 2);
-            if values[0] < values[1] {
-                1
-            } else {
-                0
-            }
+    }
+
+    // None when an intermediate sum or product overflows u64
+    pub fn value(&self) -> Option<u64> {
+        match self {
+            Packet::Literal { value, .. } => Some(*value),
+            Packet::Operator { op, packets, .. } => op.apply(
+                &packets
+                    .iter()
+                    .map(|p| p.value())
+                    .collect::<Option<Vec<_>>>()?,
+            ),
         }
-        7 => {
-            assert!(values.len() == 2);
-            if values[0] == values[1] {
-                1
-            } else {
-                0
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Literal { value, .. } => write!(f, "{value}"),
+            Packet::Operator { op, packets, .. } => {
+                write!(f, "({op}")?;
+                for p in packets {
+                    write!(f, " {p}")?;
+                }
+                write!(f, ")")
             }
         }
-        _ => panic!(),
-    };
+    }
+}
 
-    return (value, current_offset);
+pub fn quiz1(text: &str) -> u32 {
+    Packet::from_hex(text).unwrap().version_sum()
 }
 
 pub fn quiz2(text: &str) -> u64 {
-    let packet = hexs2bins(text);
-    let (value, _) = count_values(&packet, 0);
-    value
+    Packet::from_hex(text)
+        .unwrap()
+        .value()
+        .expect("value overflows u64")
 }
 
 #[cfg(test)]
@@ -193,81 +420,207 @@ mod tests {
     use super::*;
     use crate::*;
 
-    #[test]
-    fn test_value_of_literal_packet() {
-        let packet = hexs2bins("D2FE28");
-        let (v, _) = value_of_literal_packet(&packet, 0);
-        assert_eq!(v, 2021);
+    fn bits_used(hex: &str) -> usize {
+        let mut reader = BitReader::from_hex(hex).unwrap();
+        Packet::parse(&mut reader).unwrap();
+        reader.pos
     }
 
     #[test]
-    fn test_count_versions() {
-        let packet = hexs2bins("D2FE28");
-        assert_eq!(count_versions(&packet, 0), (6, 21));
-
-        let packet = hexs2bins("38006F45291200");
-        assert_eq!(count_versions(&packet, 0), (0b001 + 0b110 + 0b010, 49));
-
-        let packet = hexs2bins("EE00D40C823060");
+    fn test_literal_packet() {
         assert_eq!(
-            count_versions(&packet, 0),
-            (0b111 + 0b010 + 0b100 + 0b001, 51)
+            Packet::from_hex("D2FE28"),
+            Ok(Packet::Literal {
+                version: 6,
+                value: 2021
+            })
         );
+        assert_eq!(bits_used("D2FE28"), 21);
+    }
 
-        let packet = hexs2bins("8A004A801A8002F478");
-        assert_eq!(count_versions(&packet, 0).0, 16);
+    #[test]
+    fn test_operator_packet() {
+        let packet = Packet::from_hex("38006F45291200").unwrap();
+        assert_eq!(
+            packet,
+            Packet::Operator {
+                version: 1,
+                op: OpKind::LessThan,
+                length: Length::Bits,
+                packets: vec![
+                    Packet::Literal {
+                        version: 6,
+                        value: 10
+                    },
+                    Packet::Literal {
+                        version: 2,
+                        value: 20
+                    },
+                ]
+            }
+        );
+        assert_eq!(bits_used("38006F45291200"), 49);
 
-        let packet = hexs2bins("620080001611562C8802118E34");
-        assert_eq!(count_versions(&packet, 0).0, 12);
+        let packet = Packet::from_hex("EE00D40C823060").unwrap();
+        assert_eq!(packet.to_string(), "(max 1 2 3)");
+        assert_eq!(bits_used("EE00D40C823060"), 51);
+    }
 
-        let packet = hexs2bins("C0015000016115A2E0802F182340");
-        assert_eq!(count_versions(&packet, 0).0, 23);
+    #[test]
+    fn test_version_sum() {
+        let sum = |hex| Packet::from_hex(hex).unwrap().version_sum();
+        assert_eq!(sum("D2FE28"), 6);
+        assert_eq!(sum("38006F45291200"), 0b001 + 0b110 + 0b010);
+        assert_eq!(sum("EE00D40C823060"), 0b111 + 0b010 + 0b100 + 0b001);
+        assert_eq!(sum("8A004A801A8002F478"), 16);
+        assert_eq!(sum("620080001611562C8802118E34"), 12);
+        assert_eq!(sum("C0015000016115A2E0802F182340"), 23);
+        assert_eq!(sum("A0016C880162017C3686B18A3D4780"), 31);
+    }
 
-        let packet = hexs2bins("A0016C880162017C3686B18A3D4780");
-        assert_eq!(count_versions(&packet, 0).0, 31);
+    #[test]
+    fn test_values() {
+        let value = |hex| Packet::from_hex(hex).unwrap().value().unwrap();
+        assert_eq!(value("C200B40A82"), 3);
+        assert_eq!(value("04005AC33890"), 54);
+        assert_eq!(value("880086C3E88112"), 7);
+        assert_eq!(value("CE00C43D881120"), 9);
+        assert_eq!(value("D8005AC2A8F0"), 1);
+        assert_eq!(value("F600BC2D8F"), 0);
+        assert_eq!(value("9C005AC2F8F0"), 0);
+        assert_eq!(value("9C0141080250320F1802104A08"), 1);
     }
 
     #[test]
-    fn test_end_of_literal_packet() {
-        let packet = hexs2bins("D2FE28");
-        assert_eq!(end_of_literal_packet(&packet, 0), 21);
+    fn test_pretty_print() {
+        let expr = |hex| Packet::from_hex(hex).unwrap().to_string();
+        assert_eq!(expr("C200B40A82"), "(+ 1 2)");
+        assert_eq!(expr("04005AC33890"), "(* 6 9)");
+        assert_eq!(expr("9C0141080250320F1802104A08"), "(= (+ 1 3) (* 2 2))");
     }
 
     #[test]
-    fn test_packet_component() {
-        let packet = hexs2bins("D2FE28");
-        assert_eq!(packet_version(&packet, 0), 6);
-        assert_eq!(packet_type(&packet, 0), 4);
+    fn test_round_trip() {
+        for hex in [
+            "D2FE28",
+            "38006F45291200",
+            "EE00D40C823060",
+            "8A004A801A8002F478",
+            "620080001611562C8802118E34",
+            "C0015000016115A2E0802F182340",
+            "A0016C880162017C3686B18A3D4780",
+            "9C0141080250320F1802104A08",
+        ] {
+            let packet = Packet::from_hex(hex).unwrap();
+            let encoded = packet.to_hex().unwrap();
+            assert!(hex.starts_with(&encoded), "{} -> {}", hex, encoded);
+            assert!(hex[encoded.len()..].chars().all(|c| c == '0'));
+            assert_eq!(Packet::from_hex(&encoded), Ok(packet));
+        }
+    }
 
-        let packet = hexs2bins("38006F45291200");
-        assert_eq!(packet_version(&packet, 22), 6);
-        assert_eq!(packet_type(&packet, 22), 4);
-        assert_eq!(length_of_sub_packet(&packet, 0), 27);
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Packet::from_hex("D2FG28"),
+            Err(ParseError::InvalidHex { pos: 3, c: 'G' })
+        );
+        assert_eq!(
+            Packet::from_hex("D2FE"),
+            Err(ParseError::Truncated { bit: 16, needed: 5 })
+        );
+        assert_eq!(
+            Packet::from_hex("38006F452912"),
+            Err(ParseError::Truncated { bit: 44, needed: 1 })
+        );
+        // `<` with a single literal sub-packet
+        assert_eq!(
+            Packet::from_hex("38002D450"),
+            Err(ParseError::Arity {
+                bit: 0,
+                op: OpKind::LessThan,
+                count: 1
+            })
+        );
+    }
 
-        let packet = hexs2bins("EE00D40C823060");
-        assert_eq!(count_of_sub_packet(&packet, 0), 3);
+    fn literal(value: u64) -> Packet {
+        Packet::Literal { version: 0, value }
     }
 
     #[test]
-    fn test_bins2num() {
-        assert_eq!(bins2num(&vec![1, 1, 0], 0, 3), 6);
+    fn test_literal_overflow() {
+        let hex = |value| literal(value).to_hex().unwrap();
+        assert_eq!(Packet::from_hex(&hex(u64::MAX)), Ok(literal(u64::MAX)));
+
+        // 17 groups of 0xf: one group more than a u64 holds
+        let mut writer = BitWriter::default();
+        writer.write(0, 3);
+        writer.write(LITERAL, 3);
+        for g in (0..17).rev() {
+            writer.write((g > 0) as u64, 1);
+            writer.write(0xf, 4);
+        }
+        assert_eq!(
+            Packet::from_hex(&writer.to_hex()),
+            Err(ParseError::LiteralOverflow { bit: 0 })
+        );
     }
 
     #[test]
-    fn test_hex2bins() {
-        assert_eq!(hex2bins('0'), vec![0, 0, 0, 0]);
-        assert_eq!(hex2bins('F'), vec![1, 1, 1, 1]);
+    fn test_value_overflow() {
+        let op = |op, values: &[u64]| Packet::Operator {
+            version: 0,
+            op,
+            length: Length::Count,
+            packets: values.iter().map(|v| literal(*v)).collect(),
+        };
+        assert_eq!(op(OpKind::Sum, &[u64::MAX, 0]).value(), Some(u64::MAX));
+        assert_eq!(op(OpKind::Sum, &[u64::MAX, 1]).value(), None);
+        assert_eq!(
+            op(OpKind::Product, &[1 << 32, 1 << 31]).value(),
+            Some(1 << 63)
+        );
+        assert_eq!(op(OpKind::Product, &[1 << 32, 1 << 32]).value(), None);
+        assert_eq!(op(OpKind::Product, &[]).value(), Some(1));
+
+        // an overflow below a comparison is still reported
+        let nested = Packet::Operator {
+            version: 0,
+            op: OpKind::EqualTo,
+            length: Length::Bits,
+            packets: vec![op(OpKind::Sum, &[u64::MAX, 1]), literal(0)],
+        };
+        assert_eq!(nested.value(), None);
     }
 
     #[test]
-    fn test_hexs2bins() {
+    fn test_encode_errors() {
+        let with = |length, packets| Packet::Operator {
+            version: 0,
+            op: OpKind::Sum,
+            length,
+            packets,
+        };
+        let packet = Packet::Literal {
+            version: 8,
+            value: 1,
+        };
+        assert_eq!(packet.to_hex(), Err(EncodeError::Version(8)));
+
+        // each literal below 16 takes 11 bits
         assert_eq!(
-            hexs2bins("D2FE28"),
-            "110100101111111000101000"
-                .chars()
-                .map(|c| c.to_digit(10).unwrap() as u8)
-                .collect::<Vec<u8>>()
+            with(Length::Count, vec![literal(1); 1 << 11]).to_hex(),
+            Err(EncodeError::Count(1 << 11))
         );
+        let fits = with(Length::Bits, vec![literal(1); 2978]);
+        let encoded = fits.to_hex().unwrap();
+        assert_eq!(Packet::from_hex(&encoded), Ok(fits));
+        assert_eq!(
+            with(Length::Bits, vec![literal(1); 2979]).to_hex(),
+            Err(EncodeError::BitLength(2979 * 11))
+        );
+        assert!(with(Length::Count, vec![literal(1); 2047]).to_hex().is_ok());
     }
 
     #[test]
@@ -276,33 +629,6 @@ mod tests {
         assert_eq!(quiz1(text.as_str().trim()), 993);
     }
 
-    #[test]
-    fn test_count_values() {
-        let (v, _) = count_values(&hexs2bins("C200B40A82"), 0);
-        assert_eq!(v, 3);
-
-        let (v, _) = count_values(&hexs2bins("04005AC33890"), 0);
-        assert_eq!(v, 54);
-
-        let (v, _) = count_values(&hexs2bins("880086C3E88112"), 0);
-        assert_eq!(v, 7);
-
-        let (v, _) = count_values(&hexs2bins("CE00C43D881120"), 0);
-        assert_eq!(v, 9);
-
-        let (v, _) = count_values(&hexs2bins("D8005AC2A8F0"), 0);
-        assert_eq!(v, 1);
-
-        let (v, _) = count_values(&hexs2bins("F600BC2D8F"), 0);
-        assert_eq!(v, 0);
-
-        let (v, _) = count_values(&hexs2bins("9C005AC2F8F0"), 0);
-        assert_eq!(v, 0);
-
-        let (v, _) = count_values(&hexs2bins("9C0141080250320F1802104A08"), 0);
-        assert_eq!(v, 1);
-    }
-
     #[test]
     fn run_2021_d16_quiz2() {
         let text = read_file("data/2021/input16.txt");
@@ -1,305 +1,219 @@
-pub const L: i32 = -1;
-pub const R: i32 = -2;
-pub const C: i32 = -3;
-
-type FishNum = Vec<i32>;
-
-pub fn to_fish_num(text: &str) -> FishNum {
-    text.chars().fold(Vec::new(), |mut acc, c| {
-        let len = acc.len();
-        match c {
-            ',' => acc.push(C),
-            '[' => {
-                if len == 0 {
-                    acc.push(L);
-                } else if acc[len - 1] == C {
-                    acc[len - 1] = L
-                } else {
-                    acc.push(L);
-                }
-            }
-            ']' => {
-                if acc[len - 1] == C {
-                    acc[len - 1] = R;
-                } else {
-                    acc.push(R)
-                }
-            }
-            x if x.is_digit(10) => {
-                let n = x.to_digit(10).unwrap() as i32;
-                if len == 0 {
-                    acc.push(n)
-                } else {
-                    let i = len - 1;
-                    if acc[i] == C {
-                        acc[i] = n;
-                    } else if acc[i] == L {
-                        acc.push(n);
-                    } else if acc[i] != L && acc[i] != R {
-                        acc[i] = acc[i] * 10 + n;
-                    }
-                }
-            }
-            _ => panic!(),
-        }
-        acc
-    })
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+use SnailNum::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnailNum {
+    Regular(u32),
+    Pair(Box<SnailNum>, Box<SnailNum>),
 }
 
-pub fn add(left: &FishNum, right: &FishNum) -> FishNum {
-    if left.is_empty() {
-        right.to_vec()
-    } else if right.is_empty() {
-        left.to_vec()
-    } else {
-        let mut sum = vec![L];
-        sum.extend(left.iter());
-        sum.extend(right.iter());
-        sum.push(R);
-        sum
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Explode,
+    Split,
 }
 
-pub fn to_string(num: &FishNum) -> String {
-    num.iter()
-        .fold((String::new(), String::new()), |(mut acc, last), n| {
-            match *n {
-                L => {
-                    if last == "]" {
-                        acc.push(',');
-                    }
-                    acc.push('[');
-                    return (acc, "[".to_string());
-                }
-                R => {
-                    acc.push(']');
-                    return (acc, "]".to_string());
-                }
-                _ => {
-                    if last != "[" {
-                        acc.push(',');
-                    }
-                    let n = format!("{}", n);
-                    acc.push_str(&n);
-                    return (acc, n);
-                }
-            };
-        })
-        .0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub action: Action,
+    pub before: SnailNum,
+    pub after: SnailNum,
 }
 
-pub fn explode_pair_loc(num: &FishNum) -> Option<usize> {
-    let mut loc = 0;
-    let mut count = 0;
-    let mut max_count = 0;
-
-    for (i, n) in num.iter().enumerate() {
-        match *n {
-            L => {
-                count += 1;
-                if count > max_count {
-                    max_count = count;
-                    loc = i;
-                }
-            }
-            R => {
-                count -= 1;
-            }
-            _ => {}
-        }
+impl SnailNum {
+    pub fn pair(left: SnailNum, right: SnailNum) -> Self {
+        Pair(Box::new(left), Box::new(right))
     }
 
-    if max_count > 4 {
-        Some(loc)
-    } else {
-        None
+    pub fn magnitude(&self) -> u32 {
+        match self {
+            Regular(n) => *n,
+            Pair(l, r) => 3 * l.magnitude() + 2 * r.magnitude(),
+        }
     }
-}
 
-pub fn explode(num: &mut FishNum, loc: usize) {
-    let mut s = loc;
-    let left = loop {
-        if num[s] != L && num[s] != R {
-            break Some(s);
+    fn add_leftmost(&mut self, value: u32) {
+        match self {
+            Regular(n) => *n += value,
+            Pair(l, _) => l.add_leftmost(value),
         }
-        if s == 0 {
-            break None;
-        } else {
-            s -= 1;
-        }
-    };
+    }
 
-    s = loc + 3;
+    fn add_rightmost(&mut self, value: u32) {
+        match self {
+            Regular(n) => *n += value,
+            Pair(_, r) => r.add_rightmost(value),
+        }
+    }
 
-    let right = loop {
-        if num[s] != L && num[s] != R {
-            break Some(s);
+    // returns the values still to be carried left and right of this node
+    fn explode_at(&mut self, depth: usize) -> Option<(Option<u32>, Option<u32>)> {
+        let (l, r) = match self {
+            Regular(_) => return None,
+            Pair(l, r) => (l, r),
+        };
+        if depth >= 4 {
+            if let (Regular(a), Regular(b)) = (l.as_ref(), r.as_ref()) {
+                let carry = (Some(*a), Some(*b));
+                *self = Regular(0);
+                return Some(carry);
+            }
         }
-        if s == num.len() - 1 {
-            break None;
-        } else {
-            s += 1;
+        if let Some((a, b)) = l.explode_at(depth + 1) {
+            if let Some(b) = b {
+                r.add_leftmost(b);
+            }
+            return Some((a, None));
         }
-    };
+        if let Some((a, b)) = r.explode_at(depth + 1) {
+            if let Some(a) = a {
+                l.add_rightmost(a);
+            }
+            return Some((None, b));
+        }
+        None
+    }
+
+    pub fn explode(&mut self) -> bool {
+        self.explode_at(0).is_some()
+    }
 
-    if let Some(left) = left {
-        num[left] += num[loc + 1];
+    pub fn split(&mut self) -> bool {
+        match self {
+            Regular(n) if *n >= 10 => {
+                *self = SnailNum::pair(Regular(*n / 2), Regular(n.div_ceil(2)));
+                true
+            }
+            Regular(_) => false,
+            Pair(l, r) => l.split() || r.split(),
+        }
     }
 
-    if let Some(right) = right {
-        num[right] += num[loc + 2];
+    pub fn reduce(&mut self) {
+        while self.explode() || self.split() {}
     }
 
-    (0..3).for_each(|_| {
-        num.remove(loc);
-    });
-    num[loc] = 0;
-}
+    pub fn reduce_traced(&mut self) -> Vec<Step> {
+        let mut steps = vec![];
+        loop {
+            let before = self.clone();
+            let action = if self.explode() {
+                Action::Explode
+            } else if self.split() {
+                Action::Split
+            } else {
+                return steps;
+            };
+            steps.push(Step {
+                action,
+                before,
+                after: self.clone(),
+            });
+        }
+    }
 
-pub fn split_loc(num: &FishNum) -> Option<usize> {
-    num.iter().position(|n| *n != L && *n != R && *n >= 10)
+    pub fn add_traced(self, rhs: SnailNum) -> (SnailNum, Vec<Step>) {
+        let mut sum = SnailNum::pair(self, rhs);
+        let steps = sum.reduce_traced();
+        (sum, steps)
+    }
 }
 
-pub fn split(num: &mut FishNum, loc: usize) {
-    let value = num[loc];
-    let left = value / 2;
-    let right = if value % 2 == 0 {
-        value / 2
-    } else {
-        (value + 1) / 2
-    };
+impl Add for SnailNum {
+    type Output = SnailNum;
 
-    let pair = vec![L, left, right, R];
-    num.splice(loc..(loc + 1), pair);
+    fn add(self, rhs: SnailNum) -> SnailNum {
+        let mut sum = SnailNum::pair(self, rhs);
+        sum.reduce();
+        sum
+    }
 }
 
-pub fn reduce(num: &mut FishNum) {
-    reduce_p(num, false)
+impl fmt::Display for SnailNum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Regular(n) => write!(f, "{n}"),
+            Pair(l, r) => write!(f, "[{l},{r}]"),
+        }
+    }
 }
 
-pub fn reduce_p(num: &mut FishNum, p: bool) {
-    if p {
-        println!("start {}", to_string(&num));
-    }
-    loop {
-        let mut br = true;
-
-        while let Some(loc) = explode_pair_loc(&num) {
-            br = false;
-            explode(num, loc);
-            if p {
-                println!("explode {}", to_string(&num));
-            }
+fn parse_num(text: &[u8], pos: &mut usize) -> Result<SnailNum, String> {
+    let expect = |pos: &mut usize, c: u8| {
+        if text.get(*pos) == Some(&c) {
+            *pos += 1;
+            Ok(())
+        } else {
+            Err(format!("expected '{}' at {}", c as char, pos))
         }
+    };
 
-        if let Some(loc) = split_loc(&num) {
-            br = false;
-            split(num, loc);
-            if p {
-                println!("split {}", to_string(&num));
-            }
+    match text.get(*pos) {
+        Some(b'[') => {
+            *pos += 1;
+            let left = parse_num(text, pos)?;
+            expect(pos, b',')?;
+            let right = parse_num(text, pos)?;
+            expect(pos, b']')?;
+            Ok(SnailNum::pair(left, right))
         }
-
-        if br {
-            break;
+        Some(c) if c.is_ascii_digit() => {
+            let start = *pos;
+            while text.get(*pos).is_some_and(|c| c.is_ascii_digit()) {
+                *pos += 1;
+            }
+            std::str::from_utf8(&text[start..*pos])
+                .unwrap()
+                .parse()
+                .map(Regular)
+                .map_err(|e| format!("{e} at {start}"))
         }
+        _ => Err(format!("expected '[' or a digit at {pos}")),
     }
 }
 
-pub fn sum(nums: &Vec<FishNum>) -> FishNum {
-    sum_p(nums, false)
-}
+impl FromStr for SnailNum {
+    type Err = String;
 
-pub fn sum_p(nums: &Vec<FishNum>, p: bool) -> FishNum {
-    nums.iter().fold(Vec::new(), |acc, num| {
-        let mut num = add(&acc, num);
-        reduce(&mut num);
-        if p {
-            println!("{}", to_string(&num));
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim().as_bytes();
+        let mut pos = 0;
+        let num = parse_num(text, &mut pos)?;
+        if pos != text.len() {
+            return Err(format!("trailing characters at {pos}"));
         }
-        num
-    })
+        Ok(num)
+    }
 }
 
-pub fn load_data(text: &str) -> Vec<FishNum> {
+pub fn load_data(text: &str) -> Vec<SnailNum> {
     text.trim()
         .lines()
-        .map(|line| to_fish_num(line))
-        .collect::<Vec<FishNum>>()
+        .map(|line| line.parse().unwrap())
+        .collect()
 }
 
-fn sub_num(num: &FishNum, mut offset: usize) -> (FishNum, usize) {
-    let mut ret = vec![];
-    let mut left = 0;
-    loop {
-        let token = num[offset];
-        if token == L {
-            left += 1;
-        } else if token == R {
-            left -= 1;
-        }
-        ret.push(token);
-
-        if left == 0 {
-            return (ret, offset);
-        }
-
-        offset += 1;
-    }
+pub fn sum(nums: Vec<SnailNum>) -> Option<SnailNum> {
+    nums.into_iter().reduce(|acc, num| acc + num)
 }
 
-fn split_num(num: &FishNum) -> (FishNum, FishNum) {
-    let (left, offset) = sub_num(num, 1);
-    let (right, _) = sub_num(num, offset + 1);
-    (left, right)
+pub fn quiz1(text: &str) -> u32 {
+    sum(load_data(text)).map_or(0, |n| n.magnitude())
 }
 
-fn is_num(num: &FishNum) -> bool {
-    num[0] != L && num[0] != R
-}
-
-pub fn magnitude(num: &FishNum) -> i32 {
-    let (left, right) = split_num(num);
-
-    let left = if is_num(&left) {
-        left[0]
-    } else {
-        magnitude(&left)
-    };
-
-    let right = if is_num(&right) {
-        right[0]
-    } else {
-        magnitude(&right)
-    };
-
-    3 * left + 2 * right
-}
-
-pub fn quiz2(text: &str) -> i32 {
+pub fn quiz2(text: &str) -> u32 {
     let nums = load_data(text);
-    let len = nums.len();
-    let mut perms = vec![];
-    for i in 0..len {
-        for j in 0..len {
+    let mut max = 0;
+    for (i, a) in nums.iter().enumerate() {
+        for (j, b) in nums.iter().enumerate() {
             if i != j {
-                perms.push((i, j));
-                perms.push((j, i));
+                max = max.max((a.clone() + b.clone()).magnitude());
             }
         }
     }
-
-    let mut max = 0;
-
-    for (i, j) in perms {
-        let mut n = add(&nums[i], &nums[j]);
-        reduce(&mut n);
-        let v = magnitude(&n);
-
-        if v > max {
-            max = v;
-        }
-    }
     max
 }
 
@@ -309,13 +223,15 @@ mod tests {
     use super::*;
     use crate::*;
 
+    fn num(text: &str) -> SnailNum {
+        text.parse().unwrap()
+    }
+
     #[test]
     fn test_magnitude() {
-        assert_eq!(magnitude(&to_fish_num("[[1,2],[[3,4],5]]")), 143);
+        assert_eq!(num("[[1,2],[[3,4],5]]").magnitude(), 143);
         assert_eq!(
-            magnitude(&to_fish_num(
-                "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]"
-            )),
+            num("[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]").magnitude(),
             4140
         );
     }
@@ -323,17 +239,17 @@ mod tests {
     #[test]
     fn test_sum() {
         assert_eq!(
-            sum(&load_data(
+            sum(load_data(
                 "[1,1]
 [2,2]
 [3,3]
 [4,4]"
             )),
-            to_fish_num("[[[[1,1],[2,2]],[3,3]],[4,4]]")
+            Some(num("[[[[1,1],[2,2]],[3,3]],[4,4]]"))
         );
 
         assert_eq!(
-            sum(&load_data(
+            sum(load_data(
                 "[1,1]
 [2,2]
 [3,3]
@@ -341,11 +257,11 @@ mod tests {
 [5,5]
 "
             )),
-            to_fish_num("[[[[3,0],[5,3]],[4,4]],[5,5]]")
+            Some(num("[[[[3,0],[5,3]],[4,4]],[5,5]]"))
         );
 
         assert_eq!(
-            sum(&load_data(
+            sum(load_data(
                 "[1,1]
 [2,2]
 [3,3]
@@ -353,13 +269,12 @@ mod tests {
 [5,5]
 [6,6]"
             )),
-            to_fish_num("[[[[5,0],[7,4]],[5,5]],[6,6]]")
+            Some(num("[[[[5,0],[7,4]],[5,5]],[6,6]]"))
         );
 
         assert_eq!(
-            sum_p(
-                &load_data(
-                    "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
+            sum(load_data(
+                "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
 [7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
 [[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]
 [[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]
@@ -369,44 +284,61 @@ mod tests {
 [1,[[[9,3],9],[[9,0],[0,7]]]]
 [[[5,[7,4]],7],1]
 [[[[4,2],2],6],[8,7]]"
-                ),
-                false
-            ),
-            to_fish_num("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]")
-        )
+            )),
+            Some(num("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]"))
+        );
+        assert_eq!(sum(vec![]), None);
     }
 
     #[test]
     fn test_reduce() {
-        let mut num = to_fish_num("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]");
-        reduce(&mut num);
-        assert_eq!(num, to_fish_num("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"));
+        let mut n = num("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]");
+        n.reduce();
+        assert_eq!(n, num("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"));
 
-        let mut num = add(
-            &to_fish_num("[[[[7,7],[7,7]],[[8,7],[8,7]]],[[[7,0],[7,7]],9]]"),
-            &to_fish_num("[[[[4,2],2],6],[8,7]]"),
+        assert_eq!(
+            num("[[[[7,7],[7,7]],[[8,7],[8,7]]],[[[7,0],[7,7]],9]]") + num("[[[[4,2],2],6],[8,7]]"),
+            num("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]")
         );
-        reduce_p(&mut num, false);
+    }
+
+    #[test]
+    fn test_reduce_traced() {
+        let (n, steps) = num("[[[[4,3],4],4],[7,[[8,4],9]]]").add_traced(num("[1,1]"));
+        assert_eq!(n.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+
+        let expected = [
+            (Action::Explode, "[[[[0,7],4],[7,[[8,4],9]]],[1,1]]"),
+            (Action::Explode, "[[[[0,7],4],[15,[0,13]]],[1,1]]"),
+            (Action::Split, "[[[[0,7],4],[[7,8],[0,13]]],[1,1]]"),
+            (Action::Split, "[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]"),
+            (Action::Explode, "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"),
+        ];
+        assert_eq!(steps.len(), expected.len());
         assert_eq!(
-            num,
-            to_fish_num("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]")
+            steps[0].before.to_string(),
+            "[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]"
         );
+        for (i, (step, (action, after))) in steps.iter().zip(expected).enumerate() {
+            assert_eq!(step.action, action);
+            assert_eq!(step.after.to_string(), after);
+            if i > 0 {
+                assert_eq!(step.before, steps[i - 1].after);
+            }
+        }
     }
 
     #[test]
     fn test_split() {
-        let mut num = to_fish_num("[[[[0,7],4],[15,[0,13]]],[1,1]]");
-
-        let loc = split_loc(&num);
-        assert_eq!(loc, Some(10));
-
-        split(&mut num, loc.unwrap());
-        assert_eq!(num, to_fish_num("[[[[0,7],4],[[7,8],[0,13]]],[1,1]]"));
+        let mut n = num("[[[[0,7],4],[15,[0,13]]],[1,1]]");
+        assert!(n.split());
+        assert_eq!(n, num("[[[[0,7],4],[[7,8],[0,13]]],[1,1]]"));
+        assert!(!num("[[1,9],0]").split());
     }
 
     #[test]
     fn test_explode() {
-        let data = vec![
+        let data = [
             ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
             ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
             ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
@@ -421,98 +353,46 @@ mod tests {
         ];
 
         data.iter().for_each(|(input, expected)| {
-            let mut input = to_fish_num(input);
-            let expected = to_fish_num(expected);
-            let loc = explode_pair_loc(&input);
-            explode(&mut input, loc.unwrap());
-            assert_eq!(input, expected);
+            let mut input = num(input);
+            assert!(input.explode());
+            assert_eq!(input, num(expected));
         });
 
-        let mut num = to_fish_num("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]");
-
-        let loc = explode_pair_loc(&num);
-        explode(&mut num, loc.unwrap());
-        assert_eq!(
-            num,
-            vec![L, L, L, L, 0, 7, R, 4, R, L, 7, L, L, 8, 4, R, 9, R, R, R, L, 1, 1, R, R]
-        );
-
-        let loc = explode_pair_loc(&num);
-        explode(&mut num, loc.unwrap());
-        assert_eq!(
-            num,
-            vec![L, L, L, L, 0, 7, R, 4, R, L, 15, L, 0, 13, R, R, R, L, 1, 1, R, R]
-        );
+        assert!(!num("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").explode());
     }
 
     #[test]
-    fn test_to_fish_num() {
+    fn test_parse_and_print() {
+        let text = "[[[[0,7],4],[15,[0,13]]],[1,1]]";
+        assert_eq!(num(text).to_string(), text);
         assert_eq!(
-            to_fish_num("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]"),
-            vec![
-                L, L, L, L, L, 4, 3, R, 4, R, 4, R, L, 7, L, L, 8, 4, R, 9, R, R, R, L, 1, 1, R, R
-            ]
+            num("[1,[2,3]]"),
+            SnailNum::pair(Regular(1), SnailNum::pair(Regular(2), Regular(3)))
         );
-
         assert_eq!(
-            to_fish_num("[[[[0,7],4],[15,[0,13]]],[1,1]]"),
-            vec![L, L, L, L, 0, 7, R, 4, R, L, 15, L, 0, 13, R, R, R, L, 1, 1, R, R]
-        )
-    }
-
-    #[test]
-    fn test_print() {
-        let left = vec![L, 1, 2, R];
-        assert_eq!(to_string(&left), "[1,2]".to_string());
-    }
-
-    #[test]
-    fn test_add() {
-        let left = vec![L, 1, 2, R];
-        let right = vec![L, L, 3, 4, R, 5, R];
-        assert_eq!(
-            add(&left, &right),
-            vec![L, L, 1, 2, R, L, L, 3, 4, R, 5, R, R]
+            "[1,2".parse::<SnailNum>(),
+            Err("expected ']' at 4".to_string())
         );
-
-        assert_eq!(add(&vec![], &right), right);
-        assert_eq!(add(&left, &vec![]), left);
-    }
-
-    #[test]
-    fn test_exp_pair_loc() {
-        assert_eq!(
-            explode_pair_loc(&to_fish_num("[[[[[1,2],[3,4]],[5,6]],[6,7]],[8,9]]")),
-            Some(4)
-        );
-        assert_eq!(
-            explode_pair_loc(&to_fish_num(
-                "[[[[7,7],[[0,7],[[7,8],7]]],[[[0,8],[7,7]],9]],[[[[4,2],2],6],[8,7]]]"
-            )),
-            Some(13)
-        );
-
         assert_eq!(
-            explode_pair_loc(&to_fish_num("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]")),
-            Some(4)
+            "[1]".parse::<SnailNum>(),
+            Err("expected ',' at 2".to_string())
         );
-
         assert_eq!(
-            explode_pair_loc(&to_fish_num("[[[[0,7],4],[7,[[8,4],9]]],[1,1]]")),
-            Some(12)
+            "[1,2]]".parse::<SnailNum>(),
+            Err("trailing characters at 5".to_string())
         );
+        assert!("[a,2]".parse::<SnailNum>().is_err());
+    }
 
-        assert_eq!(
-            explode_pair_loc(&to_fish_num("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")),
-            None
-        );
+    #[test]
+    fn test_add() {
+        assert_eq!(num("[1,2]") + num("[[3,4],5]"), num("[[1,2],[[3,4],5]]"));
     }
 
     #[test]
     fn run_y2021_d18_quiz1() {
         let text = read_file("data/2021/input18.txt");
-        let num = sum(&load_data(text.as_str()));
-        assert_eq!(magnitude(&num), 3892);
+        assert_eq!(quiz1(text.as_str()), 3892);
     }
 
     #[test]
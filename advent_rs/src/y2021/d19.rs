@@ -1,99 +1,147 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
-use crate::permutations;
+use crate::read_file;
+use crate::rotation::{Rotation, Vec3};
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct Pt {
-    pub x: i32,
-    pub y: i32,
-    pub z: i32,
-}
+const FILE_NAME: &str = "data/2021/input19.txt";
 
-impl Pt {
-    fn new(x: i32, y: i32, z: i32) -> Self {
-        Self { x, y, z }
-    }
-}
+// scanners have to share this many beacons to be aligned
+const OVERLAP: usize = 12;
 
-pub fn load_data2(text: &str) -> Vec<Vec<Pt>> {
-    text.split("\n\n")
+pub fn load_data(text: &str) -> Vec<Vec<Vec3>> {
+    text.trim()
+        .split("\n\n")
         .map(|s| {
             s.lines()
                 .skip(1)
                 .map(|line| {
-                    let mut s = line.split(',');
-                    let x = s.next().unwrap().parse::<i32>().unwrap();
-                    let y = s.next().unwrap().parse::<i32>().unwrap();
-                    let z = s.next().unwrap().parse::<i32>().unwrap();
-                    Pt::new(x, y, z)
+                    let mut s = line.split(',').map(|v| v.parse::<i32>().unwrap());
+                    [s.next().unwrap(), s.next().unwrap(), s.next().unwrap()]
                 })
                 .collect()
         })
         .collect()
 }
 
-pub fn load_data(text: &str) -> Vec<(Vec<i32>, Vec<i32>, Vec<i32>)> {
-    text.split("\n\n")
-        .map(|s| {
-            s.lines().skip(1).fold(
-                (Vec::new(), Vec::new(), Vec::new()),
-                |(mut xs, mut ys, mut zs), line| {
-                    let mut s = line.split(',');
-                    let x = s.next().unwrap().parse::<i32>().unwrap();
-                    let y = s.next().unwrap().parse::<i32>().unwrap();
-                    let z = s.next().unwrap().parse::<i32>().unwrap();
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
 
-                    xs.push(x);
-                    ys.push(y);
-                    zs.push(z);
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
 
-                    (xs, ys, zs)
-                },
-            )
-        })
-        .collect()
+fn manhattan(a: Vec3, b: Vec3) -> i32 {
+    sub(a, b).iter().map(|v| v.abs()).sum()
+}
+
+// squared distances between every pair of beacons, which no rotation or offset changes
+pub fn fingerprint(beacons: &[Vec3]) -> HashMap<i64, usize> {
+    let mut dists = HashMap::new();
+    for (i, a) in beacons.iter().enumerate() {
+        for b in &beacons[i + 1..] {
+            let d = sub(*a, *b).iter().map(|v| (*v as i64).pow(2)).sum();
+            *dists.entry(d).or_insert(0) += 1;
+        }
+    }
+    dists
 }
 
-pub fn mm(m1: &Vec<i32>, m2: &Vec<i32>) -> Vec<i32> {
-    let mut v = vec![0; 9];
-    for r in 0..3 {
-        for c in 0..3 {
-            v[r * 3 + c] = m1[r * 3 + 0] * m2[r * 0 + c]
-                + m1[r * 3 + 1] * m2[r * 1 + c]
-                + m1[r * 3 + 2] * m2[r * 2 + c];
+fn shared_pairs(f1: &HashMap<i64, usize>, f2: &HashMap<i64, usize>) -> usize {
+    f1.iter()
+        .map(|(d, n)| f2.get(d).map_or(0, |m| *n.min(m)))
+        .sum()
+}
+
+// rotation and offset taking `beacons` into the frame of `known`
+pub fn align(known: &[Vec3], beacons: &[Vec3]) -> Option<(Rotation, Vec3)> {
+    for rotation in Rotation::all() {
+        let rotated = beacons
+            .iter()
+            .map(|b| rotation.apply(*b))
+            .collect::<Vec<_>>();
+        let mut votes = HashMap::new();
+        for k in known {
+            for b in rotated.iter() {
+                let offset = sub(*k, *b);
+                let count = votes.entry(offset).or_insert(0);
+                *count += 1;
+                if *count >= OVERLAP {
+                    return Some((rotation, offset));
+                }
+            }
         }
     }
-    v
+    None
 }
 
-pub fn rotations() -> HashSet<Vec<i32>> {
-    let rx = vec![1, 0, 0, 0, 0, -1, 0, 1, 0];
-    let ry = vec![0, 0, 1, 0, 1, 0, -1, 0, 0];
-    let rz = vec![0, -1, 0, 1, 0, 0, 0, 0, 1];
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeaconMap {
+    // positions relative to scanner 0
+    pub scanners: Vec<Vec3>,
+    pub beacons: BTreeSet<Vec3>,
+}
 
-    let mut rots = HashSet::new();
-    rots.insert(rx);
-    rots.insert(ry);
-    rots.insert(rz);
+impl BeaconMap {
+    pub fn max_distance(&self) -> i32 {
+        let mut max = 0;
+        for a in self.scanners.iter() {
+            for b in self.scanners.iter() {
+                max = max.max(manhattan(*a, *b));
+            }
+        }
+        max
+    }
+}
 
-    let perms = permutations(&rots);
-    println!("{:?}", perms);
+// None when some scanner can't be connected to scanner 0
+pub fn solve(scanners: &[Vec<Vec3>]) -> Option<BeaconMap> {
+    let fingerprints = scanners.iter().map(|s| fingerprint(s)).collect::<Vec<_>>();
+    let needed = OVERLAP * (OVERLAP - 1) / 2;
 
-    let mut rotations = HashSet::new();
+    let mut placed: Vec<Option<(Vec3, Vec<Vec3>)>> = vec![None; scanners.len()];
+    placed[0] = Some(([0, 0, 0], scanners[0].clone()));
+    let mut queue = vec![0];
+    let mut tried = HashSet::new();
 
-    for rots in perms.iter() {
-        if rots.len() == 1 {
-            rotations.insert(rots[0].clone());
-        } else {
-            let mut r = vec![1, 0, 0, 0, 1, 0, 0, 0, 1];
-            for i in 0..rots.len() {
-                r = mm(&r, &rots[i]);
+    while let Some(i) = queue.pop() {
+        let known = placed[i].as_ref().unwrap().1.clone();
+        for j in 0..scanners.len() {
+            if placed[j].is_some() || !tried.insert((i, j)) {
+                continue;
+            }
+            if shared_pairs(&fingerprints[i], &fingerprints[j]) < needed {
+                continue;
+            }
+            if let Some((rotation, offset)) = align(&known, &scanners[j]) {
+                let beacons = scanners[j]
+                    .iter()
+                    .map(|b| add(rotation.apply(*b), offset))
+                    .collect();
+                placed[j] = Some((offset, beacons));
+                queue.push(j);
             }
-            rotations.insert(r);
         }
     }
 
-    rotations
+    let placed = placed.into_iter().collect::<Option<Vec<_>>>()?;
+    Some(BeaconMap {
+        scanners: placed.iter().map(|(pos, _)| *pos).collect(),
+        beacons: placed
+            .iter()
+            .flat_map(|(_, bs)| bs.iter().copied())
+            .collect(),
+    })
+}
+
+pub(crate) fn quiz1() -> usize {
+    let scanners = load_data(&read_file(FILE_NAME));
+    solve(&scanners).unwrap().beacons.len()
+}
+
+pub(crate) fn quiz2() -> i32 {
+    let scanners = load_data(&read_file(FILE_NAME));
+    solve(&scanners).unwrap().max_distance()
 }
 
 #[cfg(test)]
@@ -101,43 +149,47 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_arrangements() {
-        // let pt = Pt::new(1, 2, 3);
-        // let arrs = vec![
-        //     Pt::new(1, 2, 3), // x, y, z, xy, yz, xz, xyz
-        //     Pt::new(1, 3, 2),
-        //     Pt::new(2, 1, 3),
-        //     Pt::new(2, 3, 1),
-        //     Pt::new(3, 1, 2),
-        //     Pt::new(3, 2, 1),
-        // ];
-        // assert_eq!(pt.arrangements(), arrs);
-        assert_eq!(rotations(), HashSet::new());
+    fn test_load_data() {
+        let d = load_data(DATA);
+        assert_eq!(d.len(), 5);
+        assert_eq!(d[0][0], [404, -588, -901]);
+        assert_eq!(d[4].len(), 26);
     }
 
     #[test]
-    fn test_load_data2() {
-        let d = load_data2(DATA);
-        assert_eq!(d.len(), 5);
+    fn test_align() {
+        let d = load_data(DATA);
+        let (rotation, offset) = align(&d[0], &d[1]).unwrap();
+        assert_eq!(offset, [68, -1246, -43]);
         assert_eq!(
-            d[0][0],
-            Pt {
-                x: 404,
-                y: -588,
-                z: -901
-            }
+            add(rotation.apply([-336, 658, 858]), offset),
+            [404, -588, -901]
         );
-    }
-    #[test]
-    fn test_load_data() {
-        let d = load_data(DATA);
-        assert_eq!(d.len(), 5);
-        assert_eq!(d[0].0[0], 404);
+        assert_eq!(align(&d[0], &d[2]), None);
+        assert!(shared_pairs(&fingerprint(&d[0]), &fingerprint(&d[1])) >= 66);
     }
 
     #[test]
-    fn test_ok() {
-        assert!(true);
+    fn test_solve() {
+        let map = solve(&load_data(DATA)).unwrap();
+        assert_eq!(
+            map.scanners,
+            vec![
+                [0, 0, 0],
+                [68, -1246, -43],
+                [1105, -1205, 1229],
+                [-92, -2380, -20],
+                [-20, -1133, 1061]
+            ]
+        );
+        assert_eq!(map.beacons.len(), 79);
+        assert!(map.beacons.contains(&[-892, 524, 684]));
+        assert!(map.beacons.contains(&[1889, -1729, 1762]));
+        assert_eq!(map.max_distance(), 3621);
+
+        let mut lonely = load_data(DATA);
+        lonely.push(vec![[0, 0, 0]]);
+        assert_eq!(solve(&lonely), None);
     }
 
     const DATA: &str = "--- scanner 0 ---
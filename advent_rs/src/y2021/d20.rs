@@ -1,3 +1,5 @@
+use crate::read_file;
+
 type Image = Vec<Vec<u8>>;
 
 pub fn load_data(text: &str) -> (Vec<u8>, Image) {
@@ -96,6 +98,25 @@ pub fn print_image(img: &Image) {
     }
 }
 
+// the input's algorithm lights an all-dark 3x3 and darkens an all-lit one,
+// so the infinite background flips every step
+fn enhance_times(times: u8) -> usize {
+    let text = read_file("data/2021/input20.txt");
+    let (algo, mut image) = load_data(text.as_str());
+    for i in 0..times {
+        image = enhance_image(&image, &algo, i % 2);
+    }
+    count_white(&image)
+}
+
+pub(crate) fn quiz1() -> usize {
+    enhance_times(2)
+}
+
+pub(crate) fn quiz2() -> usize {
+    enhance_times(50)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -184,25 +205,11 @@ mod tests {
 
     #[test]
     fn run_quiz1() {
-        let text = read_file("data/2021/input20.txt");
-        let (algo, image) = load_data(text.as_str());
-
-        // algorithm 데이터가 모두 0이면 1, 모두 1이면 0이라 반복하도록 하드코딩, quiz2도 동일
-
-        let image = enhance_image(&image, &algo, 0);
-        let image = enhance_image(&image, &algo, 1);
-        assert_eq!(count_white(&image), 5306);
+        assert_eq!(quiz1(), 5306);
     }
 
     #[test]
     fn run_quiz2() {
-        let text = read_file("data/2021/input20.txt");
-        let (algo, mut image) = load_data(text.as_str());
-
-        for i in 0..50 {
-            image = enhance_image(&image, &algo, i % 2);
-        }
-
-        assert_eq!(count_white(&image), 17497);
+        assert_eq!(quiz2(), 17497);
     }
 }
@@ -1,56 +1,137 @@
-fn next_pos(pos: usize, rolls: [usize; 3]) -> usize {
-    (pos + rolls[0] + rolls[1] + rolls[2] - 1) % 10 + 1
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub board: usize,
+    pub target: usize,
+    pub faces: usize,
+    // rolls per turn
+    pub rolls: usize,
 }
 
-fn play(mut p1_pos: usize, mut p2_pos: usize, target_score: usize) -> usize {
-    let mut p1_score = 0;
-    let mut p2_score = 0;
-    let mut roll = 0;
-    let mut count = 0;
+pub const DETERMINISTIC: Rules = Rules {
+    board: 10,
+    target: 1000,
+    faces: 100,
+    rolls: 3,
+};
 
-    loop {
-        roll += 1;
-        let r1 = (roll - 1) % 100 + 1;
-        roll += 1;
-        let r2 = (roll - 1) % 100 + 1;
-        roll += 1;
-        let r3 = (roll - 1) % 100 + 1;
-        count += 3;
-        p1_pos = next_pos(p1_pos, [r1, r2, r3]);
-        p1_score += p1_pos;
-        if p1_score >= target_score {
-            return count * p2_score;
-        }
+pub const DIRAC: Rules = Rules {
+    board: 10,
+    target: 21,
+    faces: 3,
+    rolls: 3,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GameState {
+    pub pos: [usize; 2],
+    pub score: [usize; 2],
+    // index of the player about to move
+    pub turn: usize,
+}
 
-        roll += 1;
-        let r1 = (roll - 1) % 100 + 1;
-        roll += 1;
-        let r2 = (roll - 1) % 100 + 1;
-        roll += 1;
-        let r3 = (roll - 1) % 100 + 1;
-        count += 3;
-        p2_pos = next_pos(p2_pos, [r1, r2, r3]);
-        p2_score += p2_pos;
-        if p2_score >= target_score {
-            return count * p1_score;
+impl GameState {
+    pub fn new(p1_pos: usize, p2_pos: usize) -> Self {
+        GameState {
+            pos: [p1_pos, p2_pos],
+            score: [0, 0],
+            turn: 0,
         }
     }
+
+    pub fn advance(&self, roll_sum: usize, rules: &Rules) -> GameState {
+        let mut next = *self;
+        let p = self.turn;
+        next.pos[p] = next_pos(self.pos[p], roll_sum, rules.board);
+        next.score[p] += next.pos[p];
+        next.turn = 1 - p;
+        next
+    }
+
+    pub fn winner(&self, rules: &Rules) -> Option<usize> {
+        self.score.iter().position(|s| *s >= rules.target)
+    }
 }
 
-pub fn universe() -> Vec<[usize; 3]> {
-    let mut univ = vec![];
-    for i in 0..3 {
-        for j in 0..3 {
-            for k in 0..3 {
-                univ.push([i as usize + 1, j as usize + 1, k as usize + 1]);
+fn next_pos(pos: usize, roll_sum: usize, board: usize) -> usize {
+    (pos + roll_sum - 1) % board + 1
+}
+
+// (sum, number of universes) for every outcome of one turn
+pub fn roll_sums(rules: &Rules) -> Vec<(usize, u128)> {
+    let mut counts = vec![1u128];
+    for _ in 0..rules.rolls {
+        let mut next = vec![0; counts.len() + rules.faces];
+        for (sum, count) in counts.iter().enumerate() {
+            for face in 1..=rules.faces {
+                next[sum + face] += count;
             }
         }
+        counts = next;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .filter(|(_, c)| *c > 0)
+        .collect()
+}
+
+// loser's score times the number of rolls
+fn play(p1_pos: usize, p2_pos: usize, rules: &Rules) -> usize {
+    let mut state = GameState::new(p1_pos, p2_pos);
+    let mut die = (0..rules.faces).map(|r| r + 1).cycle();
+    let mut count = 0;
+
+    while state.winner(rules).is_none() {
+        let roll_sum = die.by_ref().take(rules.rolls).sum();
+        count += rules.rolls;
+        state = state.advance(roll_sum, rules);
+    }
+    count * state.score.iter().min().unwrap()
+}
+
+// universes in which each player wins
+pub fn count_wins(state: GameState, rules: &Rules) -> [u128; 2] {
+    let sums = roll_sums(rules);
+    let mut memo = HashMap::new();
+    count_wins_memo(state, rules, &sums, &mut memo)
+}
+
+fn count_wins_memo(
+    state: GameState,
+    rules: &Rules,
+    sums: &[(usize, u128)],
+    memo: &mut HashMap<GameState, [u128; 2]>,
+) -> [u128; 2] {
+    if let Some(winner) = state.winner(rules) {
+        let mut wins = [0, 0];
+        wins[winner] = 1;
+        return wins;
+    }
+    if let Some(wins) = memo.get(&state) {
+        return *wins;
     }
-    univ
+
+    let mut wins = [0, 0];
+    for (sum, universes) in sums {
+        let sub = count_wins_memo(state.advance(*sum, rules), rules, sums, memo);
+        wins[0] += sub[0] * universes;
+        wins[1] += sub[1] * universes;
+    }
+    memo.insert(state, wins);
+    wins
 }
 
 pub fn quiz1() -> usize {
-    play(1, 2, 1000)
+    play(1, 2, &DETERMINISTIC)
+}
+
+pub fn quiz2() -> u128 {
+    *count_wins(GameState::new(1, 2), &DIRAC)
+        .iter()
+        .max()
+        .unwrap()
 }
 
 #[cfg(test)]
@@ -59,14 +140,14 @@ mod tests {
 
     #[test]
     fn test_next_pos() {
-        assert_eq!(next_pos(4, [1, 2, 3]), 10);
-        assert_eq!(next_pos(8, [4, 5, 6]), 3);
-        assert_eq!(next_pos(10, [7, 8, 9]), 4);
+        assert_eq!(next_pos(4, 1 + 2 + 3, 10), 10);
+        assert_eq!(next_pos(8, 4 + 5 + 6, 10), 3);
+        assert_eq!(next_pos(10, 7 + 8 + 9, 10), 4);
     }
 
     #[test]
     fn test_play() {
-        assert_eq!(play(4, 8, 1000), 739785);
+        assert_eq!(play(4, 8, &DETERMINISTIC), 739785);
     }
 
     #[test]
@@ -75,10 +156,41 @@ mod tests {
     }
 
     #[test]
-    fn test_ok() {
-        assert!(true);
-        // assert_eq!(341960390180808_u64 + 444356092776315_u64, 0);
-        // assert_eq!(786316482957123_u64, 3_u64.pow(32));
-        assert_eq!(universe(), [[0, 0, 0]; 27]);
+    fn test_roll_sums() {
+        let sums = roll_sums(&DIRAC);
+        assert_eq!(
+            sums,
+            vec![(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)]
+        );
+        assert_eq!(sums.iter().map(|(_, c)| c).sum::<u128>(), 27);
+
+        let d6 = Rules {
+            faces: 6,
+            rolls: 2,
+            ..DIRAC
+        };
+        assert_eq!(roll_sums(&d6)[5], (7, 6));
+    }
+
+    #[test]
+    fn test_count_wins() {
+        assert_eq!(
+            count_wins(GameState::new(4, 8), &DIRAC),
+            [444356092776315, 341960390180808]
+        );
+
+        // a single face makes the quantum game deterministic
+        let one = Rules {
+            faces: 1,
+            target: 10,
+            ..DIRAC
+        };
+        assert_eq!(count_wins(GameState::new(4, 8), &one), [1, 0]);
+        assert_eq!(count_wins(GameState::new(9, 1), &one), [0, 1]);
+    }
+
+    #[test]
+    fn run_y2021_d21_quiz2() {
+        assert_eq!(quiz2(), 27674034218179);
     }
 }
@@ -1,3 +1,5 @@
+use crate::{read_file, split_text};
+
 pub fn total_fuel1(vs: &Vec<i32>, base: i32) -> i32 {
     vs.iter().map(|v| (base - *v).abs()).sum()
 }
@@ -27,10 +29,23 @@ pub fn find_min_base(vs: &Vec<i32>, total_fuel: fn(&Vec<i32>, i32) -> i32) -> (i
     (fuel, base.unwrap())
 }
 
+fn crab_fuel(total_fuel: fn(&Vec<i32>, i32) -> i32) -> i32 {
+    let text = read_file("data/2021/input7.txt");
+    let vs = split_text::<i32>(text.as_str().trim(), ',');
+    find_min_base(&vs, total_fuel).0
+}
+
+pub(crate) fn quiz1() -> i32 {
+    crab_fuel(total_fuel1)
+}
+
+pub(crate) fn quiz2() -> i32 {
+    crab_fuel(total_fuel2)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::*;
 
     #[test]
     fn test_find_min_base() {
@@ -40,16 +55,12 @@ mod tests {
 
     #[test]
     fn run_y2021_d7_q1() {
-        let text = read_file("data/2021/input7.txt");
-        let vs = split_text::<i32>(text.as_str().trim(), ',');
-        assert_eq!(find_min_base(&vs, total_fuel1), (352997, 317));
+        assert_eq!(quiz1(), 352997);
     }
 
     #[test]
     fn run_y2021_d7_q2() {
-        let text = read_file("data/2021/input7.txt");
-        let vs = split_text::<i32>(text.as_str().trim(), ',');
-        assert_eq!(find_min_base(&vs, total_fuel2), (101571302, 466));
+        assert_eq!(quiz2(), 101571302);
     }
 
     #[test]
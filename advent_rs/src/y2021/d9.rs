@@ -1,5 +1,9 @@
 use std::collections::HashMap;
 
+use crate::dashboard::save_visual;
+use crate::heatmap::Heatmap;
+use crate::trace;
+
 fn load_map(text: &str) -> (Vec<i8>, usize) {
     let cols = text.lines().next().unwrap().chars().count();
     let nums = text
@@ -182,11 +186,29 @@ fn find_basins(map: &Vec<i8>, rows: usize, cols: usize) -> HashMap<usize, Vec<us
     basins
 }
 
+// every cell colored by the size of its basin, ridges of 9 left blank
+fn basin_heatmap(map: &[i8], cols: usize, basins: &HashMap<usize, Vec<usize>>) -> Heatmap {
+    let mut values = vec![None; map.len()];
+    for ps in basins.values() {
+        for p in ps {
+            values[*p] = Some(ps.len() as f64);
+        }
+    }
+    Heatmap::new(values, cols)
+}
+
+fn draw_basins(map: &[i8], cols: usize, basins: &HashMap<usize, Vec<usize>>) {
+    let heatmap = basin_heatmap(map, cols, basins);
+    trace!("\n{}", heatmap.render_terminal());
+    save_visual(2021, 9, "basins.svg", heatmap.to_svg(4).as_bytes());
+}
+
 pub fn quiz2(text: &str) -> u32 {
     let (map, cols) = load_map(text);
     let rows = map.len() / cols;
 
     let basins = find_basins(&map, rows, cols);
+    draw_basins(&map, cols, &basins);
 
     let mut areas = basins
         .values()
@@ -248,6 +270,11 @@ mod tests {
         let basins = find_basins(&map, rows, cols);
 
         assert_eq!(basins.iter().count(), 4);
+
+        let heatmap = basin_heatmap(&map, cols, &basins);
+        assert_eq!(heatmap.size(), (5, 10));
+        assert_eq!(heatmap.bounds(), Some((3.0, 14.0)));
+        assert_eq!(heatmap.cell_color(2), [0, 0, 0]);
     }
 
     #[test]
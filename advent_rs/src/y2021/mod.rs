@@ -8,9 +8,10 @@ pub mod d15;
 pub mod d16;
 pub mod d17;
 pub mod d18;
-// pub mod d19;
+pub mod d19;
 pub mod d2;
 pub mod d20;
+pub mod d21;
 pub mod d3;
 pub mod d4;
 pub mod d5;
@@ -18,3 +19,56 @@ pub mod d6;
 pub mod d7;
 pub mod d8;
 pub mod d9;
+
+use crate::{read_file, split_text};
+use crate::runner::Solver;
+
+fn input(day: u8) -> String {
+    read_file(&format!("data/2021/input{day}.txt"))
+}
+
+pub(crate) fn solvers() -> Vec<Solver> {
+    vec![
+        Solver::new(2021, 1, 1, None, || d1::quiz1().into()),
+        Solver::new(2021, 1, 2, None, || d1::quiz2().into()),
+        Solver::new(2021, 2, 1, None, || d2::quiz1().into()),
+        Solver::new(2021, 2, 2, None, || d2::quiz2().into()),
+        Solver::new(2021, 3, 1, Some("4138664"), || d3::quiz1().into()),
+        Solver::new(2021, 3, 2, Some("4273224"), || d3::quiz2().into()),
+        Solver::new(2021, 4, 1, Some("32844"), || d4::quiz1(&input(4)).into()),
+        Solver::new(2021, 4, 2, Some("4920"), || d4::quiz2(&input(4)).into()),
+        Solver::new(2021, 5, 1, Some("5294"), || d5::quiz1(&input(5)).into()),
+        Solver::new(2021, 5, 2, Some("21698"), || d5::quiz2(&input(5)).into()),
+        Solver::new(2021, 6, 1, Some("380612"), || d6::quiz(&split_text(input(6).trim(), ','), 80).into()),
+        Solver::new(2021, 6, 2, Some("1710166656900"), || d6::quiz(&split_text(input(6).trim(), ','), 256).into()),
+        Solver::new(2021, 7, 1, Some("352997"), || d7::quiz1().into()),
+        Solver::new(2021, 7, 2, Some("101571302"), || d7::quiz2().into()),
+        Solver::new(2021, 8, 1, Some("264"), || d8::quiz1(input(8).trim()).into()),
+        Solver::new(2021, 8, 2, None, || d8::quiz2(input(8).trim()).into()),
+        Solver::new(2021, 9, 1, Some("537"), || d9::quiz1(input(9).trim()).into()),
+        Solver::new(2021, 9, 2, Some("1142757"), || d9::quiz2(input(9).trim()).into()),
+        Solver::new(2021, 10, 1, Some("390993"), || d10::quiz1(input(10).trim()).into()),
+        Solver::new(2021, 10, 2, Some("2391385187"), || d10::quiz2(input(10).trim()).into()),
+        Solver::new(2021, 11, 1, Some("1627"), || d11::quiz1(input(11).trim(), 10, 10).into()),
+        Solver::new(2021, 11, 2, Some("329"), || d11::quiz2(input(11).trim(), 10, 10).into()),
+        Solver::new(2021, 12, 1, Some("4167"), || d12::quiz1(input(12).trim()).into()),
+        Solver::new(2021, 12, 2, Some("98441"), || d12::quiz2(input(12).trim()).into()),
+        Solver::new(2021, 13, 1, Some("695"), || d13::quiz1(input(13).trim()).into()),
+        Solver::new(2021, 13, 2, None, || d13::quiz2(input(13).trim()).into()),
+        Solver::new(2021, 14, 1, Some("2345"), || d14::quiz1(input(14).trim(), 10).into()),
+        Solver::new(2021, 14, 2, Some("2432786807053"), || d14::quiz2(input(14).trim(), 40).into()),
+        Solver::new(2021, 15, 1, Some("714"), || d15::quiz1(input(15).trim()).into()),
+        Solver::new(2021, 15, 2, Some("2948"), || d15::quiz2(input(15).trim()).into()),
+        Solver::new(2021, 16, 1, Some("993"), || d16::quiz1(input(16).trim()).into()),
+        Solver::new(2021, 16, 2, None, || d16::quiz2(input(16).trim()).into()),
+        Solver::new(2021, 17, 1, Some("12561"), || d17::quiz1(-159).into()),
+        Solver::new(2021, 18, 1, Some("3892"), || d18::quiz1(&input(18)).into()),
+        Solver::new(2021, 18, 2, Some("4909"), || d18::quiz2(input(18).trim()).into()),
+        Solver::new(2021, 19, 1, None, || d19::quiz1().into()),
+        Solver::new(2021, 19, 2, None, || d19::quiz2().into()),
+        Solver::new(2021, 20, 1, Some("5306"), || d20::quiz1().into()),
+        Solver::new(2021, 20, 2, Some("17497"), || d20::quiz2().into()),
+        Solver::new(2021, 21, 1, Some("598416"), || d21::quiz1().into()),
+        Solver::new(2021, 21, 2, Some("27674034218179"), || d21::quiz2().into()),
+    ]
+}
@@ -1,12 +1,12 @@
 #![allow(dead_code)]
 use crate::read_file;
 
-fn quiz1() -> i32 {
+pub(crate) fn quiz1() -> i32 {
     let text = read_file("data/2022/input1.txt");
     most_calories(&text)
 }
 
-fn quiz2() -> i32 {
+pub(crate) fn quiz2() -> i32 {
     let text = read_file("data/2022/input1.txt");
     top_three_calories(&text)
 }
@@ -1,15 +1,31 @@
-use nom::{
-    branch::alt,
-    bytes::complete::{self, tag},
-    character::complete::newline,
-    multi::separated_list1,
-    IResult, Parser,
-};
-
+use crate::answer::Screen;
 use crate::read_file;
 
 const FILE_NAME: &str = "data/2022/input10.txt";
 
+struct OpInfo {
+    name: &'static str,
+    cycles: usize,
+    operands: usize,
+    // called with exactly `operands` values
+    build: fn(&[i32]) -> Instruction,
+}
+
+const OPCODES: [OpInfo; 2] = [
+    OpInfo {
+        name: "noop",
+        cycles: 1,
+        operands: 0,
+        build: |_| Instruction::Noop,
+    },
+    OpInfo {
+        name: "addx",
+        cycles: 2,
+        operands: 1,
+        build: |xs| Instruction::Addx(xs[0]),
+    },
+];
+
 #[derive(Debug, PartialEq)]
 enum Instruction {
     Noop,
@@ -17,141 +33,177 @@ enum Instruction {
 }
 
 impl Instruction {
-    fn cycle(&self) -> usize {
+    fn operands(&self) -> Vec<i32> {
         match self {
-            Self::Noop => 1,
-            Self::Addx(_) => 2,
+            Self::Noop => vec![],
+            Self::Addx(x) => vec![*x],
         }
     }
-}
 
-fn noop(input: &str) -> IResult<&str, Instruction> {
-    let (input, _) = tag("noop")(input)?;
-    Ok((input, Instruction::Noop))
+    fn info(&self) -> &'static OpInfo {
+        let operands = self.operands();
+        OPCODES
+            .iter()
+            .find(|op| op.operands == operands.len() && (op.build)(&operands) == *self)
+            .expect("every instruction has an opcode")
+    }
+
+    fn cycle(&self) -> usize {
+        self.info().cycles
+    }
+
+    fn decode(name: &str, operands: &[i32]) -> Option<Instruction> {
+        OPCODES
+            .iter()
+            .find(|op| op.name == name && op.operands == operands.len())
+            .map(|op| (op.build)(operands))
+    }
 }
 
-fn addx(input: &str) -> IResult<&str, Instruction> {
-    let (input, _) = tag("addx ")(input)?;
-    let (input, x) = nom::character::complete::i32(input)?;
-    Ok((input, Instruction::Addx(x)))
+fn assemble(source: &str) -> Result<Vec<Instruction>, String> {
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(no, line)| {
+            let mut words = line.split_whitespace();
+            let name = words.next().unwrap();
+            let info = OPCODES
+                .iter()
+                .find(|op| op.name == name)
+                .ok_or(format!("line {}: unknown opcode {name}", no + 1))?;
+            let operands = words
+                .map(|w| w.parse::<i32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("line {}: {e}", no + 1))?;
+            if operands.len() != info.operands {
+                return Err(format!(
+                    "line {}: {name} takes {} operand(s)",
+                    no + 1,
+                    info.operands
+                ));
+            }
+            Ok(Instruction::decode(name, &operands).unwrap())
+        })
+        .collect()
 }
 
-fn instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
-    let (input, ins) = separated_list1(newline, alt((noop, addx))).parse(input)?;
-    Ok((input, ins))
+fn disassemble(program: &[Instruction]) -> String {
+    program
+        .iter()
+        .map(|ins| {
+            std::iter::once(ins.info().name.to_string())
+                .chain(ins.operands().iter().map(|x| x.to_string()))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 fn load(input: &str) -> Vec<Instruction> {
-    if let Ok((_, ins)) = instructions(input) {
-        return ins;
-    }
-    panic!()
+    assemble(input).unwrap()
 }
 
-struct Register {
-    pc: usize,
-    x: i32,
+trait Observer {
+    // `x` is the register value *during* the cycle
+    fn on_cycle(&mut self, cycle: usize, x: i32);
 }
 
-impl Register {
-    fn tick(&mut self, pc: usize) {
-        self.pc += pc;
-    }
-
-    fn addx(&mut self, x: i32) {
-        self.x += x;
-    }
+struct Cpu {
+    cycle: usize,
+    x: i32,
 }
 
-fn proc1(input: &str) -> i32 {
-    let instructions = load(input);
-    let target_cycles = vec![20, 60, 100, 140, 180, 220];
-    let mut strength = vec![0; 6];
-    let mut ti = 0;
-
-    let mut reg = Register { pc: 0, x: 1 };
-
-    for ins in instructions {
-        let cycle = ins.cycle();
-        if target_cycles[ti] <= reg.pc + cycle {
-            strength[ti] = target_cycles[ti] as i32 * reg.x;
-            ti += 1;
+impl Cpu {
+    fn new() -> Self {
+        Cpu { cycle: 0, x: 1 }
+    }
 
-            if ti == 6 {
-                break;
-            }
+    fn execute(&mut self, ins: &Instruction, observers: &mut [&mut dyn Observer]) {
+        for _ in 0..ins.cycle() {
+            self.cycle += 1;
+            observers
+                .iter_mut()
+                .for_each(|o| o.on_cycle(self.cycle, self.x));
         }
-
-        reg.tick(cycle);
-
         match ins {
             Instruction::Noop => {}
-            Instruction::Addx(x) => {
-                reg.addx(x);
-            }
+            Instruction::Addx(x) => self.x += x,
         }
     }
 
-    strength.iter().sum()
+    fn run(&mut self, program: &[Instruction], observers: &mut [&mut dyn Observer]) {
+        program.iter().for_each(|ins| self.execute(ins, observers));
+    }
 }
 
-fn quiz1() -> i32 {
-    let input = read_file(FILE_NAME);
-    proc1(&input)
+struct SignalSampler {
+    cycles: Vec<usize>,
+    strength: i32,
 }
 
-fn in_sprite(cycle: usize, sprite: i32) -> bool {
-    let c = cycle as i32;
-    sprite - 1 <= c && c <= sprite + 1
+impl SignalSampler {
+    fn new(cycles: Vec<usize>) -> Self {
+        SignalSampler {
+            cycles,
+            strength: 0,
+        }
+    }
 }
 
-fn proc2(input: &str) -> String {
-    const ROW: usize = 40;
-    const COL: usize = 6;
-
-    let instructions = load(input);
+impl Observer for SignalSampler {
+    fn on_cycle(&mut self, cycle: usize, x: i32) {
+        if self.cycles.contains(&cycle) {
+            self.strength += cycle as i32 * x;
+        }
+    }
+}
 
-    let mut crt = vec!['.'; 240];
-    let mut sprite = 1;
-    let mut ti = 0;
-    let mut acc_ins_cycle = instructions[ti].cycle();
+struct Crt {
+    screen: Screen,
+}
 
-    for cycle in 0..crt.len() {
-        if cycle >= acc_ins_cycle {
-            let ins = &instructions[ti];
-            if let Instruction::Addx(x) = ins {
-                sprite += x;
-            }
+impl Crt {
+    fn new(width: usize, height: usize) -> Self {
+        Crt {
+            screen: Screen::new(width, height),
         }
+    }
+}
 
-        if in_sprite(cycle % ROW, sprite) {
-            crt[cycle] = '#';
+impl Observer for Crt {
+    fn on_cycle(&mut self, cycle: usize, x: i32) {
+        let (width, height) = self.screen.size();
+        let pos = cycle - 1;
+        if pos >= width * height {
+            return;
         }
-
-        if cycle >= acc_ins_cycle {
-            ti += 1;
-            acc_ins_cycle += instructions[ti].cycle();
+        let col = (pos % width) as i32;
+        if (x - 1..=x + 1).contains(&col) {
+            self.screen.set(pos % width, pos / width, true);
         }
     }
-
-    to_screen(crt, ROW, COL)
 }
 
-fn to_screen(mut crt: Vec<char>, row: usize, col: usize) -> String {
-    let mut ret = String::new();
+fn proc1(input: &str) -> i32 {
+    let mut sampler = SignalSampler::new(vec![20, 60, 100, 140, 180, 220]);
+    Cpu::new().run(&load(input), &mut [&mut sampler]);
+    sampler.strength
+}
 
-    for _ in 0..col {
-        let (line, sub_crt) = crt.split_at(row);
-        let head = line.iter().collect::<String>();
-        ret.push_str(&head);
-        ret.push('\n');
-        crt = sub_crt.to_vec();
-    }
+pub(crate) fn quiz1() -> i32 {
+    let input = read_file(FILE_NAME);
+    proc1(&input)
+}
 
-    ret.trim().to_owned()
+fn proc2(input: &str) -> Screen {
+    let mut crt = Crt::new(40, 6);
+    Cpu::new().run(&load(input), &mut [&mut crt]);
+    crt.screen
 }
 
-fn quiz2() -> String {
+pub(crate) fn quiz2() -> Screen {
     let input = read_file(FILE_NAME);
     proc2(&input)
 }
@@ -170,6 +222,59 @@ addx -5";
         assert_eq!(load(INPUT0), vec![Noop, Addx(3), Addx(-5)]);
     }
 
+    #[test]
+    fn test_assembler() {
+        assert_eq!(disassemble(&assemble(INPUT0).unwrap()), INPUT0);
+        assert_eq!(
+            assemble("noop\nmulx 3"),
+            Err("line 2: unknown opcode mulx".to_string())
+        );
+        assert_eq!(
+            assemble("addx"),
+            Err("line 1: addx takes 1 operand(s)".to_string())
+        );
+        assert!(assemble("addx 1.5").unwrap_err().starts_with("line 1: "));
+    }
+
+    #[test]
+    fn test_opcodes() {
+        for op in OPCODES.iter() {
+            let operands = vec![7; op.operands];
+            let ins = Instruction::decode(op.name, &operands).unwrap();
+            assert_eq!(ins.info().name, op.name);
+            assert_eq!(ins.cycle(), op.cycles);
+            assert_eq!(ins.operands(), operands);
+            assert_eq!(Instruction::decode(op.name, &[7; 3]), None);
+        }
+        assert_eq!(Instruction::decode("mulx", &[]), None);
+    }
+
+    struct Trace(Vec<(usize, i32)>);
+
+    impl Observer for Trace {
+        fn on_cycle(&mut self, cycle: usize, x: i32) {
+            self.0.push((cycle, x));
+        }
+    }
+
+    #[test]
+    fn test_cpu_trace() {
+        let mut trace = Trace(vec![]);
+        let mut cpu = Cpu::new();
+        cpu.run(&load(INPUT0), &mut [&mut trace]);
+        assert_eq!(trace.0, vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+        assert_eq!((cpu.cycle, cpu.x), (5, -1));
+    }
+
+    #[test]
+    fn test_observers_share_run() {
+        let mut sampler = SignalSampler::new(vec![20, 60, 100, 140, 180, 220]);
+        let mut crt = Crt::new(40, 6);
+        Cpu::new().run(&load(INPUT), &mut [&mut sampler, &mut crt]);
+        assert_eq!(sampler.strength, 13140);
+        assert_eq!(crt.screen.to_string(), OUTPUT_CRT1);
+    }
+
     const OUTPUT_CRT1: &str = "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
@@ -179,14 +284,15 @@ addx -5";
 
     #[test]
     fn test_proc2() {
-        assert_eq!(proc2(INPUT), OUTPUT_CRT1);
+        assert_eq!(proc2(INPUT), OUTPUT_CRT1.parse().unwrap());
+        assert_eq!(proc2(INPUT).size(), (40, 6));
     }
 
     #[test]
     fn test_quiz2() {
         let crt = quiz2();
         assert_eq!(
-            crt,
+            crt.to_string(),
             "###...##...##..####.#..#.#....#..#.####.
 #..#.#..#.#..#.#....#.#..#....#..#.#....
 ###..#..#.#....###..##...#....####.###..
@@ -1,71 +1,156 @@
-use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 use nom::branch::alt;
-use nom::character::complete::{self, newline, space1};
-use nom::multi::{count, separated_list0, separated_list1};
-use nom::sequence::separated_pair;
+use nom::character::complete::{self, char, newline, space0, space1};
+use nom::multi::{count, fold_many0, separated_list0, separated_list1};
+use nom::sequence::{delimited, pair, separated_pair};
 use nom::Parser;
 use nom::{bytes::complete::tag, sequence::preceded, IResult};
+use num::{BigUint, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, Integer};
 
 use crate::read_file;
 
 const FILE_NAME: &str = "data/2022/input11.txt";
 
-#[derive(Debug, PartialEq)]
-enum Op {
-    Plus,
-    Multiply,
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Old,
+    Num(u64),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Arith {
+    Overflow,
+    Underflow,
+    DivisionByZero,
 }
 
-impl Op {
-    fn eval(&self, v: u32, rh: u32) -> u64 {
+impl Expr {
+    fn eval<W: Worry>(&self, old: &W) -> Result<W, Arith> {
+        match self {
+            Expr::Old => Ok(old.clone()),
+            Expr::Num(n) => W::from_u64(*n).ok_or(Arith::Overflow),
+            Expr::Add(a, b) => a
+                .eval(old)?
+                .checked_add(&b.eval(old)?)
+                .ok_or(Arith::Overflow),
+            Expr::Sub(a, b) => a
+                .eval(old)?
+                .checked_sub(&b.eval(old)?)
+                .ok_or(Arith::Underflow),
+            Expr::Mul(a, b) => a
+                .eval(old)?
+                .checked_mul(&b.eval(old)?)
+                .ok_or(Arith::Overflow),
+            Expr::Div(a, b) => a
+                .eval(old)?
+                .checked_div(&b.eval(old)?)
+                .ok_or(Arith::DivisionByZero),
+        }
+    }
+
+    // whether the result modulo m only depends on `old` modulo m, which holds
+    // for + and * but not for - or /
+    fn is_modular(&self) -> bool {
         match self {
-            Op::Plus => v as u64 + rh as u64,
-            Op::Multiply => v as u64 * rh as u64,
+            Expr::Old | Expr::Num(_) => true,
+            Expr::Add(a, b) | Expr::Mul(a, b) => a.is_modular() && b.is_modular(),
+            Expr::Sub(..) | Expr::Div(..) => false,
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
-enum Rh {
-    Num(u32),
-    Old,
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Old => write!(f, "old"),
+            Expr::Num(n) => write!(f, "{n}"),
+            Expr::Add(a, b) => write!(f, "({a} + {b})"),
+            Expr::Sub(a, b) => write!(f, "({a} - {b})"),
+            Expr::Mul(a, b) => write!(f, "({a} * {b})"),
+            Expr::Div(a, b) => write!(f, "({a} / {b})"),
+        }
+    }
+}
+
+fn factor(input: &str) -> IResult<&str, Expr> {
+    delimited(
+        space0,
+        alt((
+            complete::u64.map(Expr::Num),
+            tag("old").map(|_| Expr::Old),
+            delimited(char('('), expr, char(')')),
+        )),
+        space0,
+    )
+    .parse(input)
+}
+
+fn term(input: &str) -> IResult<&str, Expr> {
+    let (input, first) = factor(input)?;
+    fold_many0(
+        pair(alt((char('*'), char('/'))), factor),
+        move || first.clone(),
+        |acc, (op, rh)| match op {
+            '*' => Expr::Mul(Box::new(acc), Box::new(rh)),
+            _ => Expr::Div(Box::new(acc), Box::new(rh)),
+        },
+    )
+    .parse(input)
+}
+
+fn expr(input: &str) -> IResult<&str, Expr> {
+    let (input, first) = term(input)?;
+    fold_many0(
+        pair(alt((char('+'), char('-'))), term),
+        move || first.clone(),
+        |acc, (op, rh)| match op {
+            '+' => Expr::Add(Box::new(acc), Box::new(rh)),
+            _ => Expr::Sub(Box::new(acc), Box::new(rh)),
+        },
+    )
+    .parse(input)
 }
 
+trait Worry:
+    Clone + Integer + CheckedAdd + CheckedSub + CheckedMul + CheckedDiv + FromPrimitive
+{
+}
+
+impl Worry for u64 {}
+impl Worry for u128 {}
+impl Worry for BigUint {}
+
 fn monkey(input: &str) -> IResult<&str, u32> {
     let (input, idx) = preceded(tag("Monkey "), complete::u32).parse(input)?;
     let (input, _) = tag(":")(input)?;
     Ok((input, idx))
 }
 
-fn items(input: &str) -> IResult<&str, Vec<u32>> {
+fn items(input: &str) -> IResult<&str, Vec<u64>> {
     let (input, _) = space1(input)?;
     let (input, _) = tag("Starting items: ")(input)?;
-    let (input, items) = separated_list0(tag(", "), complete::u32).parse(input)?;
+    let (input, items) = separated_list0(tag(", "), complete::u64).parse(input)?;
     Ok((input, items))
 }
 
-fn operation(input: &str) -> IResult<&str, (Op, Rh)> {
+fn operation(input: &str) -> IResult<&str, Expr> {
     let (input, _) = space1(input)?;
-    let (input, _) = tag("Operation: new = old ")(input)?;
-    let (input, (op, n)) = separated_pair(
-        alt((tag("+").map(|_| Op::Plus), tag("*").map(|_| Op::Multiply))),
-        space1,
-        alt((
-            complete::u32.map(|n| Rh::Num(n)),
-            tag("old").map(|_| Rh::Old),
-        )),
-    ).parse(input)?;
-    Ok((input, (op, n)))
+    let (input, _) = tag("Operation: new =")(input)?;
+    expr(input)
 }
 
-fn divisible(input: &str) -> IResult<&str, u32> {
+fn divisible(input: &str) -> IResult<&str, u64> {
     let (input, _) = space1(input)?;
-    let (input, n) = preceded(tag("Test: divisible by "), complete::u32).parse(input)?;
+    let (input, n) = preceded(tag("Test: divisible by "), complete::u64).parse(input)?;
     Ok((input, n))
 }
 
-fn throw(input: &str) -> IResult<&str, u32> {
+fn throw(input: &str) -> IResult<&str, usize> {
     let (input, _) = space1(input)?;
     let (input, n) = preceded(
         alt((
@@ -73,35 +158,34 @@ fn throw(input: &str) -> IResult<&str, u32> {
             tag("If false: throw to monkey "),
         )),
         complete::u32,
-    ).parse(input)?;
-    Ok((input, n))
+    )
+    .parse(input)?;
+    Ok((input, n as usize))
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 struct Monkey {
     idx: u32,
-    items: Vec<u32>,
-    op: Op,
-    op_val: Rh,
-    divisor: u32,
-    case_t: u32,
-    case_f: u32,
+    items: Vec<u64>,
+    op: Expr,
+    divisor: u64,
+    case_t: usize,
+    case_f: usize,
 }
+
 impl Monkey {
     fn new(
         idx: u32,
-        items: Vec<u32>,
-        op: Op,
-        op_val: Rh,
-        divisor: u32,
-        case_t: u32,
-        case_f: u32,
+        items: Vec<u64>,
+        op: Expr,
+        divisor: u64,
+        case_t: usize,
+        case_f: usize,
     ) -> Self {
         Self {
             idx,
             items,
             op,
-            op_val,
             divisor,
             case_t,
             case_f,
@@ -114,119 +198,143 @@ fn block(input: &str) -> IResult<&str, Monkey> {
     let (input, _) = newline(input)?;
     let (input, items) = items(input)?;
     let (input, _) = newline(input)?;
-    let (input, (op, op_val)) = operation(input)?;
+    let (input, op) = operation(input)?;
     let (input, _) = newline(input)?;
     let (input, divisor) = divisible(input)?;
     let (input, _) = newline(input)?;
     let (input, case_t) = throw(input)?;
     let (input, _) = newline(input)?;
     let (input, case_f) = throw(input)?;
-    Ok((
-        input,
-        Monkey::new(idx, items, op, op_val, divisor, case_t, case_f),
-    ))
+    Ok((input, Monkey::new(idx, items, op, divisor, case_t, case_f)))
 }
 
 fn load(input: &str) -> Vec<Monkey> {
-    let (_, monkeys) = separated_list1(count(newline, 2), block).parse(input).unwrap();
+    let (_, monkeys) = separated_list1(count(newline, 2), block)
+        .parse(input)
+        .unwrap();
     monkeys
 }
 
-fn proc1(input: &str) -> usize {
-    let mut monkeys = load(input);
-    let len = monkeys.len();
-    let mut inspect = vec![0; len];
-
-    for round in 0..20 {
-        for i in 0..monkeys.len() {
-            let monkey = &mut monkeys[i];
-            let mut items = &mut monkey.items;
-            let mut p: HashMap<u32, Vec<u32>> = HashMap::new();
-            items.reverse();
-            while let Some(w) = items.pop() {
-                inspect[i] += 1;
-
-                let op_val = match monkey.op_val {
-                    Rh::Num(n) => n,
-                    Rh::Old => w,
-                };
-
-                let worry = (monkey.op.eval(w, op_val) / 3) as u32;
-                let i = if worry % monkey.divisor == 0 {
-                    monkey.case_t
-                } else {
-                    monkey.case_f
-                };
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Relief {
+    None,
+    Divide(u64),
+    // keep worry modulo the product of every test divisor
+    ModuloDivisors,
+}
 
-                let vs = p.entry(i).or_default();
-                vs.push(worry);
-            }
+#[derive(Debug, PartialEq)]
+enum SimError {
+    Overflow { round: usize, monkey: usize },
+    Underflow { round: usize, monkey: usize },
+    DivisionByZero { round: usize, monkey: usize },
+    // `Relief::ModuloDivisors` would change the result of the monkey's operation
+    NotModular { monkey: usize },
+    InvalidTarget { monkey: usize, target: usize },
+    ZeroDivisor { monkey: usize },
+    ZeroRelief,
+}
 
-            for (k, mut v) in p {
-                monkeys[k as usize].items.append(&mut v);
+// inspections[round][monkey] is how many items the monkey inspected in that round
+fn simulate<W: Worry>(
+    monkeys: &[Monkey],
+    relief: Relief,
+    rounds: usize,
+) -> Result<Vec<Vec<usize>>, SimError> {
+    if relief == Relief::Divide(0) {
+        return Err(SimError::ZeroRelief);
+    }
+    for (i, m) in monkeys.iter().enumerate() {
+        if m.divisor == 0 {
+            return Err(SimError::ZeroDivisor { monkey: i });
+        }
+        if relief == Relief::ModuloDivisors && !m.op.is_modular() {
+            return Err(SimError::NotModular { monkey: i });
+        }
+        for target in [m.case_t, m.case_f] {
+            if target >= monkeys.len() || target == i {
+                return Err(SimError::InvalidTarget { monkey: i, target });
             }
         }
     }
 
-    inspect.sort();
-    println!("{inspect:?}");
-    inspect[len - 1] * inspect[len - 2]
-}
-
-fn quiz1() -> usize {
-    let input = read_file(FILE_NAME);
-    proc1(&input)
-}
-
-fn proc2(input: &str) -> usize {
-    let mut monkeys = load(input);
-    let len = monkeys.len();
-    let mut inspect = vec![0; len];
-
-    // 흠................
-    let dv: u64 = monkeys.iter().map(|monkey| monkey.divisor).product::<u32>() as u64;
-
-    for round in 0..10000 {
-        for i in 0..monkeys.len() {
-            let monkey = &mut monkeys[i];
-            let mut items = &mut monkey.items;
-            let mut p: HashMap<u32, Vec<u32>> = HashMap::new();
-            items.reverse();
-            while let Some(w) = items.pop() {
-                inspect[i] += 1;
-
-                let op_val = match monkey.op_val {
-                    Rh::Num(n) => n,
-                    Rh::Old => w,
+    let to_worry = |n: u64| W::from_u64(n).unwrap();
+    let modulus = monkeys
+        .iter()
+        .map(|m| to_worry(m.divisor))
+        .fold(W::one(), |acc, d| acc.lcm(&d));
+    let divisors = monkeys
+        .iter()
+        .map(|m| to_worry(m.divisor))
+        .collect::<Vec<_>>();
+
+    let mut items = monkeys
+        .iter()
+        .map(|m| m.items.iter().map(|n| to_worry(*n)).collect::<Vec<W>>())
+        .collect::<Vec<_>>();
+
+    let mut inspections = vec![];
+    for round in 0..rounds {
+        let mut counts = vec![0; monkeys.len()];
+        for (i, monkey) in monkeys.iter().enumerate() {
+            let held = std::mem::take(&mut items[i]);
+            counts[i] += held.len();
+            for old in held {
+                let worry = monkey.op.eval(&old).map_err(|e| match e {
+                    Arith::Overflow => SimError::Overflow { round, monkey: i },
+                    Arith::Underflow => SimError::Underflow { round, monkey: i },
+                    Arith::DivisionByZero => SimError::DivisionByZero { round, monkey: i },
+                })?;
+                let worry = match relief {
+                    Relief::None => worry,
+                    Relief::Divide(d) => worry / to_worry(d),
+                    Relief::ModuloDivisors => worry % modulus.clone(),
                 };
-
-                let worry = (monkey.op.eval(w, op_val) % dv) as u32;
-                let i = if worry % monkey.divisor == 0 {
+                let target = if (worry.clone() % divisors[i].clone()).is_zero() {
                     monkey.case_t
                 } else {
                     monkey.case_f
                 };
-
-                let vs = p.entry(i).or_default();
-                vs.push(worry);
-            }
-
-            for (k, mut v) in p {
-                monkeys[k as usize].items.append(&mut v);
+                items[target].push(worry);
             }
         }
+        inspections.push(counts);
+    }
+    Ok(inspections)
+}
 
-        let r = round + 1;
-        if r >= 1000 && r % 1000 == 0 {
-            println!("{r}: {inspect:?}");
-        }
+fn monkey_business(inspections: &[Vec<usize>]) -> usize {
+    let monkeys = inspections.first().map_or(0, Vec::len);
+    let mut totals = inspections
+        .iter()
+        .fold(vec![0; monkeys], |mut acc, counts| {
+            acc.iter_mut().zip(counts).for_each(|(a, c)| *a += c);
+            acc
+        });
+    // no rounds means nothing was inspected
+    if totals.len() < 2 {
+        return 0;
     }
+    totals.sort();
+    totals.iter().rev().take(2).product()
+}
+
+fn proc1(input: &str) -> usize {
+    let monkeys = load(input);
+    monkey_business(&simulate::<u64>(&monkeys, Relief::Divide(3), 20).unwrap())
+}
+
+pub(crate) fn quiz1() -> usize {
+    let input = read_file(FILE_NAME);
+    proc1(&input)
+}
 
-    inspect.sort();
-    inspect[len - 1] * inspect[len - 2]
+fn proc2(input: &str) -> usize {
+    let monkeys = load(input);
+    monkey_business(&simulate::<u64>(&monkeys, Relief::ModuloDivisors, 10000).unwrap())
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     let input = read_file(FILE_NAME);
     proc2(&input)
 }
@@ -263,15 +371,144 @@ Monkey 3:
     If true: throw to monkey 0
     If false: throw to monkey 1";
 
+    fn add(a: Expr, b: Expr) -> Expr {
+        Expr::Add(Box::new(a), Box::new(b))
+    }
+
+    fn mul(a: Expr, b: Expr) -> Expr {
+        Expr::Mul(Box::new(a), Box::new(b))
+    }
+
+    #[test]
+    fn test_expr() {
+        let parse = |s| expr(s).unwrap().1;
+        assert_eq!(parse(" old * 19"), mul(Expr::Old, Expr::Num(19)));
+        assert_eq!(
+            parse("2 + old * old - 1").to_string(),
+            "((2 + (old * old)) - 1)"
+        );
+        assert_eq!(
+            parse("(old + 1) * (old - 1) / 2").to_string(),
+            "(((old + 1) * (old - 1)) / 2)"
+        );
+        assert_eq!(parse("(old + 1) * (old - 1) / 2").eval(&5u64), Ok(12));
+        assert_eq!(parse("old - 6").eval(&5u64), Err(Arith::Underflow));
+        assert_eq!(
+            parse("old / (old - 5)").eval(&5u64),
+            Err(Arith::DivisionByZero)
+        );
+        assert_eq!(parse("old * old").eval(&u64::MAX), Err(Arith::Overflow));
+        assert_eq!(
+            parse("old * old").eval(&BigUint::from(u64::MAX)),
+            Ok(BigUint::from(u64::MAX) * BigUint::from(u64::MAX))
+        );
+        assert!(parse("(old + 1) * old").is_modular());
+        assert!(!parse("old * 2 - 1").is_modular());
+        assert!(!parse("old / 2").is_modular());
+    }
+
+    #[test]
+    fn test_inspections() {
+        let monkeys = load(INPUT);
+        let rounds = simulate::<u64>(&monkeys, Relief::Divide(3), 20).unwrap();
+        assert_eq!(rounds.len(), 20);
+        assert_eq!(rounds[0], vec![2, 4, 3, 5]);
+
+        let rounds = simulate::<u128>(&monkeys, Relief::ModuloDivisors, 20).unwrap();
+        let totals = (0..4)
+            .map(|m| rounds.iter().map(|r| r[m]).sum::<usize>())
+            .collect::<Vec<_>>();
+        assert_eq!(totals, vec![99, 97, 8, 103]);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let monkeys = load(INPUT);
+        assert_eq!(
+            simulate::<u64>(&monkeys, Relief::None, 20),
+            Err(SimError::Overflow {
+                round: 12,
+                monkey: 0
+            })
+        );
+        let rounds = simulate::<BigUint>(&monkeys, Relief::None, 20).unwrap();
+        assert_eq!(rounds.len(), 20);
+        assert_eq!(rounds[19].iter().sum::<usize>(), 15);
+    }
+
+    #[test]
+    fn test_invalid_setup() {
+        let monkeys = load(INPUT);
+        assert_eq!(
+            simulate::<u64>(&monkeys, Relief::Divide(0), 20),
+            Err(SimError::ZeroRelief)
+        );
+
+        let mut broken = monkeys.clone();
+        broken[2].divisor = 0;
+        assert_eq!(
+            simulate::<u64>(&broken, Relief::Divide(3), 20),
+            Err(SimError::ZeroDivisor { monkey: 2 })
+        );
+
+        let mut broken = monkeys.clone();
+        broken[1].case_f = 4;
+        assert_eq!(
+            simulate::<u64>(&broken, Relief::Divide(3), 20),
+            Err(SimError::InvalidTarget {
+                monkey: 1,
+                target: 4
+            })
+        );
+
+        let mut broken = monkeys.clone();
+        broken[3].op = expr("old / 2").unwrap().1;
+        assert_eq!(
+            simulate::<u64>(&broken, Relief::ModuloDivisors, 20),
+            Err(SimError::NotModular { monkey: 3 })
+        );
+        assert!(simulate::<u64>(&broken, Relief::Divide(3), 20).is_ok());
+
+        let mut broken = monkeys.clone();
+        broken[0].op = expr("old - 80").unwrap().1;
+        assert_eq!(
+            simulate::<u64>(&broken, Relief::None, 20),
+            Err(SimError::Underflow {
+                round: 0,
+                monkey: 0
+            })
+        );
+
+        let mut broken = monkeys.clone();
+        broken[0].op = expr("old / (old - 79)").unwrap().1;
+        assert_eq!(
+            simulate::<u64>(&broken, Relief::None, 20),
+            Err(SimError::DivisionByZero {
+                round: 0,
+                monkey: 0
+            })
+        );
+
+        let rounds = simulate::<u64>(&monkeys, Relief::Divide(3), 0).unwrap();
+        assert_eq!(monkey_business(&rounds), 0);
+    }
+
     #[test]
     fn test_load() {
         assert_eq!(
             load(INPUT),
             vec![
-                Monkey::new(0, vec![79, 98], Op::Multiply, Rh::Num(19), 23, 2, 3),
-                Monkey::new(1, vec![54, 65, 75, 74], Op::Plus, Rh::Num(6), 19, 2, 0),
-                Monkey::new(2, vec![79, 60, 97], Op::Multiply, Rh::Old, 13, 1, 3),
-                Monkey::new(3, vec![74], Op::Plus, Rh::Num(3), 17, 0, 1),
+                Monkey::new(0, vec![79, 98], mul(Expr::Old, Expr::Num(19)), 23, 2, 3),
+                Monkey::new(
+                    1,
+                    vec![54, 65, 75, 74],
+                    add(Expr::Old, Expr::Num(6)),
+                    19,
+                    2,
+                    0
+                ),
+                Monkey::new(2, vec![79, 60, 97], mul(Expr::Old, Expr::Old), 13, 1, 3),
+                Monkey::new(3, vec![74], add(Expr::Old, Expr::Num(3)), 17, 0, 1),
             ]
         )
     }
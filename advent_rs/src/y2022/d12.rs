@@ -1,7 +1,8 @@
-use colored::Colorize;
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use crate::{interpolate_color, read_file};
+use crate::dashboard::save_visual;
+use crate::heatmap::Heatmap;
+use crate::{read_file, trace};
 
 const FILE_NAME: &str = "data/2022/input12.txt";
 
@@ -123,7 +124,7 @@ fn build_graph(grid: &Grid) -> HashMap<usize, Vec<usize>> {
 
 fn proc1(input: &str) -> u32 {
     let grid = load(input);
-    println!("{grid:?}");
+    trace!("{grid:?}");
 
     let graph = build_graph(&grid);
 
@@ -136,7 +137,7 @@ fn proc1(input: &str) -> u32 {
     let mut next_pos = VecDeque::new();
     push_back_next_nodes(&graph, grid.start, &visited, &mut next_pos);
 
-    println!("{:?}", next_pos);
+    trace!("{:?}", next_pos);
     while let Some((from, to)) = next_pos.pop_front() {
         let next = dist[from] + 1;
         if dist[to] > next {
@@ -152,6 +153,7 @@ fn proc1(input: &str) -> u32 {
     }
 
     draw_map(&dist, grid.rows, grid.cols, grid.start, grid.target);
+    save_map(&dist, grid.cols, grid.start, grid.target, "from-start.svg");
     dist[grid.target]
 }
 
@@ -170,37 +172,22 @@ fn push_back_next_nodes(
     }
 }
 
+fn heatmap(map: &[u32], cols: usize, start: usize, target: usize) -> Heatmap {
+    Heatmap::from_grid(map, cols, |v| v != u32::MAX)
+        .start(start)
+        .end(target)
+}
+
 fn draw_map(map: &Vec<u32>, rows: usize, cols: usize, start: usize, target: usize) {
-    let max = 340; // ((rows * cols - 1) / 10) as u32;
-    let t = format!("{}", 178 as char);
-    let a = '0' as u8;
-    for r in 0..rows {
-        (0..cols).for_each(|col| {
-            let idx = r * cols + col;
-            let (color, txt) = if idx == start {
-                ([255, 255, 0], "S")
-            } else if idx == target {
-                ([0, 255, 255], "E")
-            } else {
-                let v = map[idx];
-                (
-                    if v > max {
-                        [0, 0, 0]
-                    } else {
-                        let factor = v as f32 / max as f32;
-                        interpolate_color([255, 255, 255], [255, 0, 0], factor)
-                    },
-                    "X",
-                )
-            };
-            print!("{}", txt.truecolor(color[0], color[1], color[2]));
-        });
-        println!("");
-    }
-    println!("---");
+    trace!("\n{}", heatmap(map, cols, start, target).render_terminal());
+}
+
+fn save_map(map: &[u32], cols: usize, start: usize, target: usize, name: &str) {
+    let svg = heatmap(map, cols, start, target).to_svg(6);
+    save_visual(2022, 12, name, svg.as_bytes());
 }
 
-fn quiz1() -> u32 {
+pub(crate) fn quiz1() -> u32 {
     let input = read_file(FILE_NAME);
     proc1(&input)
 }
@@ -240,10 +227,10 @@ fn build_graph2(grid: &Grid) -> HashMap<usize, Vec<usize>> {
 }
 fn proc2(input: &str) -> u32 {
     let grid = load(input);
-    println!("{grid:?}");
+    trace!("{grid:?}");
 
     let graph = build_graph2(&grid);
-    println!("{graph:?}");
+    trace!("{graph:?}");
 
     let mut dist = vec![u32::MAX; grid.rows * grid.cols];
     dist[grid.target] = 0;
@@ -254,9 +241,9 @@ fn proc2(input: &str) -> u32 {
     let mut next_pos = VecDeque::new();
     push_back_next_nodes(&graph, grid.target, &visited, &mut next_pos);
 
-    println!("{:?}", next_pos);
+    trace!("{:?}", next_pos);
     while let Some((from, to)) = next_pos.pop_front() {
-        println!("{from} -> {to}");
+        trace!("{from} -> {to}");
 
         let next = dist[from] + 1;
         if dist[to] > next {
@@ -271,6 +258,7 @@ fn proc2(input: &str) -> u32 {
         push_back_next_nodes(&graph, to, &visited, &mut next_pos);
         draw_map(&dist, grid.rows, grid.cols, grid.start, grid.target);
     }
+    save_map(&dist, grid.cols, grid.start, grid.target, "to-target.svg");
 
     let mut min_dist = u32::MAX;
     for (idx, v) in grid.grid.iter().enumerate() {
@@ -284,7 +272,7 @@ fn proc2(input: &str) -> u32 {
     min_dist
 }
 
-fn quiz2() -> u32 {
+pub(crate) fn quiz2() -> u32 {
     let input = read_file(FILE_NAME);
     proc2(&input)
 }
@@ -1,84 +1,223 @@
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet, VecDeque};
-
-use colored::Colorize;
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::combinator::eof;
-use nom::multi::separated_list0;
-use nom::sequence::terminated;
-use nom::{IResult, Parser};
-use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
 
 use PackData::*;
 
-use crate::{interpolate_color, read_file};
+use crate::read_file;
 
 const FILE_NAME: &str = "data/2022/input13.txt";
 
-#[derive(Debug, PartialEq, Clone)]
-enum PackData {
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PackData {
     V(u32),
     L(Vec<PackData>),
 }
 
-impl Eq for PackData {}
-
 impl Ord for PackData {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        match (self, other) {
+            (V(v1), V(v2)) => v1.cmp(v2),
+            (L(vs1), L(vs2)) => vs1.cmp(vs2),
+            (L(vs1), V(_)) => vs1.as_slice().cmp(std::slice::from_ref(other)),
+            (V(_), L(vs2)) => std::slice::from_ref(self).cmp(vs2.as_slice()),
+        }
     }
 }
 
 impl PartialOrd<Self> for PackData {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match (self, other) {
-            (V(v1), V(v2)) => v1.partial_cmp(v2),
-            (L(vs1), L(vs2)) => vs1
-                .iter()
-                .zip(vs2)
-                .map(|(v1, v2)| v1.partial_cmp(v2).unwrap())
-                .find(|ordering| ordering.is_ne())
-                .or_else(|| vs1.len().partial_cmp(&vs2.len())),
-            (L(_), V(_)) => self.partial_cmp(&L(vec![other.clone()])),
-            (V(_), L(_)) => L(vec![self.clone()]).partial_cmp(other),
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for PackData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            V(v) => write!(f, "{v}"),
+            L(vs) => {
+                write!(f, "[")?;
+                for (i, v) in vs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{v}")?;
+                }
+                write!(f, "]")
+            }
         }
     }
 }
 
-type Packet = Vec<PackData>;
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub pos: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.msg, self.pos)
+    }
+}
 
-fn v(input: &str) -> IResult<&str, PackData> {
-    let (input, n) = complete::u32(input)?;
-    Ok((input, V(n)))
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+    // JSON allows whitespace between tokens, the puzzle format does not
+    lenient: bool,
 }
 
-fn l(input: &str) -> IResult<&str, PackData> {
-    let (input, _) = tag("[")(input)?;
-    let (input, vs) = separated_list0(tag(","), alt((v, l))).parse(input)?;
-    let (input, _) = tag("]")(input)?;
-    Ok((input, L(vs)))
+impl<'a> Parser<'a> {
+    fn error<T>(&self, msg: &str) -> Result<T, ParseError> {
+        Err(ParseError {
+            pos: self.pos,
+            msg: msg.to_string(),
+        })
+    }
+
+    fn skip_ws(&mut self) {
+        while self.lenient
+            && self.pos < self.text.len()
+            && self.text[self.pos].is_ascii_whitespace()
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_ws();
+        self.text.get(self.pos).copied()
+    }
+
+    fn value(&mut self) -> Result<PackData, ParseError> {
+        match self.peek() {
+            Some(b'[') => self.list(),
+            Some(c) if c.is_ascii_digit() => self.number(),
+            Some(_) => self.error("expected a number or a list"),
+            None => self.error("unexpected end of input"),
+        }
+    }
+
+    fn number(&mut self) -> Result<PackData, ParseError> {
+        let start = self.pos;
+        while self.pos < self.text.len() && self.text[self.pos].is_ascii_digit() {
+            self.pos += 1;
+        }
+        let digits = std::str::from_utf8(&self.text[start..self.pos]).unwrap();
+        match digits.parse() {
+            Ok(n) => Ok(V(n)),
+            Err(_) => {
+                self.pos = start;
+                self.error("number out of range")
+            }
+        }
+    }
+
+    fn list(&mut self) -> Result<PackData, ParseError> {
+        self.pos += 1;
+        let mut vs = vec![];
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(L(vs));
+        }
+        loop {
+            vs.push(self.value()?);
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(L(vs));
+                }
+                Some(_) => return self.error("expected ',' or ']'"),
+                None => return self.error("unclosed list"),
+            }
+        }
+    }
+
+    fn parse(text: &'a str, lenient: bool) -> Result<PackData, ParseError> {
+        let mut parser = Parser {
+            text: text.as_bytes(),
+            pos: 0,
+            lenient,
+        };
+        let value = parser.value()?;
+        if parser.peek().is_some() {
+            return parser.error("trailing characters");
+        }
+        Ok(value)
+    }
 }
 
-fn parse_line(line: &str) -> Packet {
-    if let Ok((_, L(vs))) = l(line) {
-        vs
-    } else {
-        panic!()
+impl FromStr for PackData {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::parse(s, false)
     }
 }
 
-fn load_block(block: &str) -> (Packet, Packet) {
-    let mut lines = block.lines();
-    (
-        parse_line(lines.next().unwrap()),
-        parse_line(lines.next().unwrap()),
-    )
+impl PackData {
+    // a non-negative JSON integer or an array of them and nested arrays
+    pub fn from_json(json: &str) -> Result<Self, ParseError> {
+        Parser::parse(json, true)
+    }
+
+    pub fn divider(n: u32) -> Self {
+        L(vec![L(vec![V(n)])])
+    }
 }
 
-fn load(input: &str) -> Vec<(Packet, Packet)> {
-    input.split("\n\n").map(|block| load_block(block)).collect()
+pub fn merge<T: Ord>(a: Vec<T>, b: Vec<T>) -> Vec<T> {
+    let mut merged = Vec::with_capacity(a.len() + b.len());
+    let mut a = a.into_iter().peekable();
+    let mut b = b.into_iter().peekable();
+    loop {
+        let next = match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) if x <= y => a.next(),
+            (Some(_), Some(_)) => b.next(),
+            (Some(_), None) => a.next(),
+            (None, Some(_)) => b.next(),
+            (None, None) => return merged,
+        };
+        merged.extend(next);
+    }
+}
+
+// 1-based positions the dividers take once merged into the sorted packets.
+// Each divider carries its index, so a packet equal to one is never
+// mistaken for it; equal packets sort first
+pub fn divider_positions(packets: &[PackData], dividers: &[PackData]) -> Vec<usize> {
+    let mut sorted_packets = packets.iter().map(|p| (p, None)).collect::<Vec<_>>();
+    sorted_packets.sort();
+    let mut sorted_dividers = dividers
+        .iter()
+        .enumerate()
+        .map(|(i, d)| (d, Some(i)))
+        .collect::<Vec<_>>();
+    sorted_dividers.sort();
+
+    let merged = merge(sorted_packets, sorted_dividers);
+    let mut positions = vec![0; dividers.len()];
+    for (pos, (_, index)) in merged.into_iter().enumerate() {
+        if let Some(i) = index {
+            positions[i] = pos + 1;
+        }
+    }
+    positions
+}
+
+fn load(input: &str) -> Vec<(PackData, PackData)> {
+    input
+        .split("\n\n")
+        .map(|block| {
+            let mut lines = block.lines();
+            (
+                lines.next().unwrap().parse().unwrap(),
+                lines.next().unwrap().parse().unwrap(),
+            )
+        })
+        .collect()
 }
 
 fn proc1(input: &str) -> u32 {
@@ -90,35 +229,24 @@ fn proc1(input: &str) -> u32 {
         .sum()
 }
 
-fn quiz1() -> u32 {
+pub(crate) fn quiz1() -> u32 {
     let input = read_file(FILE_NAME);
     proc1(&input)
 }
 
 fn proc2(input: &str) -> u32 {
-    let mut ps = load(input)
+    let packets = load(input)
         .into_iter()
         .flat_map(|(p1, p2)| vec![p1, p2])
-        .collect::<Vec<Packet>>();
-
-    ps.push(vec![L(vec![V(2)])]);
-    ps.push(vec![L(vec![V(6)])]);
+        .collect::<Vec<_>>();
 
-    ps.sort();
-
-    ps.into_iter()
-        .enumerate()
-        .filter_map(|(idx, p)| {
-            if p.len() == 1 && (p[0].eq(&L(vec![V(2)])) || p[0].eq(&L(vec![V(6)]))) {
-                Some(idx as u32 + 1)
-            } else {
-                None
-            }
-        })
+    divider_positions(&packets, &[PackData::divider(2), PackData::divider(6)])
+        .iter()
+        .map(|p| *p as u32)
         .product()
 }
 
-fn quiz2() -> u32 {
+pub(crate) fn quiz2() -> u32 {
     let input = read_file(FILE_NAME);
     proc2(&input)
 }
@@ -127,6 +255,16 @@ fn quiz2() -> u32 {
 mod tests {
     use super::*;
 
+    type Packet = Vec<PackData>;
+
+    fn parse_line(line: &str) -> Packet {
+        match line.parse() {
+            Ok(L(vs)) => vs,
+            Ok(V(_)) => panic!("not a list: {}", line),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
     const INPUT: &str = "[1,1,3,1,1]
 [1,1,5,1,1]
 
@@ -176,6 +314,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_display_round_trip() {
+        for line in INPUT.lines().filter(|l| !l.is_empty()) {
+            assert_eq!(line.parse::<PackData>().unwrap().to_string(), line);
+        }
+        assert_eq!(V(42).to_string().parse(), Ok(V(42)));
+        assert_eq!(PackData::from_json(" 7 "), Ok(V(7)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |s: &str| s.parse::<PackData>().unwrap_err();
+        assert_eq!(err("").pos, 0);
+        assert_eq!(
+            err("[1,2"),
+            ParseError {
+                pos: 4,
+                msg: "unclosed list".to_string()
+            }
+        );
+        assert_eq!(err("[1;2]").pos, 2);
+        assert_eq!(err("[1,,2]").pos, 3);
+        assert_eq!(err("[1] ").msg, "trailing characters");
+        assert_eq!(err("[1, 2]").pos, 3);
+        assert_eq!(err("[99999999999]").msg, "number out of range");
+    }
+
+    #[test]
+    fn test_from_json() {
+        assert_eq!(
+            PackData::from_json(" [ 1, [2 ,3], [] ]\n"),
+            Ok(L(vec![V(1), L(vec![V(2), V(3)]), L(vec![])]))
+        );
+        assert_eq!(PackData::from_json("[1, -2]").unwrap_err().pos, 4);
+        assert_eq!(PackData::from_json("[\"a\"]").unwrap_err().pos, 1);
+    }
+
+    #[test]
+    fn test_divider_positions() {
+        let packets = load(INPUT)
+            .into_iter()
+            .flat_map(|(p1, p2)| vec![p1, p2])
+            .collect::<Vec<_>>();
+        let dividers = [PackData::divider(6), PackData::divider(2)];
+        assert_eq!(divider_positions(&packets, &dividers), vec![14, 10]);
+
+        // a packet equal to a divider sorts just before it
+        let mut with_copy = packets.clone();
+        with_copy.push(PackData::divider(2));
+        assert_eq!(divider_positions(&with_copy, &dividers), vec![15, 11]);
+        let same = [PackData::divider(2), PackData::divider(2)];
+        assert_eq!(divider_positions(&with_copy, &same), vec![11, 12]);
+
+        let mut sorted = packets.clone();
+        sorted.sort();
+        assert_eq!(sorted[0].to_string(), "[]");
+        assert_eq!(sorted[15].to_string(), "[9]");
+        assert_eq!(
+            merge(sorted.clone(), vec![PackData::divider(2)])[9],
+            PackData::divider(2)
+        );
+    }
+
     #[test]
     fn test_proc1() {
         assert_eq!(proc1(INPUT), 13);
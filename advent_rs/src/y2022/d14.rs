@@ -11,6 +11,7 @@ use nom::sequence::{separated_pair, terminated};
 use nom::{IResult, Parser};
 use regex::Regex;
 
+use crate::debugger::{Debugger, Simulation};
 use crate::{interpolate_color, read_file};
 
 const FILE_NAME: &str = "data/2022/input14.txt";
@@ -28,7 +29,7 @@ fn load(input: &str) -> Vec<Vec<(u32, u32)>> {
     ps
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 struct Cave {
     left: u32,
     top: u32,
@@ -68,10 +69,17 @@ impl Cave {
         self.inf || (x >= self.left && x <= self.right && y <= self.bottom)
     }
 
-    fn draw(&self) {
+    // where a grain at (x, y) moves next: down, down-left or down-right
+    fn below(&self, x: u32, y: u32) -> Option<(u32, u32)> {
+        [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)]
+            .iter()
+            .copied()
+            .find(|(x, y)| !self.have_rock(*x, *y))
+    }
+
+    fn view(&self, grain: Option<(u32, u32)>) -> String {
         let mut view = String::new();
         let width = (self.right - self.left + 1) as usize;
-        let empty = ".".repeat(width);
         let base_line = "#".repeat(width);
         let bottom = if self.inf {
             self.bottom - 1
@@ -80,20 +88,16 @@ impl Cave {
         };
         for y in 0..=bottom {
             view.push_str(&format!("{:2} ", y));
-            if let Some(hs) = self.rocks.get(&y) {
-                for x in self.left..=self.right {
-                    if hs.contains(&x) {
-                        if self.base.contains(&(x, y)) {
-                            view.push('#');
-                        } else {
-                            view.push('o');
-                        }
-                    } else {
-                        view.push('.');
-                    }
+            for x in self.left..=self.right {
+                if grain == Some((x, y)) {
+                    view.push('+');
+                } else if !self.have_rock(x, y) {
+                    view.push('.');
+                } else if self.base.contains(&(x, y)) {
+                    view.push('#');
+                } else {
+                    view.push('o');
                 }
-            } else {
-                view.push_str(&empty);
             }
             view.push('\n');
         }
@@ -103,8 +107,74 @@ impl Cave {
             view.push_str(&base_line);
             view.push('\n');
         }
+        view
+    }
+}
 
-        println!("{view}");
+// one grain of sand at a time falling from the source until it settles
+#[derive(Debug, Clone)]
+struct SandFall {
+    cave: Cave,
+    source: (u32, u32),
+    grain: (u32, u32),
+    settled: u32,
+    done: bool,
+}
+
+impl SandFall {
+    fn new(cave: Cave, source: (u32, u32)) -> Self {
+        SandFall {
+            cave,
+            source,
+            grain: source,
+            settled: 0,
+            done: false,
+        }
+    }
+}
+
+impl Simulation for SandFall {
+    type State = SandFall;
+    type Key = (u32, (u32, u32));
+
+    fn state(&self) -> SandFall {
+        self.clone()
+    }
+
+    fn restore(&mut self, state: SandFall) {
+        *self = state;
+    }
+
+    // moves the falling grain one square, stopping once a grain falls into
+    // the abyss or one settles on the source
+    fn step(&mut self) -> bool {
+        let (x, y) = self.grain;
+        if self.done || !self.cave.is_safe(x, y) {
+            self.done = true;
+            return false;
+        }
+        match self.cave.below(x, y) {
+            Some(next) => self.grain = next,
+            None => {
+                self.cave.add_sand(x, y);
+                self.settled += 1;
+                self.done = self.grain == self.source;
+                self.grain = self.source;
+            }
+        }
+        true
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "{}settled: {}",
+            self.cave.view(Some(self.grain)),
+            self.settled
+        )
+    }
+
+    fn key(&self) -> Self::Key {
+        (self.settled, self.grain)
     }
 }
 
@@ -183,92 +253,38 @@ fn update_bound((left, top, right, bottom): &mut (u32, u32, u32, u32), xs: &[(u3
     }
 }
 
-fn fall_1(mut cave: Cave, init_x: u32, init_y: u32) -> u32 {
-    let mut sx = init_x;
-    let mut sy = init_y;
-    let mut count = 0;
-
-    while cave.is_safe(sx, sy) {
-        let bb = cave.have_rock(sx, sy + 1);
-        if !bb {
-            sy += 1;
-        } else {
-            let bl = cave.have_rock(sx - 1, sy + 1);
-            let br = cave.have_rock(sx + 1, sy + 1);
-            if bb && !bl {
-                sx -= 1;
-                sy += 1;
-            } else if bb && bl && !br {
-                sx += 1;
-                sy += 1;
-            } else if bb && bl && br {
-                cave.add_sand(sx, sy);
-                count += 1;
-                sx = init_x;
-                sy = init_y;
-                cave.draw();
-            }
-        }
-    }
-
-    count
+fn fall(cave: Cave, init_x: u32, init_y: u32) -> u32 {
+    let mut sand = SandFall::new(cave, (init_x, init_y));
+    while sand.step() {}
+    sand.settled
 }
 
 fn proc1(input: &str) -> u32 {
     let cave = to_cave(load(input), false);
-    fall_1(cave, 500, 0)
+    fall(cave, 500, 0)
 }
 
-fn quiz1() -> u32 {
+pub(crate) fn quiz1() -> u32 {
     let input = read_file(FILE_NAME);
     proc1(&input)
 }
 
-fn fall_2(mut cave: Cave, init_x: u32, init_y: u32) -> u32 {
-    let mut sx = init_x;
-    let mut sy = init_y;
-    let mut count = 0;
-
-    loop {
-        let bb = cave.have_rock(sx, sy + 1);
-        if !bb {
-            sy += 1;
-        } else {
-            let bl = cave.have_rock(sx - 1, sy + 1);
-            let br = cave.have_rock(sx + 1, sy + 1);
-            if bb && !bl {
-                sx -= 1;
-                sy += 1;
-            } else if bb && bl && !br {
-                sx += 1;
-                sy += 1;
-            } else if bb && bl && br {
-                cave.add_sand(sx, sy);
-                cave.draw();
-                count += 1;
-                if sx == init_x && sy == init_y {
-                    break;
-                } else {
-                    sx = init_x;
-                    sy = init_y;
-                }
-            }
-        }
-    }
-
-    count
-}
-
 fn proc2(input: &str) -> u32 {
     let cave = to_cave(load(input), true);
-    fall_2(cave, 500, 0)
+    fall(cave, 500, 0)
 }
 
-fn quiz2() -> u32 {
+pub(crate) fn quiz2() -> u32 {
     let input = read_file(FILE_NAME);
     proc2(&input)
 }
 
+pub(crate) fn debug(input: Option<&str>) {
+    let input = input.map_or_else(|| read_file(FILE_NAME), str::to_string);
+    let cave = to_cave(load(&input), false);
+    Debugger::new(SandFall::new(cave, (500, 0))).run_terminal();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,6 +360,22 @@ mod tests {
         assert_eq!(quiz1(), 843);
     }
 
+    #[test]
+    fn test_sand_fall() {
+        let mut dbg = Debugger::new(SandFall::new(to_cave(load(INPUT), false), (500, 0)));
+        assert!(dbg.run_until(|sand| sand.settled == 1));
+        assert_eq!(dbg.steps(), 9);
+        assert_eq!(dbg.sim().render().lines().next(), Some(" 0 ......+..."));
+        assert_eq!(
+            dbg.sim().render().lines().skip(8).collect::<Vec<_>>(),
+            [" 8 ......o.#.", " 9 #########.", "settled: 1"]
+        );
+        assert!(dbg.finish(usize::MAX));
+        assert_eq!(dbg.sim().settled, 24);
+        dbg.back(dbg.steps());
+        assert_eq!(dbg.sim().settled, 0);
+    }
+
     #[test]
     fn test_proc2() {
         assert_eq!(proc2(INPUT), 93);
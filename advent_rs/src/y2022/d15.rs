@@ -100,7 +100,7 @@ fn dist((sx, sy): Coord, (bx, by): Coord) -> i64 {
     (sx - bx).abs() + (sy - by).abs()
 }
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     let input = read_file(FILE_NAME);
     proc1(&input, 2000000)
 }
@@ -223,7 +223,7 @@ fn proc2(input: &str, sz: i64) -> usize {
     panic!()
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     let input = read_file(FILE_NAME);
     proc2(&input, 4000000)
 }
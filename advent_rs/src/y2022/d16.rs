@@ -1,24 +1,14 @@
-use std::borrow::ToOwned;
-use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::ops::RangeInclusive;
-
-use colored::Colorize;
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::character::complete::newline;
-use nom::multi::separated_list0;
-use nom::sequence::{separated_pair, terminated};
-use nom::IResult;
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+
 use regex::Regex;
 
-use crate::{interpolate_color, read_file, set};
+use crate::{read_file, trace};
 
 const FILE_NAME: &str = "data/2022/input16.txt";
 
 #[derive(Debug, PartialEq)]
-struct Valve {
+pub struct Valve {
     name: String,
     idx: usize,
     rate: u32,
@@ -79,11 +69,11 @@ https://graphviz.org/
 http://magjac.com/graphviz-visual-editor/
 https://dreampuf.github.io/GraphvizOnline/
  */
-fn to_dot(valves: &HashMap<usize, Valve>) -> String {
+fn to_dot(valves: &[Valve]) -> String {
     let mut graph = "strict graph {\n".to_owned();
     let nodes = valves
         .iter()
-        .map(|(idx, valve)| {
+        .map(|valve| {
             let mut s = String::new();
             s.push_str(&format!(
                 "    {0} [label=\"{0}[{1}] {2}\", fontsize=8]\n",
@@ -93,9 +83,7 @@ fn to_dot(valves: &HashMap<usize, Valve>) -> String {
             for (next, cost) in valve.nexts.iter() {
                 s.push_str(&format!(
                     "    {} -- {} [label={}, fontsize=8]\n",
-                    valve.name,
-                    valves.get(next).unwrap().name,
-                    cost
+                    valve.name, valves[*next].name, cost
                 ));
             }
             s
@@ -109,186 +97,175 @@ fn to_dot(valves: &HashMap<usize, Valve>) -> String {
     graph
 }
 
-fn find_next(valve: &Valve, next_id: usize) -> usize {
-    valve
-        .nexts
-        .iter()
-        .enumerate()
-        .find(|(_, (i, c))| *i == next_id)
-        .map(|(idx, _)| idx)
-        .unwrap()
+// useful valves with the shortest distances between them
+#[derive(Debug)]
+pub struct Network {
+    names: Vec<String>,
+    rates: Vec<u32>,
+    dist: Vec<Vec<u32>>,
+    // distance from the start valve to each useful valve
+    from_start: Vec<u32>,
 }
 
-fn connect(valves: &mut HashMap<usize, Valve>, idx: usize) {
-    let valve = valves.get(&idx).unwrap();
-    let (left_idx, left_cost) = valve.nexts[0];
-    let (right_idx, right_cost) = valve.nexts[1];
-    let cost = left_cost + right_cost;
-
-    valves.entry(left_idx).and_modify(|left| {
-        left.nexts.remove(find_next(left, idx));
-        left.nexts.push((right_idx, cost));
-    });
-
-    valves.entry(right_idx).and_modify(|right| {
-        right.nexts.remove(find_next(right, idx));
-        right.nexts.push((left_idx, cost));
-    });
-
-    valves.entry(idx).and_modify(|valve| {
-        valve.nexts.clear();
-    });
+fn bfs(valves: &[Valve], from: usize) -> Vec<u32> {
+    let mut dist = vec![u32::MAX; valves.len()];
+    let mut queue = VecDeque::from([from]);
+    dist[from] = 0;
+    while let Some(idx) = queue.pop_front() {
+        for (next, weight) in valves[idx].nexts.iter() {
+            if dist[*next] == u32::MAX {
+                dist[*next] = dist[idx] + weight;
+                queue.push_back(*next);
+            }
+        }
+    }
+    dist
 }
 
-fn compact_valves(valves: &mut HashMap<usize, Valve>) {
-    let start: String = "AA".to_owned();
-
-    loop {
-        let mut candidate = HashSet::new();
-        let keys = valves.keys().map(|k| *k).collect::<Vec<usize>>();
+impl Network {
+    pub fn compress(valves: &[Valve], start: &str) -> Self {
+        let useful = valves
+            .iter()
+            .filter(|v| v.rate > 0)
+            .map(|v| v.idx)
+            .collect::<Vec<_>>();
+        assert!(useful.len() <= 32, "too many valves for a u32 mask");
+
+        let pick = |dist: Vec<u32>| useful.iter().map(|i| dist[*i]).collect::<Vec<_>>();
+        let start = valves.iter().find(|v| v.name == start).unwrap().idx;
+        Network {
+            names: useful.iter().map(|i| valves[*i].name.clone()).collect(),
+            rates: useful.iter().map(|i| valves[*i].rate).collect(),
+            dist: useful.iter().map(|i| pick(bfs(valves, *i))).collect(),
+            from_start: pick(bfs(valves, start)),
+        }
+    }
 
-        for idx in keys {
-            let valve = valves.get(&idx).unwrap();
+    // best pressure and schedule for every set of opened valves reachable in `time`
+    pub fn explore(&self, time: u32) -> HashMap<u32, (u32, Vec<Opening>)> {
+        let mut best: HashMap<u32, (u32, Vec<Opening>)> = HashMap::new();
+        // (valve, opened, time left) -> most pressure seen arriving there
+        let mut seen: HashMap<(usize, u32, u32), u32> = HashMap::new();
+        let mut stack = vec![];
 
-            if valve.name == start || valve.rate > 0 {
-                continue;
+        for v in 0..self.names.len() {
+            if self.from_start[v] < time {
+                stack.push((v, 0u32, time - self.from_start[v], 0u32, vec![]));
             }
-
-            // ???
-            if valve.nexts.len() != 2 {
+        }
+        best.insert(0, (0, vec![]));
+
+        while let Some((v, mask, left, pressure, schedule)) = stack.pop() {
+            // opening takes a minute
+            let left = left - 1;
+            let mask = mask | 1 << v;
+            let pressure = pressure + self.rates[v] * left;
+            if seen.get(&(v, mask, left)).is_some_and(|p| *p >= pressure) {
                 continue;
             }
+            seen.insert((v, mask, left), pressure);
+
+            let mut schedule = schedule;
+            schedule.push(Opening {
+                valve: self.names[v].clone(),
+                minute: time - left,
+            });
+            if best.get(&mask).is_none_or(|(p, _)| *p < pressure) {
+                best.insert(mask, (pressure, schedule.clone()));
+            }
 
-            connect(valves, idx);
-            candidate.insert(idx);
-        }
-
-        if candidate.is_empty() {
-            break;
+            // unreachable valves are u32::MAX away
+            for next in 0..self.names.len() {
+                let reachable = self.dist[v][next].checked_add(1).is_some_and(|d| d < left);
+                if mask & 1 << next == 0 && reachable {
+                    let arrive = left - self.dist[v][next];
+                    stack.push((next, mask, arrive, pressure, schedule.clone()));
+                }
+            }
         }
+        best
+    }
 
-        for idx in candidate {
-            valves.remove(&idx);
+    pub fn best_plan(&self, time: u32) -> Plan {
+        let (pressure, schedule) = self
+            .explore(time)
+            .into_values()
+            .max_by_key(|(p, _)| *p)
+            .unwrap();
+        Plan {
+            pressure,
+            schedules: vec![schedule],
         }
     }
-}
-
-fn to_map(valves: Vec<Valve>) -> HashMap<usize, Valve> {
-    valves.into_iter().map(|valve| (valve.idx, valve)).collect()
-}
-
-fn routes(valves: &HashMap<usize, Valve>) -> u32 {
-    let start = valves.iter().find(|(_, v)| v.name == "AA").unwrap().0;
-
-    // remains minute, releasing pressure
-    let mut routes: Vec<(Vec<usize>, HashSet<usize>, i32, u32)> =
-        vec![(vec![*start], set![*start], 30, 0)];
-
-    let mut result: Vec<(Vec<usize>, HashSet<usize>, u32)> = vec![];
-
-    let mut max_pressures = u32::MIN;
-    let mut max_route = vec![];
-    let mut max_opened = set![];
-
-    loop {
-        println!("routes: {:?}", routes.len());
 
-        let mut spawned: Vec<(Vec<usize>, HashSet<usize>, i32, u32)> = vec![];
+    // two agents opening disjoint sets of valves at the same time
+    pub fn best_pair_plan(&self, time: u32) -> Plan {
+        let mut options = self.explore(time).into_iter().collect::<Vec<_>>();
+        options.sort_by_key(|(_, (p, _))| Reverse(*p));
 
-        while let Some((route, opened, remains, pressures)) = routes.pop() {
-            let from = route[route.len() - 1];
-            let from_from = if route.len() >= 2 {
-                route[route.len() - 2]
-            } else {
-                usize::MAX
-            };
-
-            let valve = valves.get(&from).unwrap();
-
-            for (next, weight) in valve.nexts.iter() {
-                if valve.nexts.len() > 1 && *next == from_from {
-                    continue;
-                }
-
-                let mut new_remain = remains - *weight as i32;
-                if new_remain <= 0 {
-                    if pressures > max_pressures {
-                        max_pressures = pressures;
-                        max_route = route.clone();
-                        max_opened = opened.clone();
-                    }
-                    continue;
+        let mut best = (0, 0, 0);
+        for (i, (m1, (p1, _))) in options.iter().enumerate() {
+            // the list is sorted, nothing later can beat the best pair
+            if p1 * 2 < best.0 {
+                break;
+            }
+            for (j, (m2, (p2, _))) in options.iter().enumerate().skip(i) {
+                if p1 + p2 <= best.0 {
+                    break;
                 }
-
-                let mut new_route = route.clone();
-                new_route.push(*next);
-
-                spawned.push((new_route.clone(), opened.clone(), new_remain, pressures));
-
-                if !opened.contains(next) {
-                    let mut new_opened = opened.clone();
-                    new_opened.insert(*next);
-                    new_remain -= 1;
-                    let new_pressures =
-                        pressures + (new_remain as u32) * valves.get(next).unwrap().rate;
-                    if new_remain == 0 || new_opened.len() == valves.len() {
-                        if new_pressures > max_pressures {
-                            max_pressures = new_pressures;
-                            max_route = new_route;
-                            max_opened = new_opened;
-                        }
-                        continue;
-                    } else {
-                        spawned.push((new_route, new_opened, new_remain, new_pressures));
-                    }
+                if m1 & m2 == 0 {
+                    best = (p1 + p2, i, j);
                 }
             }
         }
 
-        println!("spawned : {}", spawned.len());
-        println!("max pressure : {}", max_pressures);
-        println!("routes: {:?}", max_route);
-        println!("opened: {:?}", max_opened);
-
-        println!("------");
-
-        if spawned.len() == 0 {
-            break;
+        let (pressure, i, j) = best;
+        Plan {
+            pressure,
+            schedules: vec![options[i].1 .1.clone(), options[j].1 .1.clone()],
         }
-
-        routes = spawned;
     }
+}
 
-    max_pressures
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opening {
+    pub valve: String,
+    // the minute the valve is opened in, it releases pressure from the next one
+    pub minute: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub pressure: u32,
+    // one schedule per agent
+    pub schedules: Vec<Vec<Opening>>,
 }
 
 fn proc1(input: &str) -> u32 {
     let valves = load(input);
-    let mut valves: HashMap<usize, Valve> = to_map(valves);
-    compact_valves(&mut valves);
-    println!("{}", to_dot(&valves));
-    routes(&valves)
+    trace!("{}", to_dot(&valves));
+    Network::compress(&valves, "AA").best_plan(30).pressure
 }
 
-fn quiz1() -> u32 {
+pub(crate) fn quiz1() -> u32 {
     let input = read_file(FILE_NAME);
     proc1(&input)
 }
 
-fn proc2(input: &str) -> usize {
-    todo!()
+fn proc2(input: &str) -> u32 {
+    let valves = load(input);
+    Network::compress(&valves, "AA").best_pair_plan(26).pressure
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> u32 {
     let input = read_file(FILE_NAME);
     proc2(&input)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::set;
-
     use super::*;
+    use std::collections::HashSet;
 
     // AA -> DD -> CC -> BB -> AA ->II -> JJ -> II -> AA -> DD -> EE -> FF _. GG -> HH -> GG -> Ff -> EE -> DD -> CC
     // AA -> (DD) -> CC -> (BB) -> AA -> (JJ) -> AA -> DD -> EE -> (HH) -> (EE) -> DD -> (CC)
@@ -336,15 +313,107 @@ Valve JJ has flow rate=21; tunnel leads to valve II";
         assert_eq!(quiz1(), 2253);
     }
 
+    fn schedule(plan: &[Opening]) -> Vec<(&str, u32)> {
+        plan.iter().map(|o| (o.valve.as_str(), o.minute)).collect()
+    }
+
+    #[test]
+    fn test_compress() {
+        let net = Network::compress(&load(INPUT), "AA");
+        assert_eq!(net.names, vec!["BB", "CC", "DD", "EE", "HH", "JJ"]);
+        assert_eq!(net.from_start, vec![1, 2, 1, 2, 5, 2]);
+        assert_eq!(net.dist[4][5], 7);
+        assert_eq!(net.dist[5][4], 7);
+    }
+
+    #[test]
+    fn test_unreachable_valve() {
+        let input = "Valve AA has flow rate=0; tunnel leads to valve BB
+Valve BB has flow rate=5; tunnel leads to valve AA
+Valve CC has flow rate=9; tunnel leads to valve DD
+Valve DD has flow rate=0; tunnel leads to valve CC";
+        let net = Network::compress(&load(input), "AA");
+        assert_eq!(net.dist[0][1], u32::MAX);
+        assert_eq!(net.best_plan(30).pressure, 5 * 28);
+        assert_eq!(net.best_pair_plan(26).pressure, 5 * 24);
+    }
+
+    #[test]
+    fn test_best_plan() {
+        let net = Network::compress(&load(INPUT), "AA");
+        let plan = net.best_plan(30);
+        assert_eq!(plan.pressure, 1651);
+        check_plan(&net, &plan, 30);
+        assert_eq!(
+            schedule(&plan.schedules[0]),
+            vec![
+                ("DD", 2),
+                ("BB", 5),
+                ("JJ", 9),
+                ("HH", 17),
+                ("EE", 21),
+                ("CC", 24)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_best_pair_plan() {
+        let net = Network::compress(&load(INPUT), "AA");
+        let plan = net.best_pair_plan(26);
+        assert_eq!(plan.pressure, 1707);
+        check_plan(&net, &plan, 26);
+
+        let mut schedules = plan
+            .schedules
+            .iter()
+            .map(|s| schedule(s))
+            .collect::<Vec<_>>();
+        schedules.sort();
+        assert_eq!(
+            schedules,
+            vec![
+                vec![("DD", 2), ("HH", 7), ("EE", 11)],
+                vec![("JJ", 3), ("BB", 7), ("CC", 9)],
+            ]
+        );
+    }
+
+    // replays every schedule of the plan: each agent walks the shortest path
+    // to its next valve and spends a minute opening it, no valve is opened
+    // twice, and the openings add up to the plan's pressure
+    fn check_plan(net: &Network, plan: &Plan, time: u32) {
+        let mut opened = HashSet::new();
+        let mut pressure = 0;
+        for schedule in plan.schedules.iter() {
+            let mut at = None;
+            let mut minute = 0;
+            for o in schedule {
+                let v = net.names.iter().position(|n| *n == o.valve).unwrap();
+                assert!(opened.insert(v), "{} opened twice", o.valve);
+                minute += at.map_or(net.from_start[v], |u: usize| net.dist[u][v]) + 1;
+                assert_eq!(o.minute, minute);
+                pressure += net.rates[v] * (time - minute);
+                at = Some(v);
+            }
+            assert!(minute <= time);
+        }
+        assert_eq!(plan.pressure, pressure);
+    }
+
     #[test]
-    #[ignore]
     fn test_proc2() {
-        assert_eq!(proc2(INPUT), 0);
+        assert_eq!(proc2(INPUT), 1707);
     }
 
+    // no part 2 answer is on record for this input yet, so check that the
+    // plan is one both agents can carry out and beats going alone
     #[test]
-    #[ignore]
     fn test_quiz2() {
-        assert_eq!(quiz2(), 0);
+        let net = Network::compress(&load(&read_file(FILE_NAME)), "AA");
+        let plan = net.best_pair_plan(26);
+        check_plan(&net, &plan, 26);
+        assert_eq!(plan.schedules.len(), 2);
+        assert!(plan.pressure >= net.best_plan(26).pressure);
     }
 }
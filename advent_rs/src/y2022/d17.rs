@@ -1,4 +1,6 @@
-use crate::read_file;
+use std::collections::HashMap;
+
+use crate::{debug, read_file, trace};
 
 const ROCKS: [&str; 5] = [
     "####",
@@ -17,6 +19,7 @@ const ROCKS: [&str; 5] = [
 ];
 
 const SIZE: usize = 7;
+const SKYLINE_ROWS: usize = 32;
 
 const FILE_NAME: &str = "data/2022/input17.txt";
 
@@ -204,38 +207,55 @@ impl Simulator {
     }
 
     fn play(&mut self, count: usize) -> usize {
-        let mut fell_count = 0;
-        let mut try_count = 0;
-        while fell_count < count {
-            if self.falling_rock.is_none() {
-                let rock_width = self.spawn_rock();
-
-                if self.fast_move(rock_width) {
-                    fell_count += 1;
-                    // self.draw("ok");
-                    continue;
-                }
+        for fell_count in 1..=count {
+            self.drop_rock();
+            if fell_count % 10_000_000 == 0 {
+                debug!("fell count: {fell_count}");
             }
+        }
+        self.height()
+    }
 
-            while !self.tick() {}
-            // self.draw("try ok");
-
+    // like `play`, but once the rock, the jet and the top of the tower repeat
+    // it skips over whole cycles instead of simulating them
+    fn play_cycled(&mut self, count: usize) -> usize {
+        let mut seen = HashMap::new();
+        let mut fell_count = 0;
+        let mut skipped = 0;
+        while fell_count < count {
+            self.drop_rock();
             fell_count += 1;
-            try_count += 1;
-            if fell_count % 10_000_000 == 0 {
-                println!("fell count: {fell_count}");
+            if skipped > 0 {
+                continue;
+            }
+            let key = (self.next_rock, self.next_move, self.skyline());
+            if let Some((prev_count, prev_height)) = seen.insert(key, (fell_count, self.height())) {
+                let period = fell_count - prev_count;
+                let cycles = (count - fell_count) / period;
+                skipped = cycles * (self.height() - prev_height);
+                fell_count += cycles * period;
             }
+        }
+        self.height() + skipped
+    }
 
-            // println!(
-            //     "try count: {}, ({} / {})",
-            //     try_count as f32 / fell_count as f32,
-            //     try_count,
-            //     fell_count,
-            // );
+    fn drop_rock(&mut self) {
+        let rock_width = self.spawn_rock();
+        if !self.fast_move(rock_width) {
+            while !self.tick() {}
         }
+    }
+
+    fn height(&self) -> usize {
         self.chamber.rocks_bound.1 as usize
     }
 
+    // the top rows of the tower, deep enough that no rock falls past them
+    fn skyline(&self) -> Vec<bool> {
+        let grid = &self.chamber.grid;
+        grid[grid.len().saturating_sub(SKYLINE_ROWS * SIZE)..].to_vec()
+    }
+
     fn fast_move(&mut self, rock_width: usize) -> bool {
         let mut m = 2;
 
@@ -260,7 +280,6 @@ impl Simulator {
     }
 
     fn draw(&self, msg: &str) {
-        println!("Rock ... {}", msg);
         let mut canvas = self
             .chamber
             .grid
@@ -300,8 +319,7 @@ impl Simulator {
             paper.push('\n');
         }
         paper.push_str("+-------+");
-        println!("{paper}");
-        println!();
+        trace!("Rock ... {}\n{}", msg, paper);
     }
 
     fn next_dir(&mut self) -> Dir {
@@ -370,12 +388,18 @@ impl Simulator {
     }
 }
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     let input = read_file(FILE_NAME);
     let mut simulator = Simulator::new(&input, rocks());
     simulator.play(2022)
 }
 
+pub(crate) fn quiz2() -> usize {
+    let input = read_file(FILE_NAME);
+    let mut simulator = Simulator::new(&input, rocks());
+    simulator.play_cycled(1000000000000)
+}
+
 fn rocks() -> Vec<Rock> {
     ROCKS.map(|pattern| Rock::new(pattern)).to_vec()
 }
@@ -398,9 +422,23 @@ mod tests {
         assert_eq!(quiz1(), 3067);
     }
 
+    #[test]
+    fn test_play_cycled() {
+        for count in [0, 1, 2022, 5000] {
+            let mut simulator = Simulator::new(INPUT, rocks());
+            let mut cycled = Simulator::new(INPUT, rocks());
+            assert_eq!(
+                cycled.play_cycled(count),
+                simulator.play(count),
+                "{}",
+                count
+            );
+        }
+    }
+
     #[test]
     fn test_proc2() {
         let mut simulator = Simulator::new(INPUT, rocks());
-        assert_eq!(simulator.play(1000000000000), 1514285714288);
+        assert_eq!(simulator.play_cycled(1000000000000), 1514285714288);
     }
 }
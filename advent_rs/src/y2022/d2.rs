@@ -24,7 +24,7 @@ fn total_score(input: &str) -> i32 {
         .sum()
 }
 
-fn quiz1() -> i32 {
+pub(crate) fn quiz1() -> i32 {
     let input = read_file("data/2022/input2.txt");
     total_score(&input)
 }
@@ -54,7 +54,7 @@ fn round_score(input: &str) -> i32 {
         .sum()
 }
 
-fn quiz2() -> i32 {
+pub(crate) fn quiz2() -> i32 {
     let input = read_file("data/2022/input2.txt");
     round_score(&input)
 }
@@ -25,7 +25,7 @@ fn sum_priorities(input: &str) -> i32 {
         .sum::<usize>() as i32
 }
 
-fn quiz1() -> i32 {
+pub(crate) fn quiz1() -> i32 {
     let input = read_file("data/2022/input3.txt");
     sum_priorities(&input)
 }
@@ -54,7 +54,7 @@ fn sum_priorities2(input: &str) -> i32 {
         .sum::<usize>() as i32
 }
 
-fn quiz2() -> i32 {
+pub(crate) fn quiz2() -> i32 {
     let input = read_file("data/2022/input3.txt");
     sum_priorities2(&input)
 }
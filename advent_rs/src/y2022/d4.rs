@@ -56,7 +56,7 @@ fn process1(input: &str) -> usize {
         .count()
 }
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     let input = read_file("data/2022/input4.txt");
     process1(&input)
 }
@@ -71,7 +71,7 @@ fn process2(input: &str) -> usize {
         .count()
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     let input = read_file("data/2022/input4.txt");
     process2(&input)
 }
@@ -1,118 +1,254 @@
+use std::fmt;
+use std::str::FromStr;
+
 use regex::Regex;
 
 use crate::read_file;
 
-#[derive(Debug, Eq, PartialEq)]
-struct Op {
-    count: usize,
-    from: usize,
-    to: usize,
+const FILE_NAME: &str = "data/2022/input5.txt";
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Move {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
 }
 
-impl Op {
-    fn new(count: usize, from: usize, to: usize) -> Self {
-        Op { count, from, to }
+impl Move {
+    pub fn new(count: usize, from: usize, to: usize) -> Self {
+        Move { count, from, to }
     }
 }
 
-type Stacks = Vec<Vec<String>>;
-type Ops = Vec<Op>;
-
-fn parse_input(input: &str) -> (Stacks, Ops) {
-    let blocks = input.split("\n\n").collect::<Vec<&str>>();
-    (parse_stack_block(blocks[0]), parse_ops_block(blocks[1]))
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Crane {
+    // CrateMover 9000, one crate at a time
+    Single,
+    // CrateMover 9001, the whole pile at once
+    Batch,
+    // up to n crates per lift
+    Capacity(usize),
 }
 
-fn parse_stack_block(block: &str) -> Vec<Vec<String>> {
-    let lines = block.lines().collect::<Vec<_>>();
-    let stack_max_height = lines.len() - 1;
-    let stack_count = lines.last().unwrap().split_whitespace().count();
+impl Crane {
+    fn capacity(&self) -> usize {
+        match self {
+            Crane::Single => 1,
+            Crane::Batch => usize::MAX,
+            Crane::Capacity(n) => (*n).max(1),
+        }
+    }
+}
 
-    let mut stacks: Vec<Vec<String>> = vec![vec!["".to_owned(); stack_max_height]; stack_count];
+#[derive(Debug, Eq, PartialEq)]
+pub enum StackError {
+    Parse {
+        line: usize,
+        msg: String,
+    },
+    NoSuchStack {
+        step: usize,
+        stack: usize,
+    },
+    Underflow {
+        step: usize,
+        stack: usize,
+        have: usize,
+        need: usize,
+    },
+}
 
-    for (idx, line) in lines.iter().enumerate() {
-        if idx == stack_max_height {
-            break;
+impl fmt::Display for StackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StackError::Parse { line, msg } => write!(f, "line {line}: {msg}"),
+            StackError::NoSuchStack { step, stack } => {
+                write!(f, "step {step}: no stack {stack}")
+            }
+            StackError::Underflow {
+                step,
+                stack,
+                have,
+                need,
+            } => write!(
+                f,
+                "step {step}: stack {stack} has {have} crates, needs {need}"
+            ),
         }
-        parse_stack_line(line, stack_max_height - idx - 1, &mut stacks);
     }
+}
+
+fn parse_error<T>(line: usize, msg: String) -> Result<T, StackError> {
+    Err(StackError::Parse { line, msg })
+}
 
-    for stack in stacks.iter_mut() {
-        while let Some(elm) = stack.last() {
-            if elm.is_empty() {
-                stack.pop();
-            } else {
-                break;
+// bottom crate first
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Stacks(Vec<Vec<char>>);
+
+impl Stacks {
+    pub fn stacks(&self) -> &[Vec<char>] {
+        &self.0
+    }
+
+    pub fn tops(&self) -> String {
+        self.0.iter().filter_map(|s| s.last()).collect()
+    }
+
+    // step is only used to label errors
+    pub fn apply(&mut self, mv: &Move, crane: Crane, step: usize) -> Result<(), StackError> {
+        for stack in [mv.from, mv.to] {
+            if stack == 0 || stack > self.0.len() {
+                return Err(StackError::NoSuchStack { step, stack });
             }
         }
+        let have = self.0[mv.from - 1].len();
+        if have < mv.count {
+            return Err(StackError::Underflow {
+                step,
+                stack: mv.from,
+                have,
+                need: mv.count,
+            });
+        }
+
+        let mut left = mv.count;
+        while left > 0 {
+            let lift = left.min(crane.capacity());
+            let from = &mut self.0[mv.from - 1];
+            let crates = from.split_off(from.len() - lift);
+            self.0[mv.to - 1].extend(crates);
+            left -= lift;
+        }
+        Ok(())
     }
 
-    stacks
+    pub fn run(&mut self, moves: &[Move], crane: Crane) -> Result<(), StackError> {
+        moves
+            .iter()
+            .enumerate()
+            .try_for_each(|(step, mv)| self.apply(mv, crane, step + 1))
+    }
 }
 
-fn parse_stack_line(line: &str, row: usize, stacks: &mut Vec<Vec<String>>) {
-    for (idx, c) in line.chars().enumerate() {
-        let col = idx / 4;
-        if c.is_alphabetic() {
-            stacks[col][row].push(c);
+impl FromStr for Stacks {
+    type Err = StackError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s.lines().collect::<Vec<_>>();
+        let labels = match lines.last() {
+            Some(labels) => labels,
+            None => return parse_error(1, "empty drawing".to_string()),
+        };
+        let count = labels.split_whitespace().count();
+        for (i, label) in labels.split_whitespace().enumerate() {
+            if label != (i + 1).to_string() {
+                return parse_error(lines.len(), format!("unexpected stack label {label}"));
+            }
+        }
+
+        let mut stacks = vec![vec![]; count];
+        for (row, line) in lines[..lines.len() - 1].iter().enumerate().rev() {
+            let cells = line.as_bytes().chunks(4).collect::<Vec<_>>();
+            if cells.len() > count {
+                return parse_error(row + 1, format!("more than {count} stacks"));
+            }
+            for (i, cell) in cells.iter().enumerate() {
+                match cell {
+                    [b'[', c, b']', rest @ ..]
+                        if c.is_ascii_alphabetic() && rest.iter().all(|r| *r == b' ') =>
+                    {
+                        if stacks[i].len() != lines.len() - 2 - row {
+                            return parse_error(
+                                row + 1,
+                                format!("crate floating above stack {}", i + 1),
+                            );
+                        }
+                        stacks[i].push(*c as char);
+                    }
+                    _ if cell.iter().all(|c| *c == b' ') => {}
+                    _ => {
+                        return parse_error(
+                            row + 1,
+                            format!(
+                                "unexpected {:?} at column {}",
+                                String::from_utf8_lossy(cell),
+                                i * 4 + 1
+                            ),
+                        )
+                    }
+                }
+            }
         }
+        Ok(Stacks(stacks))
     }
 }
 
-fn parse_ops_block(block: &str) -> Vec<Op> {
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.0.iter().map(|s| s.len()).max().unwrap_or(0);
+        for row in (0..height).rev() {
+            let cells = self
+                .0
+                .iter()
+                .map(|s| s.get(row).map_or("   ".to_string(), |c| format!("[{c}]")))
+                .collect::<Vec<_>>();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        let labels = (1..=self.0.len())
+            .map(|i| format!(" {i} "))
+            .collect::<Vec<_>>();
+        write!(f, "{}", labels.join(" "))
+    }
+}
+
+fn parse_moves(block: &str, first_line: usize) -> Result<Vec<Move>, StackError> {
     let regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
     block
         .lines()
-        .map(|line| {
-            let cas = regex.captures(line).unwrap();
-            let count = *&cas[1].parse::<usize>().unwrap();
-            let from = *&cas[2].parse::<usize>().unwrap();
-            let to = *&cas[3].parse::<usize>().unwrap();
-            Op::new(count, from, to)
+        .enumerate()
+        .map(|(i, line)| match regex.captures(line) {
+            Some(cas) => Ok(Move::new(
+                cas[1].parse().unwrap(),
+                cas[2].parse().unwrap(),
+                cas[3].parse().unwrap(),
+            )),
+            None => parse_error(first_line + i, format!("invalid move {line:?}")),
         })
         .collect()
 }
 
-fn proc1(input: &str) -> String {
-    let (mut stacks, ops) = parse_input(input);
+pub fn parse_input(input: &str) -> Result<(Stacks, Vec<Move>), StackError> {
+    let (drawing, moves) = match input.split_once("\n\n") {
+        Some(blocks) => blocks,
+        None => return parse_error(1, "missing moves".to_string()),
+    };
+    let stacks = drawing.parse()?;
+    let moves = parse_moves(moves.trim_end(), drawing.lines().count() + 2)?;
+    Ok((stacks, moves))
+}
 
-    for op in ops.iter() {
-        for _ in 0..op.count {
-            let item = stacks[op.from - 1].pop().unwrap();
-            stacks[op.to - 1].push(item);
-        }
-    }
+fn proc(input: &str, crane: Crane) -> String {
+    let (mut stacks, moves) = parse_input(input).unwrap();
+    stacks.run(&moves, crane).unwrap();
+    stacks.tops()
+}
 
-    stacks.iter().fold(String::new(), |mut acc, vs| {
-        acc.push_str(vs.last().unwrap());
-        acc
-    })
+fn proc1(input: &str) -> String {
+    proc(input, Crane::Single)
 }
 
-fn quiz1() -> String {
-    let input = read_file("data/2022/input5.txt");
+pub(crate) fn quiz1() -> String {
+    let input = read_file(FILE_NAME);
     proc1(&input)
 }
 
 fn proc2(input: &str) -> String {
-    let (mut stacks, ops) = parse_input(input);
-
-    for op in ops.iter() {
-        let index = stacks[op.to - 1].len();
-        for _ in 0..op.count {
-            let item = stacks[op.from - 1].pop().unwrap();
-            stacks[op.to - 1].insert(index, item);
-        }
-    }
-
-    stacks.iter().fold(String::new(), |mut acc, vs| {
-        acc.push_str(vs.last().unwrap());
-        acc
-    })
+    proc(input, Crane::Batch)
 }
 
-fn quiz2() -> String {
-    let input = read_file("data/2022/input5.txt");
+pub(crate) fn quiz2() -> String {
+    let input = read_file(FILE_NAME);
     proc2(&input)
 }
 
@@ -120,41 +256,132 @@ fn quiz2() -> String {
 mod tests {
     use super::*;
 
-    const INPUT: &str = "____[D]____
-[N] [C]____
+    const DRAWING: &str = "    [D]    
+[N] [C]    
 [Z] [M] [P]
-_1   2   3_
+ 1   2   3 ";
 
-move 1 from 2 to 1
+    const MOVES: &str = "move 1 from 2 to 1
 move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2";
 
+    fn input() -> String {
+        format!("{DRAWING}\n\n{MOVES}")
+    }
+
     #[test]
     fn test_parse() {
-        let (stacks, ops) = parse_input(&INPUT.replace("_", " "));
+        let (stacks, moves) = parse_input(&input()).unwrap();
         assert_eq!(
-            stacks,
-            vec![
-                vec!["Z".to_owned(), "N".to_owned()],
-                vec!["M".to_owned(), "C".to_owned(), "D".to_owned()],
-                vec!["P".to_owned()],
-            ],
+            stacks.stacks(),
+            &[vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']],
         );
         assert_eq!(
-            ops,
+            moves,
             vec![
-                Op::new(1, 2, 1),
-                Op::new(3, 1, 3),
-                Op::new(2, 2, 1),
-                Op::new(1, 1, 2),
+                Move::new(1, 2, 1),
+                Move::new(3, 1, 3),
+                Move::new(2, 2, 1),
+                Move::new(1, 1, 2),
             ],
         );
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let err = |s: &str| s.parse::<Stacks>().unwrap_err();
+        assert_eq!(
+            err("[A]\n[B] [C\n 1   2 "),
+            StackError::Parse {
+                line: 2,
+                msg: "unexpected \"[C\" at column 5".to_string()
+            }
+        );
+        assert!(matches!(
+            err("    [A]\n[B]    \n 1   2 "),
+            StackError::Parse { line: 1, .. }
+        ));
+        assert!(matches!(
+            err("[A] [B]\n 1 "),
+            StackError::Parse { line: 1, .. }
+        ));
+        assert!(matches!(err("[A]\n 2 "), StackError::Parse { line: 2, .. }));
+        assert_eq!(
+            parse_input(&format!("{DRAWING}\n\nmove 1 from 2 to 1\nmove x")).unwrap_err(),
+            StackError::Parse {
+                line: 7,
+                msg: "invalid move \"move x\"".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let (mut stacks, moves) = parse_input(&input()).unwrap();
+        assert_eq!(stacks.to_string(), DRAWING);
+
+        stacks.apply(&moves[0], Crane::Single, 1).unwrap();
+        assert_eq!(
+            stacks.to_string(),
+            "[D]        
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 "
+        );
+
+        stacks.apply(&moves[1], Crane::Single, 2).unwrap();
+        assert_eq!(stacks.to_string().parse::<Stacks>(), Ok(stacks.clone()));
+        assert_eq!(
+            stacks.to_string(),
+            "        [Z]
+        [N]
+    [C] [D]
+    [M] [P]
+ 1   2   3 "
+                .lines()
+                .map(|l| format!("{l:11}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_cranes() {
+        let run = |crane| {
+            let mut stacks: Stacks = "[A]\n[B]\n[C]\n[D]\n[E]\n 1   2 ".parse().unwrap();
+            stacks.apply(&Move::new(5, 1, 2), crane, 1).unwrap();
+            stacks.stacks()[1].iter().collect::<String>()
+        };
+        assert_eq!(run(Crane::Single), "ABCDE");
+        assert_eq!(run(Crane::Batch), "EDCBA");
+        assert_eq!(run(Crane::Capacity(2)), "BADCE");
+        assert_eq!(run(Crane::Capacity(5)), "EDCBA");
+    }
+
+    #[test]
+    fn test_underflow() {
+        let (mut stacks, _) = parse_input(&input()).unwrap();
+        let moves = [Move::new(1, 3, 1), Move::new(2, 3, 2)];
+        assert_eq!(
+            stacks.run(&moves, Crane::Batch),
+            Err(StackError::Underflow {
+                step: 2,
+                stack: 3,
+                have: 0,
+                need: 2
+            })
+        );
+        assert_eq!(stacks.tops(), "PD");
+        assert_eq!(
+            stacks.apply(&Move::new(1, 4, 1), Crane::Single, 3),
+            Err(StackError::NoSuchStack { step: 3, stack: 4 })
+        );
+    }
+
     #[test]
     fn test_proc1() {
-        assert_eq!(proc1(&INPUT.replace("_", " ")), "CMZ".to_owned());
+        assert_eq!(proc1(&input()), "CMZ".to_owned());
     }
 
     #[test]
@@ -164,7 +391,7 @@ move 1 from 1 to 2";
 
     #[test]
     fn test_proc2() {
-        assert_eq!(proc2(&INPUT.replace("_", " ")), "MCD".to_owned());
+        assert_eq!(proc2(&input()), "MCD".to_owned());
     }
 
     #[test]
@@ -17,7 +17,7 @@ fn proc1(input: &str) -> usize {
     proc(input, 4)
 }
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     let input = read_file("data/2022/input6.txt");
     proc1(&input)
 }
@@ -26,7 +26,7 @@ fn proc2(input: &str) -> usize {
     proc(input, 14)
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     let input = read_file("data/2022/input6.txt");
     proc2(&input)
 }
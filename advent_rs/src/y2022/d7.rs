@@ -1,52 +1,52 @@
-use std::borrow::BorrowMut;
-use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::collections::BTreeMap;
+use std::fmt;
 
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{alpha1, anychar, digit1, newline, not_line_ending, space1};
-use nom::multi::{separated_list0, separated_list1};
-use nom::sequence::separated_pair;
+use nom::character::complete::{digit1, newline, not_line_ending, space1};
+use nom::multi::{many0, separated_list1};
+use nom::sequence::{preceded, separated_pair};
 use nom::{IResult, Parser};
-use regex::Regex;
 
 use crate::read_file;
 
 const FILE_NAME: &str = "data/2022/input7.txt";
+const DISK_SIZE: usize = 70000000;
+const UPDATE_SIZE: usize = 30000000;
 
 #[derive(Debug)]
 enum Op<'a> {
-    Ls(Vec<File<'a>>),
+    Ls(Vec<Listing<'a>>),
     Cd(&'a str),
 }
 
 #[derive(Debug)]
-enum File<'a> {
+enum Listing<'a> {
     Dir(&'a str),
     File(&'a str, usize),
 }
 
-fn file(input: &str) -> IResult<&str, File<'_>> {
+fn file(input: &str) -> IResult<&str, Listing<'_>> {
     let (input, (size, name)) = separated_pair(digit1, space1, not_line_ending).parse(input)?;
-    Ok((input, File::File(name, size.parse::<usize>().unwrap())))
+    Ok((input, Listing::File(name, size.parse::<usize>().unwrap())))
 }
 
-fn dir(input: &str) -> IResult<&str, File<'_>> {
+fn dir(input: &str) -> IResult<&str, Listing<'_>> {
     let (input, _) = tag("dir ")(input)?;
     let (input, dir) = not_line_ending(input)?;
-    Ok((input, File::Dir(dir)))
+    Ok((input, Listing::Dir(dir)))
 }
 
 fn ls(input: &str) -> IResult<&str, Op<'_>> {
     let (input, _) = tag("$ ls")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, fs) = separated_list1(newline, alt((dir, file))).parse(input)?;
+    // an empty directory lists nothing
+    let (input, fs) = many0(preceded(newline, alt((dir, file)))).parse(input)?;
     Ok((input, Op::Ls(fs)))
 }
 
 fn cd(input: &str) -> IResult<&str, Op<'_>> {
     let (input, _) = tag("$ cd ")(input)?;
-    let (input, dir) = alt((tag(".."), alpha1, tag("/"))).parse(input)?;
+    let (input, dir) = not_line_ending(input)?;
     Ok((input, Op::Cd(dir)))
 }
 
@@ -54,69 +54,247 @@ fn commands(input: &str) -> IResult<&str, Vec<Op<'_>>> {
     separated_list1(newline, alt((ls, cd))).parse(input)
 }
 
-fn proc(input: &str) -> HashMap<PathBuf, usize> {
-    let (_, vvs) = commands(input).unwrap();
+#[derive(Debug, PartialEq, Eq)]
+pub enum FsError {
+    Parse(String),
+    NoSuchDir(String),
+    NotADir(String),
+    // the same path listed twice with a different kind or size
+    Conflict(String),
+}
 
-    let mut current = PathBuf::new();
-    let mut fs: HashMap<PathBuf, usize> = HashMap::new();
-    fs.insert(PathBuf::from("/"), 0);
+impl fmt::Display for FsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FsError::Parse(rest) => write!(f, "cannot parse: {rest}"),
+            FsError::NoSuchDir(path) => write!(f, "no such directory: {path}"),
+            FsError::NotADir(path) => write!(f, "not a directory: {path}"),
+            FsError::Conflict(path) => write!(f, "inconsistent listing for {path}"),
+        }
+    }
+}
 
-    for op in vvs {
-        match op {
-            Op::Ls(vs) => {
-                for file in vs {
-                    match file {
-                        File::Dir(dir) => {
-                            let mut sub = current.clone();
-                            sub.push(dir);
-                            fs.insert(sub, 0);
-                        }
-                        File::File(name, size) => {
-                            for (fb, vs) in fs.iter_mut() {
-                                if current.starts_with(fb) {
-                                    *vs += size;
-                                }
+#[derive(Debug)]
+enum Kind {
+    Dir(BTreeMap<String, usize>),
+    File(usize),
+}
+
+#[derive(Debug)]
+struct Node {
+    name: String,
+    parent: usize,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub path: String,
+    pub is_dir: bool,
+    pub size: usize,
+}
+
+// nodes[0] is the root, every other node points at its parent directory
+#[derive(Debug)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+}
+
+impl FileSystem {
+    pub fn new() -> Self {
+        FileSystem {
+            nodes: vec![Node {
+                name: String::new(),
+                parent: 0,
+                kind: Kind::Dir(BTreeMap::new()),
+            }],
+        }
+    }
+
+    pub fn from_transcript(input: &str) -> Result<Self, FsError> {
+        let (rest, ops) = commands(input).map_err(|e| FsError::Parse(e.to_string()))?;
+        if !rest.trim().is_empty() {
+            return Err(FsError::Parse(rest.lines().next().unwrap().to_string()));
+        }
+
+        let mut fs = FileSystem::new();
+        let mut current = 0;
+        for op in ops {
+            match op {
+                Op::Cd("/") => current = 0,
+                Op::Cd("..") => current = fs.nodes[current].parent,
+                Op::Cd(name) => current = fs.child_dir(current, name)?,
+                Op::Ls(listing) => {
+                    for item in listing {
+                        match item {
+                            Listing::Dir(name) => {
+                                fs.add(current, name, Kind::Dir(BTreeMap::new()))?
                             }
+                            Listing::File(name, size) => fs.add(current, name, Kind::File(size))?,
                         }
                     }
                 }
             }
-            Op::Cd(path) => {
-                if path == "..".to_owned() {
-                    current.pop();
-                } else if path == "/".to_owned() {
-                    current = PathBuf::from("/");
-                } else {
-                    current.push(path);
+        }
+        Ok(fs)
+    }
+
+    fn children(&self, id: usize) -> Option<&BTreeMap<String, usize>> {
+        match &self.nodes[id].kind {
+            Kind::Dir(children) => Some(children),
+            Kind::File(_) => None,
+        }
+    }
+
+    fn child_dir(&self, id: usize, name: &str) -> Result<usize, FsError> {
+        let path = self.join(id, name);
+        match self.children(id).unwrap().get(name) {
+            Some(&child) if self.children(child).is_some() => Ok(child),
+            Some(_) => Err(FsError::NotADir(path)),
+            None => Err(FsError::NoSuchDir(path)),
+        }
+    }
+
+    fn add(&mut self, parent: usize, name: &str, kind: Kind) -> Result<(), FsError> {
+        if let Some(&id) = self.children(parent).unwrap().get(name) {
+            return match (&self.nodes[id].kind, &kind) {
+                (Kind::Dir(_), Kind::Dir(_)) => Ok(()),
+                (Kind::File(s1), Kind::File(s2)) if s1 == s2 => Ok(()),
+                _ => Err(FsError::Conflict(self.join(parent, name))),
+            };
+        }
+
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            name: name.to_string(),
+            parent,
+            kind,
+        });
+        if let Kind::Dir(children) = &mut self.nodes[parent].kind {
+            children.insert(name.to_string(), id);
+        }
+        Ok(())
+    }
+
+    fn path(&self, id: usize) -> String {
+        if id == 0 {
+            "/".to_string()
+        } else {
+            self.join(self.nodes[id].parent, &self.nodes[id].name)
+        }
+    }
+
+    fn join(&self, id: usize, name: &str) -> String {
+        let parent = self.path(id);
+        if parent.ends_with('/') {
+            parent + name
+        } else {
+            parent + "/" + name
+        }
+    }
+
+    fn lookup(&self, path: &str) -> Option<usize> {
+        path.split('/')
+            .filter(|s| !s.is_empty())
+            .try_fold(0, |id, name| self.children(id)?.get(name).copied())
+    }
+
+    // total size of every node, children always come after their parent
+    fn sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.nodes.len()];
+        for id in (0..self.nodes.len()).rev() {
+            if let Kind::File(size) = self.nodes[id].kind {
+                sizes[id] = size;
+            }
+            if id > 0 {
+                sizes[self.nodes[id].parent] += sizes[id];
+            }
+        }
+        sizes
+    }
+
+    pub fn du(&self, path: &str) -> Option<usize> {
+        self.lookup(path).map(|id| self.sizes()[id])
+    }
+
+    pub fn find<F: Fn(&Entry) -> bool>(&self, pred: F) -> Vec<Entry> {
+        let sizes = self.sizes();
+        (0..self.nodes.len())
+            .map(|id| Entry {
+                path: self.path(id),
+                is_dir: self.children(id).is_some(),
+                size: sizes[id],
+            })
+            .filter(|e| pred(e))
+            .collect()
+    }
+
+    pub fn smallest_dir_freeing(&self, bytes: usize) -> Option<Entry> {
+        self.find(|e| e.is_dir && e.size >= bytes)
+            .into_iter()
+            .min_by_key(|e| e.size)
+    }
+
+    // bytes still to free so that `needed` fit on a disk of `capacity`,
+    // 0 when they already do
+    pub fn space_to_free(&self, capacity: usize, needed: usize) -> usize {
+        (self.sizes()[0] + needed).saturating_sub(capacity)
+    }
+
+    pub fn tree(&self) -> String {
+        let mut out = String::new();
+        self.render(0, 0, &mut out);
+        out
+    }
+
+    fn render(&self, id: usize, depth: usize, out: &mut String) {
+        let name = if id == 0 { "/" } else { &self.nodes[id].name };
+        let indent = "  ".repeat(depth);
+        match &self.nodes[id].kind {
+            Kind::File(size) => out.push_str(&format!("{indent}- {name} (file, size={size})\n")),
+            Kind::Dir(children) => {
+                out.push_str(&format!("{indent}- {name} (dir)\n"));
+                for &child in children.values() {
+                    self.render(child, depth + 1, out);
                 }
             }
         }
     }
-    fs
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        FileSystem::new()
+    }
 }
 
 fn proc1(lines: &str) -> usize {
-    proc(lines)
+    FileSystem::from_transcript(lines)
+        .unwrap()
+        .find(|e| e.is_dir && e.size <= 100000)
         .iter()
-        .map(|(_, &s)| s)
-        .filter(|&s| s < 100000)
-        .sum::<usize>()
+        .map(|e| e.size)
+        .sum()
 }
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     let input = read_file(FILE_NAME);
     proc1(&input)
 }
 
 fn proc2(lines: &str) -> usize {
-    let fs = proc(lines);
-    let total = fs.get(Path::new("/")).unwrap();
-    let unused = 70000000 - total;
-    let delete = 30000000 - unused;
-    fs.into_values().filter(|s| *s > delete).min().unwrap()
+    let fs = FileSystem::from_transcript(lines).unwrap();
+    match fs.space_to_free(DISK_SIZE, UPDATE_SIZE) {
+        0 => 0,
+        bytes => {
+            fs.smallest_dir_freeing(bytes)
+                .expect("the update does not fit even on an empty disk")
+                .size
+        }
+    }
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     let input = read_file(FILE_NAME);
     proc2(&input)
 }
@@ -149,6 +327,95 @@ $ ls
 5626152 d.ext
 7214296 k";
 
+    #[test]
+    fn test_tree() {
+        let fs = FileSystem::from_transcript(INPUT).unwrap();
+        assert_eq!(
+            fs.tree(),
+            "- / (dir)
+  - a (dir)
+    - e (dir)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)
+"
+        );
+    }
+
+    #[test]
+    fn test_queries() {
+        let fs = FileSystem::from_transcript(INPUT).unwrap();
+        assert_eq!(fs.du("/"), Some(48381165));
+        assert_eq!(fs.du("/a"), Some(94853));
+        assert_eq!(fs.du("/a/e/"), Some(584));
+        assert_eq!(fs.du("/d/k"), Some(7214296));
+        assert_eq!(fs.du("/x"), None);
+
+        let logs = fs.find(|e| !e.is_dir && e.path.starts_with("/d/d."));
+        let paths = logs.iter().map(|e| e.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["/d/d.log", "/d/d.ext"]);
+
+        let e = fs.smallest_dir_freeing(100000).unwrap();
+        assert_eq!((e.path.as_str(), e.size), ("/d", 24933642));
+        assert_eq!(fs.smallest_dir_freeing(48381166), None);
+    }
+
+    #[test]
+    fn test_validation() {
+        let err = |s: &str| FileSystem::from_transcript(s).unwrap_err();
+        assert_eq!(err("$ cd /\n$ cd x"), FsError::NoSuchDir("/x".to_string()));
+        assert_eq!(
+            err("$ ls\n12 a\n$ cd a"),
+            FsError::NotADir("/a".to_string())
+        );
+        assert_eq!(
+            err("$ ls\ndir a\n$ cd a\n$ ls\n1 b\n$ cd ..\n$ cd a\n$ ls\n2 b"),
+            FsError::Conflict("/a/b".to_string())
+        );
+        assert_eq!(
+            err("$ ls\ndir a\n10 a"),
+            FsError::Conflict("/a".to_string())
+        );
+        assert!(matches!(err("$ rm -rf /"), FsError::Parse(_)));
+
+        // listing the same directory twice is fine
+        let fs = FileSystem::from_transcript("$ ls\n1 a\n$ ls\n1 a").unwrap();
+        assert_eq!(fs.du("/"), Some(1));
+    }
+
+    #[test]
+    fn test_empty_listing() {
+        let fs = FileSystem::from_transcript("$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n$ cd ..\n$ ls")
+            .unwrap();
+        assert_eq!(fs.du("/a"), Some(0));
+        assert_eq!(fs.tree(), "- / (dir)\n  - a (dir)\n");
+        assert_eq!(
+            FileSystem::from_transcript("$ ls").unwrap().du("/"),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_space_to_free() {
+        let fs = FileSystem::from_transcript(INPUT).unwrap();
+        assert_eq!(fs.space_to_free(DISK_SIZE, UPDATE_SIZE), 8381165);
+        assert_eq!(fs.space_to_free(DISK_SIZE, 1000), 0);
+        // more in use than the disk holds
+        assert_eq!(fs.space_to_free(40000000, UPDATE_SIZE), 38381165);
+
+        let small = FileSystem::from_transcript("$ ls\n100 a").unwrap();
+        assert_eq!(proc2("$ ls\n100 a"), 0);
+        assert_eq!(small.space_to_free(DISK_SIZE, DISK_SIZE), 100);
+    }
+
     #[test]
     fn test_proc1() {
         assert_eq!(proc1(INPUT), 95437);
@@ -59,7 +59,7 @@ fn proc1(input: &str) -> usize {
     count
 }
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     let input = read_file(FILE_NAME);
     proc1(&input)
 }
@@ -147,7 +147,7 @@ fn proc2(input: &str) -> u32 {
     max
 }
 
-fn quiz2() -> u32 {
+pub(crate) fn quiz2() -> u32 {
     let input = read_file(FILE_NAME);
     proc2(&input)
 }
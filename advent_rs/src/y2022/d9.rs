@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::debugger::{Debugger, Simulation};
 use crate::read_file;
 
 const FILE_NAME: &str = "data/2022/input9.txt";
@@ -75,35 +76,123 @@ fn tail_follow_move(head_tail_diff: Pos, head_move: Pos) -> Pos {
     }
 }
 
-fn trace(path: Vec<Pos>, snake: &mut Vec<Pos>) -> usize {
+// moves the head one square and lets the knots behind it follow
+fn pull(snake: &mut [Pos], (mut mx, mut my): Pos) {
     let length = snake.len();
+    for i in 1..length {
+        let (dx, dy) = (snake[i - 1].0 - snake[i].0, snake[i - 1].1 - snake[i].1);
+
+        snake[i - 1].0 += mx;
+        snake[i - 1].1 += my;
+
+        (mx, my) = tail_follow_move((dx, dy), (mx, my));
+
+        if mx == 0 && my == 0 {
+            break;
+        } else if i == length - 1 {
+            snake[i].0 += mx;
+            snake[i].1 += my;
+        }
+    }
+}
 
+fn trace(path: Vec<Pos>, snake: &mut Vec<Pos>) -> usize {
     let mut ts = HashSet::new();
     ts.insert((0, 0));
 
-    for (mut mx, mut my) in path {
-        println!("{:?} {:?}", &snake, (mx, my));
-        for i in 1..snake.len() {
-            let (dx, dy) = (snake[i - 1].0 - snake[i].0, snake[i - 1].1 - snake[i].1);
+    for m in path {
+        pull(snake, m);
+        ts.insert(snake[snake.len() - 1]);
+    }
 
-            snake[i - 1].0 += mx;
-            snake[i - 1].1 += my;
+    ts.len()
+}
 
-            (mx, my) = tail_follow_move((dx, dy), (mx, my));
+// the rope one head move at a time, with the squares the tail has visited
+// in the order it first reached them
+struct Rope {
+    path: Vec<Pos>,
+    knots: Vec<Pos>,
+    visited: HashSet<Pos>,
+    visits: Vec<Pos>,
+    moves: usize,
+}
 
-            if mx == 0 && my == 0 {
-                break;
-            } else if i == length - 1 {
-                snake[i].0 += mx;
-                snake[i].1 += my;
-                ts.insert((snake[i].0, snake[i].1));
-            }
+impl Rope {
+    fn new(path: Vec<Pos>, length: usize) -> Self {
+        Rope {
+            path,
+            knots: vec![(0, 0); length],
+            visited: HashSet::from([(0, 0)]),
+            visits: vec![(0, 0)],
+            moves: 0,
         }
     }
+}
 
-    println!("{:?}", &snake);
+impl Simulation for Rope {
+    // the debugger only rewinds, so a snapshot keeps how many squares the
+    // tail had visited and restoring forgets the ones reached since
+    type State = (Vec<Pos>, usize, usize);
+    type Key = usize;
 
-    ts.iter().count()
+    fn state(&self) -> Self::State {
+        (self.knots.clone(), self.visits.len(), self.moves)
+    }
+
+    fn restore(&mut self, (knots, visits, moves): Self::State) {
+        assert!(visits <= self.visits.len(), "cannot restore a later state");
+        for p in self.visits.drain(visits..) {
+            self.visited.remove(&p);
+        }
+        self.knots = knots;
+        self.moves = moves;
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(m) = self.path.get(self.moves) else {
+            return false;
+        };
+        pull(&mut self.knots, *m);
+        let tail = self.knots[self.knots.len() - 1];
+        if self.visited.insert(tail) {
+            self.visits.push(tail);
+        }
+        self.moves += 1;
+        true
+    }
+
+    // a 41x21 window around the head, y pointing up
+    fn render(&self) -> String {
+        let (hx, hy) = self.knots[0];
+        let mut rows = vec![];
+        for y in (hy - 10..=hy + 10).rev() {
+            let row = (hx - 20..=hx + 20)
+                .map(|x| match self.knots.iter().position(|k| *k == (x, y)) {
+                    Some(0) => 'H',
+                    Some(_) if self.knots.len() == 2 => 'T',
+                    Some(i) => char::from_digit(i as u32, 36).unwrap(),
+                    None if (x, y) == (0, 0) => 's',
+                    None if self.visited.contains(&(x, y)) => '#',
+                    None => '.',
+                })
+                .collect::<String>();
+            rows.push(row);
+        }
+        rows.push(format!(
+            "move {}/{}, tail visited {}",
+            self.moves,
+            self.path.len(),
+            self.visited.len()
+        ));
+        rows.join("\n")
+    }
+
+    // the head follows a finite path, so the rope never repeats a state
+    // with the same moves ahead of it and there is no cycle to find
+    fn key(&self) -> usize {
+        self.moves
+    }
 }
 
 fn proc1(input: &str) -> usize {
@@ -112,7 +201,7 @@ fn proc1(input: &str) -> usize {
     trace(path, &mut snake)
 }
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     let input = read_file(FILE_NAME);
     proc1(&input)
 }
@@ -123,11 +212,16 @@ fn proc2(input: &str) -> usize {
     trace(path, &mut snake)
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     let input = read_file(FILE_NAME);
     proc2(&input)
 }
 
+pub(crate) fn debug(input: Option<&str>) {
+    let input = input.map_or_else(|| read_file(FILE_NAME), str::to_string);
+    Debugger::new(Rope::new(load(&input), 10)).run_terminal();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,6 +260,31 @@ U 20";
         assert_eq!(proc2(INPUT2), 36);
     }
 
+    #[test]
+    fn test_rope() {
+        let mut dbg = Debugger::new(Rope::new(load(INPUT2), 10));
+        assert_eq!(dbg.run(5), 5);
+        let view = dbg.sim().render();
+        // the head is at (5, 0), the last five knots are still on the start
+        assert_eq!(&view.lines().nth(10).unwrap()[14..23], ".54321H..");
+
+        assert!(dbg.finish(usize::MAX));
+        assert_eq!(dbg.sim().visited.len(), 36);
+        assert_eq!(dbg.cycle(), None);
+        assert!(dbg.goto(5));
+        assert_eq!(dbg.sim().render(), view);
+        assert_eq!(dbg.sim().visited.len(), dbg.sim().visits.len());
+
+        // squares reached after the snapshot are forgotten on a rewind
+        let mut dbg = Debugger::with_interval(Rope::new(load(INPUT), 2), 4);
+        assert!(dbg.goto(15));
+        let visited = dbg.sim().visited.clone();
+        assert!(dbg.finish(usize::MAX));
+        assert_eq!(dbg.sim().visited.len(), 13);
+        assert!(dbg.goto(15));
+        assert_eq!(dbg.sim().visited, visited);
+    }
+
     #[test]
     fn test_quiz2() {
         assert_eq!(quiz2(), 2630);
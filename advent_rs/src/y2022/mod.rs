@@ -15,3 +15,52 @@ pub mod d6;
 pub mod d7;
 pub mod d8;
 pub mod d9;
+
+use crate::debugger::Target;
+use crate::runner::Solver;
+
+pub(crate) fn solvers() -> Vec<Solver> {
+    vec![
+        Solver::new(2022, 1, 1, Some("70509"), || d1::quiz1().into()),
+        Solver::new(2022, 1, 2, Some("208567"), || d1::quiz2().into()),
+        Solver::new(2022, 2, 1, Some("10310"), || d2::quiz1().into()),
+        Solver::new(2022, 2, 2, Some("14859"), || d2::quiz2().into()),
+        Solver::new(2022, 3, 1, Some("7850"), || d3::quiz1().into()),
+        Solver::new(2022, 3, 2, Some("2581"), || d3::quiz2().into()),
+        Solver::new(2022, 4, 1, Some("464"), || d4::quiz1().into()),
+        Solver::new(2022, 4, 2, Some("770"), || d4::quiz2().into()),
+        Solver::new(2022, 5, 1, Some("SVFDLGLWV"), || d5::quiz1().into()),
+        Solver::new(2022, 5, 2, Some("DCVTCVPCL"), || d5::quiz2().into()),
+        Solver::new(2022, 6, 1, Some("1262"), || d6::quiz1().into()),
+        Solver::new(2022, 6, 2, Some("3444"), || d6::quiz2().into()),
+        Solver::new(2022, 7, 1, Some("1723892"), || d7::quiz1().into()),
+        Solver::new(2022, 7, 2, Some("8474158"), || d7::quiz2().into()),
+        Solver::new(2022, 8, 1, Some("1782"), || d8::quiz1().into()),
+        Solver::new(2022, 8, 2, Some("474606"), || d8::quiz2().into()),
+        Solver::new(2022, 9, 1, Some("6243"), || d9::quiz1().into()),
+        Solver::new(2022, 9, 2, Some("2630"), || d9::quiz2().into()),
+        Solver::new(2022, 10, 1, Some("16060"), || d10::quiz1().into()),
+        Solver::new(2022, 10, 2, Some("###...##...##..####.#..#.#....#..#.####.\n#..#.#..#.#..#.#....#.#..#....#..#.#....\n###..#..#.#....###..##...#....####.###..\n#..#.####.#....#....#.#..#....#..#.#....\n#..#.#..#.#..#.#....#.#..#....#..#.#....\n###..#..#..##..####.#..#.####.#..#.#...."), || d10::quiz2().into()),
+        Solver::new(2022, 11, 1, Some("316888"), || d11::quiz1().into()),
+        Solver::new(2022, 11, 2, Some("35270398814"), || d11::quiz2().into()),
+        Solver::new(2022, 12, 1, Some("339"), || d12::quiz1().into()),
+        Solver::new(2022, 12, 2, Some("332"), || d12::quiz2().into()),
+        Solver::new(2022, 13, 1, Some("5882"), || d13::quiz1().into()),
+        Solver::new(2022, 13, 2, Some("24948"), || d13::quiz2().into()),
+        Solver::new(2022, 14, 1, Some("843"), || d14::quiz1().into()),
+        Solver::new(2022, 14, 2, Some("27625"), || d14::quiz2().into()),
+        Solver::new(2022, 15, 1, Some("5461729"), || d15::quiz1().into()),
+        Solver::new(2022, 15, 2, Some("10621647166538"), || d15::quiz2().into()),
+        Solver::new(2022, 16, 1, Some("2253"), || d16::quiz1().into()),
+        Solver::new(2022, 16, 2, None, || d16::quiz2().into()),
+        Solver::new(2022, 17, 1, Some("3067"), || d17::quiz1().into()),
+        Solver::new(2022, 17, 2, None, || d17::quiz2().into()),
+    ]
+}
+
+pub(crate) fn debug_targets() -> Vec<Target> {
+    vec![
+        Target::new(2022, 9, d9::debug),
+        Target::new(2022, 14, d14::debug),
+    ]
+}
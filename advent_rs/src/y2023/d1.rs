@@ -10,11 +10,11 @@ const DIGITS: [&str; 9] = [
     "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
 ];
 
-fn quiz1() -> u32 {
+pub(crate) fn quiz1() -> u32 {
     cal_values(&QUIZ_INPUT, to_ns1)
 }
 
-fn quiz2() -> u32 {
+pub(crate) fn quiz2() -> u32 {
     cal_values(&QUIZ_INPUT, to_ns2)
 }
 
@@ -1,3 +1,7 @@
+use crate::dashboard::save_visual;
+use crate::heatmap::Heatmap;
+use crate::polygon::{Location, Polygon};
+use crate::trace;
 use std::collections::{HashMap, HashSet, LinkedList};
 use std::iter::FromIterator;
 
@@ -157,9 +161,35 @@ fn distance_map(map: &Map) -> HashMap<Position, usize> {
     visited
 }
 
+// steps from the start along the shorter way round the loop, the start
+// itself holds the loop length
+fn distance_heatmap(map: &Map, visited: &HashMap<Position, usize>) -> Heatmap {
+    let (rows, cols) = map.size;
+    let length = visited[&map.start];
+    let values = (0..rows * cols)
+        .map(|i| {
+            visited
+                .get(&(i / cols, i % cols))
+                .map(|&d| d.min(length - d) as f64)
+        })
+        .collect();
+    Heatmap::new(values, cols).start(map.start.0 * cols + map.start.1)
+}
+
+fn draw_map(map: &Map, visited: &HashMap<Position, usize>) {
+    let heatmap = distance_heatmap(map, visited);
+    trace!("\n{}", heatmap.render_terminal());
+    save_visual(2023, 10, "distance.svg", heatmap.to_svg(4).as_bytes());
+}
+
+pub(crate) fn quiz1() -> usize {
+    solve1(INPUT)
+}
+
 fn solve1(data: &str) -> usize {
     let map = load(data);
     let visited = distance_map(&map);
+    draw_map(&map, &visited);
     visited.get(&map.start).unwrap() / 2
 }
 
@@ -193,156 +223,29 @@ fn boundary(map: &Map, visited: HashMap<Position, usize>) -> (HashSet<Position>,
     (cleaned, path)
 }
 
-fn enclosed(map: &Map, boundary: &HashSet<Position>, path: &Vec<Position>) -> HashSet<Position> {
-    // let filled: Vec<u8> = vec![0; map.size.0 * map.size.1];
-
-    let mut filled = HashSet::new();
-
-    for i in 1..path.len() {
-        let p = path[i - 1];
-
-        // FIXME find ccw / cw
-        let diffs = diffs_counter_clock(path[i - 1], map.tiles[p.0][p.1], path[i]);
-
-        for diff in diffs {
-            let mut r = p.0 as i32;
-            let mut c = p.1 as i32;
-
-            loop {
-                r += diff.0;
-                c += diff.1;
-
-                if r <= 0
-                    || r >= map.size.0 as i32 - 1
-                    || c <= 0
-                    || c >= map.size.1 as i32 - 1
-                    || boundary.contains(&(r as usize, c as usize))
-                {
-                    break;
-                } else {
-                    filled.insert((r as usize, c as usize));
-                }
-            }
-        }
-    }
-
-    filled
+fn loop_polygon(path: &[Position]) -> Polygon {
+    Polygon::new(path.iter().map(|&(r, c)| (r as i64, c as i64)).collect())
 }
 
-fn diffs_clock(
-    (from_r, from_c): Position,
-    from_tile: Tile,
-    (to_r, to_c): Position,
-) -> Vec<(i32, i32)> {
-    if from_r == to_r {
-        if from_c + 1 == to_c {
-            // move east
-            if from_tile == Tile::NE {
-                vec![(1, 0), (0, -1)]
-            } else if from_tile == Tile::HO {
-                vec![(1, 0)]
-            } else {
-                vec![]
-            }
-        } else if from_c - 1 == to_c {
-            // move west
-            if from_tile == Tile::SW {
-                vec![(-1, 0), (0, 1)]
-            } else if from_tile == Tile::HO {
-                vec![(-1, 0)]
-            } else {
-                vec![]
-            }
-        } else {
-            panic!();
-        }
-    } else if from_c == to_c {
-        if from_r + 1 == to_r {
-            // move south
-            if from_tile == Tile::SE {
-                vec![(0, -1), (-1, 0)]
-            } else if from_tile == Tile::VR {
-                vec![(0, -1)]
-            } else {
-                vec![]
-            }
-        } else if from_r - 1 == to_r {
-            // move north
-            if from_tile == Tile::NW {
-                vec![(0, 1), (1, 0)]
-            } else if from_tile == Tile::VR {
-                vec![(0, 1)]
-            } else {
-                vec![]
-            }
-        } else {
-            panic!();
-        }
-    } else {
-        panic!();
-    }
+fn enclosed(map: &Map, boundary: &HashSet<Position>, path: &[Position]) -> HashSet<Position> {
+    let polygon = loop_polygon(path);
+
+    (0..map.size.0)
+        .flat_map(|r| (0..map.size.1).map(move |c| (r, c)))
+        .filter(|p| !boundary.contains(p))
+        .filter(|&(r, c)| polygon.locate((r as i64, c as i64)) == Location::Inside)
+        .collect()
 }
 
-fn diffs_counter_clock(
-    (from_r, from_c): Position,
-    from_tile: Tile,
-    (to_r, to_c): Position,
-) -> Vec<(i32, i32)> {
-    if from_r == to_r {
-        if from_c + 1 == to_c {
-            // move east
-            if from_tile == Tile::SE {
-                vec![(-1, 0), (0, -1)]
-            } else if from_tile == Tile::HO {
-                vec![(-1, 0)]
-            } else {
-                vec![]
-            }
-        } else if from_c - 1 == to_c {
-            // move west
-            if from_tile == Tile::NW {
-                vec![(1, 0), (0, 1)]
-            } else if from_tile == Tile::HO {
-                vec![(1, 0)]
-            } else {
-                vec![]
-            }
-        } else {
-            panic!();
-        }
-    } else if from_c == to_c {
-        if from_r + 1 == to_r {
-            // move south
-            if from_tile == Tile::SW {
-                vec![(0, 1), (-1, 0)]
-            } else if from_tile == Tile::VR {
-                vec![(0, 1)]
-            } else {
-                vec![]
-            }
-        } else if from_r - 1 == to_r {
-            // move north
-            if from_tile == Tile::NE {
-                vec![(0, -1), (1, 0)]
-            } else if from_tile == Tile::VR {
-                vec![(0, -1)]
-            } else {
-                vec![]
-            }
-        } else {
-            panic!();
-        }
-    } else {
-        panic!();
-    }
+pub(crate) fn quiz2() -> usize {
+    solve2(INPUT)
 }
 
 fn solve2(data: &str) -> usize {
     let map = load(data);
     let visited = distance_map(&map);
-    let (boundary, path) = boundary(&map, visited);
-    let inner = enclosed(&map, &boundary, &path);
-    inner.len()
+    let (_, path) = boundary(&map, visited);
+    loop_polygon(&path).interior_points() as usize
 }
 
 fn print_boundary((rows, cols): (usize, usize), boundary: &HashSet<Position>) {
@@ -406,6 +309,15 @@ LJ...";
         )
     }
 
+    #[test]
+    fn test_distance_heatmap() {
+        let map = load(EXAMPLE3);
+        let heatmap = distance_heatmap(&map, &distance_map(&map));
+        assert_eq!(heatmap.size(), (5, 5));
+        assert_eq!(heatmap.bounds(), Some((0.0, 8.0)));
+        assert_eq!(heatmap.render_plain().lines().nth(2), Some("S████"));
+    }
+
     #[test]
     fn test_solve1() {
         assert_eq!(solve1(EXAMPLE2), 4);
@@ -414,7 +326,7 @@ LJ...";
 
     #[test]
     fn test_quiz1() {
-        assert_eq!(solve1(INPUT), 6820);
+        assert_eq!(quiz1(), 6820);
     }
 
     #[test]
@@ -493,6 +405,6 @@ L7JLJL-JLJLJL--JLJ.L";
 
     #[test]
     fn test_quiz2() {
-        assert_eq!(solve2(INPUT), 337);
+        assert_eq!(quiz2(), 337);
     }
 }
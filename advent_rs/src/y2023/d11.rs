@@ -92,12 +92,20 @@ impl Image {
     }
 }
 
+pub(crate) fn quiz1() -> u64 {
+    solve1(INPUT)
+}
+
 fn solve1(data: &str) -> u64 {
     let mut image = Image::from(data);
     image.expand(1);
     image.sum_of_shortest_path()
 }
 
+pub(crate) fn quiz2() -> u64 {
+    solve2(INPUT, 1000000 - 1)
+}
+
 fn solve2(data: &str, expansion: usize) -> u64 {
     let mut image = Image::from(data);
     image.expand(expansion);
@@ -126,7 +134,7 @@ mod tests {
 
     #[test]
     fn test_quiz1() {
-        assert_eq!(solve1(INPUT), 10228230);
+        assert_eq!(quiz1(), 10228230);
     }
 
     #[test]
@@ -138,7 +146,7 @@ mod tests {
 
     #[test]
     fn test_quiz2() {
-        assert_eq!(solve2(INPUT, 1000000 - 1), 447073334102);
+        assert_eq!(quiz2(), 447073334102);
     }
 
     #[test]
@@ -68,6 +68,10 @@ fn find_reflect(series: Vec<usize>) -> Option<usize> {
     None
 }
 
+pub(crate) fn quiz1() -> usize {
+    solve1(INPUT)
+}
+
 fn solve1(input: &str) -> usize {
     load(input)
         .into_iter()
@@ -137,6 +141,10 @@ fn check_bit(bits: usize) -> bool {
     bits != 0 && (bits & (bits - 1)) == 0
 }
 
+pub(crate) fn quiz2() -> usize {
+    solve2(INPUT)
+}
+
 fn solve2(input: &str) -> usize {
     load(input)
         .into_iter()
@@ -241,7 +249,7 @@ mod tests {
 
     #[test]
     fn test_quiz1() {
-        assert_eq!(solve1(INPUT), 35360);
+        assert_eq!(quiz1(), 35360);
     }
 
     #[test]
@@ -251,7 +259,7 @@ mod tests {
 
     #[test]
     fn test_quiz2() {
-        assert_eq!(solve2(INPUT), 36755);
+        assert_eq!(quiz2(), 36755);
     }
 
     #[test]
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::debug;
+
 const INPUT: &str = include_str!("../../data/2023/input14.txt");
 
 struct Block {
@@ -148,6 +150,10 @@ fn reverse_tilted2(rounded: &Vec<usize>, sharp: &Vec<usize>, length: usize) -> V
     tilted.iter().map(|p| length - p - 1).rev().collect()
 }
 
+pub(crate) fn quiz1() -> usize {
+    solve1(INPUT)
+}
+
 fn solve1(data: &str) -> usize {
     let (block, rounded_columns) = load(data);
     north_load(
@@ -192,31 +198,40 @@ fn solve2(data: &str, count: usize) -> usize {
 
     let result = north_load(&platform, block.row_size);
 
-    print(
-        &transpose(platform, block.row_size),
-        &block.sharp_rows,
-        block.col_size,
+    debug!(
+        "\n{}",
+        render(
+            &transpose(platform, block.row_size),
+            &block.sharp_rows,
+            block.col_size,
+        )
     );
 
     result
 }
 
-fn print(platform: &Vec<Vec<usize>>, sharp_rows: &HashMap<usize, Vec<usize>>, length: usize) {
+fn render(
+    platform: &[Vec<usize>],
+    sharp_rows: &HashMap<usize, Vec<usize>>,
+    length: usize,
+) -> String {
+    let mut text = String::new();
     platform.iter().enumerate().for_each(|(r, row)| {
         let vec1 = vec![];
         let sharps = sharp_rows.get(&r).unwrap_or(&vec1);
 
         (0..length).for_each(|c| {
             if row.contains(&c) {
-                print!("O");
+                text.push('O');
             } else if sharps.contains(&c) {
-                print!("#");
+                text.push('#');
             } else {
-                print!(".");
+                text.push('.');
             }
         });
-        println!();
-    })
+        text.push('\n');
+    });
+    text
 }
 
 fn tilt2_all(
@@ -267,7 +282,7 @@ O.#..O.#.#
 
     #[test]
     fn test_quiz1() {
-        assert_eq!(solve1(INPUT), 105461);
+        assert_eq!(quiz1(), 105461);
     }
 
     #[test]
@@ -9,6 +9,10 @@ fn hash(s: &str) -> u32 {
     s.chars().fold(0, |acc, c| (acc + c as u32) * 17 % 256)
 }
 
+pub(crate) fn quiz1() -> u32 {
+    solve1(INPUT)
+}
+
 fn solve1(data: &str) -> u32 {
     data.lines()
         .next()
@@ -36,6 +40,10 @@ fn lens(s: &str) -> Op<'_> {
     }
 }
 
+pub(crate) fn quiz2() -> u32 {
+    solve2(INPUT)
+}
+
 fn solve2(data: &str) -> u32 {
     let mut boxes: Vec<Vec<(&str, u32)>> = vec![Vec::new(); 256];
 
@@ -100,7 +108,7 @@ mod tests {
 
     #[test]
     fn test_quiz1() {
-        assert_eq!(solve1(INPUT), 516469);
+        assert_eq!(quiz1(), 516469);
     }
 
     #[test]
@@ -110,6 +118,6 @@ mod tests {
 
     #[test]
     fn test_quiz2() {
-        assert_eq!(solve2(INPUT), 221627);
+        assert_eq!(quiz2(), 221627);
     }
 }
@@ -1,5 +1,7 @@
 use std::collections::{HashSet, VecDeque};
 
+use crate::debugger::{Debugger, Simulation};
+
 const INPUT: &str = include_str!("../../data/2023/input16.txt");
 
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
@@ -66,22 +68,87 @@ fn next(map: &Vec<Vec<char>>, (dir, row, col): &Beam) -> Vec<Beam> {
         .collect()
 }
 
-fn energize(map: &Vec<Vec<char>>, start: Beam) -> HashSet<Beam> {
-    let mut energized: HashSet<Beam> = HashSet::new();
-    let mut queue: VecDeque<Beam> = VecDeque::new();
+// the breadth-first walk of `energize`, one beam at a time
+struct BeamWalk<'a> {
+    map: &'a Vec<Vec<char>>,
+    queue: VecDeque<Beam>,
+    energized: HashSet<Beam>,
+}
 
-    queue.push_back(start);
+impl<'a> BeamWalk<'a> {
+    fn new(map: &'a Vec<Vec<char>>, start: Beam) -> Self {
+        BeamWalk {
+            map,
+            queue: VecDeque::from([start]),
+            energized: HashSet::new(),
+        }
+    }
+}
+
+impl Simulation for BeamWalk<'_> {
+    type State = (VecDeque<Beam>, HashSet<Beam>);
+    type Key = usize;
+
+    fn state(&self) -> Self::State {
+        (self.queue.clone(), self.energized.clone())
+    }
 
-    while let Some(v) = queue.pop_front() {
-        if energized.contains(&v) {
-            continue;
+    fn restore(&mut self, (queue, energized): Self::State) {
+        self.queue = queue;
+        self.energized = energized;
+    }
+
+    fn step(&mut self) -> bool {
+        while let Some(v) = self.queue.pop_front() {
+            if self.energized.insert(v) {
+                self.queue.extend(next(self.map, &v));
+                return true;
+            }
         }
+        false
+    }
 
-        energized.insert(v);
-        next(map, &v).into_iter().for_each(|b| queue.push_back(b));
+    // energized tiles as '#', the beams waiting in the queue as arrows
+    fn render(&self) -> String {
+        let mut grid = self.map.clone();
+        for (_, row, col) in self.energized.iter() {
+            if grid[*row][*col] == '.' {
+                grid[*row][*col] = '#';
+            }
+        }
+        for (dir, row, col) in self.queue.iter() {
+            grid[*row][*col] = match dir {
+                Dir::North => '^',
+                Dir::East => '>',
+                Dir::South => 'v',
+                Dir::West => '<',
+            };
+        }
+        grid.iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
-    energized
+    // every step adds a beam, so the walk never repeats itself
+    fn key(&self) -> usize {
+        self.energized.len()
+    }
+}
+
+fn energize(map: &Vec<Vec<char>>, start: Beam) -> HashSet<Beam> {
+    let mut walk = BeamWalk::new(map, start);
+    while walk.step() {}
+    walk.energized
+}
+
+pub(crate) fn debug(input: Option<&str>) {
+    let map = load(input.unwrap_or(INPUT));
+    Debugger::new(BeamWalk::new(&map, (Dir::East, 0, 0))).run_terminal();
+}
+
+pub(crate) fn quiz1() -> usize {
+    solve1(INPUT)
 }
 
 fn solve1(data: &str) -> usize {
@@ -105,6 +172,10 @@ fn load(data: &str) -> Vec<Vec<char>> {
         .collect()
 }
 
+pub(crate) fn quiz2() -> usize {
+    solve2(INPUT)
+}
+
 fn solve2(data: &str) -> usize {
     let map: Vec<Vec<char>> = load(data);
 
@@ -202,6 +273,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_beam_walk() {
+        let map = load(EXAMPLE);
+        let mut dbg = Debugger::new(BeamWalk::new(&map, (Dir::East, 0, 0)));
+        assert_eq!(dbg.run(2), 2);
+        let view = dbg.sim().render();
+        assert_eq!(
+            view.lines().take(2).collect::<Vec<_>>(),
+            ["#|...\\....", "|v-.\\....."]
+        );
+
+        assert!(dbg.finish(usize::MAX));
+        assert_eq!(tiles(dbg.sim().energized.clone()).len(), 46);
+        assert!(dbg.goto(2));
+        assert_eq!(dbg.sim().render(), view);
+    }
+
     #[test]
     fn test_solve1() {
         assert_eq!(solve1(EXAMPLE), 46);
@@ -209,7 +297,7 @@ mod tests {
 
     #[test]
     fn test_quiz1() {
-        assert_eq!(solve1(INPUT), 7307);
+        assert_eq!(quiz1(), 7307);
     }
 
     #[test]
@@ -219,6 +307,6 @@ mod tests {
 
     #[test]
     fn test_quiz2() {
-        assert_eq!(solve2(INPUT), 7635);
+        assert_eq!(quiz2(), 7635);
     }
 }
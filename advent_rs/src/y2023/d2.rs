@@ -17,11 +17,11 @@ use std::str::FromStr;
 
 const QUIZ_INPUT: &str = include_str!("../../data/2023/input2.txt");
 
-fn quiz1() -> u32 {
+pub(crate) fn quiz1() -> u32 {
     solve1(QUIZ_INPUT, (12, 13, 14))
 }
 
-fn quiz2() -> u32 {
+pub(crate) fn quiz2() -> u32 {
     solve2(QUIZ_INPUT)
 }
 
@@ -3,11 +3,11 @@ use std::collections::HashSet;
 
 const INPUT: &str = include_str!("../../data/2023/input3.txt");
 
-fn quiz1() -> u32 {
+pub(crate) fn quiz1() -> u32 {
     solve1(INPUT)
 }
 
-fn quiz2() -> u32 {
+pub(crate) fn quiz2() -> u32 {
     solve2(INPUT)
 }
 
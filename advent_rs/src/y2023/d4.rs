@@ -11,11 +11,11 @@ use std::str::FromStr;
 
 const DATA: &str = include_str!("../../data/2023/input4.txt");
 
-fn quiz1() -> u32 {
+pub(crate) fn quiz1() -> u32 {
     solve1(DATA)
 }
 
-fn quiz2() -> u32 {
+pub(crate) fn quiz2() -> u32 {
     solve2(DATA)
 }
 
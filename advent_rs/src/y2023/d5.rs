@@ -7,7 +7,7 @@ use nom::{IResult, Parser};
 
 const INPUT: &str = include_str!("../../data/2023/input5.txt");
 
-fn quiz1() -> u64 {
+pub(crate) fn quiz1() -> u64 {
     solve1(INPUT)
 }
 
@@ -27,35 +27,21 @@ fn solve1(data: &str) -> u64 {
         .unwrap()
 }
 
-fn quiz2() -> u64 {
+pub(crate) fn quiz2() -> u64 {
     solve2(INPUT)
 }
 
 fn solve2(data: &str) -> u64 {
     let (_, almanac) = almanac_parser(data).unwrap();
+    let composed = almanac.compose();
 
-    let mut seeds = vec![];
-    for i in 0..almanac.seeds.len() / 2 {
-        seeds.append(
-            &mut (almanac.seeds[i * 2]..almanac.seeds[i * 2] + almanac.seeds[i * 2 + 1])
-                .collect::<Vec<u64>>(),
-        );
-    }
-
-    let mut result = u64::MAX;
-
-    for seed in seeds {
-        let mapped = almanac
-            .maps_list
-            .iter()
-            .fold(seed, |acc, maps| maps.corresponds(acc));
-
-        if mapped < result {
-            result = mapped;
-        }
-    }
-
-    result
+    almanac
+        .seed_ranges()
+        .into_iter()
+        .flat_map(|seeds| composed.translate(seeds))
+        .map(|locations| locations.start)
+        .min()
+        .unwrap()
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -64,6 +50,9 @@ struct Almanac {
     maps_list: Vec<Maps>,
 }
 
+// half-open interval of ids
+type Interval = std::ops::Range<u64>;
+
 #[derive(Debug, Eq, PartialEq)]
 struct Maps {
     src_name: String,
@@ -72,13 +61,35 @@ struct Maps {
     // range_map: HashMap<u64, u64>,
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 struct Range {
     dest_start: u64,
     src_start: u64,
     length: u64,
 }
 
+impl Almanac {
+    fn seed_ranges(&self) -> Vec<Interval> {
+        self.seeds
+            .chunks(2)
+            .map(|pair| pair[0]..pair[0] + pair[1])
+            .collect()
+    }
+
+    // the whole seed-to-location chain as a single map
+    fn compose(&self) -> Maps {
+        let name = &self.maps_list[0].src_name;
+        let identity = Maps {
+            src_name: name.clone(),
+            dest_name: name.clone(),
+            ranges: vec![],
+        };
+        self.maps_list
+            .iter()
+            .fold(identity, |acc, maps| acc.then(maps))
+    }
+}
+
 impl Maps {
     fn corresponds(&self, input: u64) -> u64 {
         self.ranges
@@ -87,11 +98,96 @@ impl Maps {
             .map(|range| range.correspond(input))
             .unwrap_or_else(|| input)
     }
+
+    // the ranges plus identity ranges for the gaps between them,
+    // sorted and covering every id
+    fn pieces(&self) -> Vec<Range> {
+        let mut ranges = self.ranges.clone();
+        ranges.sort_by_key(|range| range.src_start);
+
+        let mut pieces = vec![];
+        let mut next = 0;
+        for range in ranges {
+            if next < range.src_start {
+                pieces.push(Range::identity(next..range.src_start));
+            }
+            pieces.push(range);
+            next = range.src_end();
+        }
+        if next < u64::MAX {
+            pieces.push(Range::identity(next..u64::MAX));
+        }
+        pieces
+    }
+
+    // split `input` at the range boundaries and map every part
+    fn translate(&self, input: Interval) -> Vec<Interval> {
+        self.pieces()
+            .iter()
+            .filter_map(|piece| {
+                let start = input.start.max(piece.src_start);
+                let end = input.end.min(piece.src_end());
+                (start < end)
+                    .then(|| piece.correspond(start)..piece.correspond(start) + end - start)
+            })
+            .collect()
+    }
+
+    // a single map doing `self` and then `next`
+    fn then(&self, next: &Maps) -> Maps {
+        let mut ranges = vec![];
+        for piece in self.pieces() {
+            let image = piece.dest_start..piece.dest_start + piece.length;
+            for other in next.pieces() {
+                let start = image.start.max(other.src_start);
+                let end = image.end.min(other.src_end());
+                if start >= end {
+                    continue;
+                }
+                let range = Range {
+                    dest_start: other.correspond(start),
+                    src_start: piece.src_start + (start - piece.dest_start),
+                    length: end - start,
+                };
+                if range.dest_start != range.src_start {
+                    ranges.push(range);
+                }
+            }
+        }
+        ranges.sort_by_key(|range| range.src_start);
+
+        Maps {
+            src_name: self.src_name.clone(),
+            dest_name: next.dest_name.clone(),
+            ranges,
+        }
+    }
+
+    // every input that maps to `output`, in ascending order
+    fn preimages(&self, output: u64) -> Vec<u64> {
+        self.pieces()
+            .iter()
+            .filter(|piece| (piece.dest_start..piece.dest_start + piece.length).contains(&output))
+            .map(|piece| piece.src_start + (output - piece.dest_start))
+            .collect()
+    }
 }
 
 impl Range {
+    fn identity(interval: Interval) -> Self {
+        Range {
+            dest_start: interval.start,
+            src_start: interval.start,
+            length: interval.end - interval.start,
+        }
+    }
+
+    fn src_end(&self) -> u64 {
+        self.src_start + self.length
+    }
+
     fn mapped(&self, input: u64) -> bool {
-        input >= self.src_start && input < self.src_start + self.length
+        input >= self.src_start && input < self.src_end()
     }
 
     fn correspond(&self, input: u64) -> u64 {
@@ -327,6 +423,49 @@ soil-to-fertilizer map:
         assert_eq!(almanac, expected)
     }
 
+    #[test]
+    fn test_translate() {
+        let (_, maps) = maps_parser(
+            "seed-to-soil map:
+50 98 2
+52 50 48",
+        )
+        .unwrap();
+
+        assert_eq!(maps.translate(79..93), vec![81..95]);
+        assert_eq!(maps.translate(40..60), vec![40..50, 52..62]);
+        assert_eq!(maps.translate(95..105), vec![97..100, 50..52, 100..105]);
+    }
+
+    #[test]
+    fn test_compose() {
+        let (_, almanac) = almanac_parser(EXAMPLE).unwrap();
+        let composed = almanac.compose();
+
+        assert_eq!(composed.src_name, "seed");
+        assert_eq!(composed.dest_name, "location");
+        for seed in 0..200 {
+            let expected = almanac
+                .maps_list
+                .iter()
+                .fold(seed, |acc, maps| maps.corresponds(acc));
+            assert_eq!(composed.corresponds(seed), expected);
+        }
+    }
+
+    #[test]
+    fn test_preimages() {
+        let (_, almanac) = almanac_parser(EXAMPLE).unwrap();
+        let composed = almanac.compose();
+
+        assert!(composed.preimages(46).contains(&82));
+        for location in 0..200 {
+            for seed in composed.preimages(location) {
+                assert_eq!(composed.corresponds(seed), location);
+            }
+        }
+    }
+
     #[test]
     fn test_solve1() {
         assert_eq!(solve1(EXAMPLE), 35);
@@ -10,6 +10,10 @@ use std::iter::zip;
 const INPUT: &str = "Time:        53     71     78     80
 Distance:   275   1181   1215   1524";
 
+pub(crate) fn quiz1() -> u64 {
+    solve1(INPUT)
+}
+
 fn solve1(data: &str) -> u64 {
     let (_, vs) = load1(data).unwrap();
 
@@ -65,6 +69,10 @@ fn load2(data: &str) -> IResult<&str, (u64, u64)> {
     Ok((data, (time, distances)))
 }
 
+pub(crate) fn quiz2() -> u64 {
+    solve2(INPUT)
+}
+
 fn solve2(data: &str) -> u64 {
     let (_, (time, distance)) = load2(data).unwrap();
     count_record(time, distance)
@@ -97,7 +105,7 @@ Distance:  9  40  200";
 
     #[test]
     fn test_quiz1() {
-        assert_eq!(solve1(INPUT), 449820);
+        assert_eq!(quiz1(), 449820);
     }
 
     #[test]
@@ -113,6 +121,6 @@ Distance:  9  40  200";
 
     #[test]
     fn test_quiz2() {
-        assert_eq!(solve2(INPUT), 42250895);
+        assert_eq!(quiz2(), 42250895);
     }
 }
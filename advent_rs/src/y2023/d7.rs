@@ -19,6 +19,10 @@ enum Type {
     Five,
 }
 
+pub(crate) fn quiz1() -> u32 {
+    solve1(INPUT)
+}
+
 fn solve1(data: &str) -> u32 {
     let (_, vs) = load(data).unwrap();
     let mut vs = vs
@@ -28,6 +32,10 @@ fn solve1(data: &str) -> u32 {
     total_winnings(vs)
 }
 
+pub(crate) fn quiz2() -> u32 {
+    solve2(INPUT)
+}
+
 fn solve2(data: &str) -> u32 {
     let (_, vs) = load(data).unwrap();
     let mut vs = vs
@@ -215,7 +223,7 @@ T55J5 684",
 
     #[test]
     fn test_quiz1() {
-        assert_eq!(solve1(INPUT), 253638586);
+        assert_eq!(quiz1(), 253638586);
     }
 
     #[test]
@@ -225,6 +233,6 @@ T55J5 684",
 
     #[test]
     fn test_quiz2() {
-        assert_eq!(solve2(INPUT), 253253225);
+        assert_eq!(quiz2(), 253253225);
     }
 }
@@ -61,6 +61,10 @@ fn network_parser(line: &str) -> IResult<&str, (String, LR)> {
     ).parse(line)
 }
 
+pub(crate) fn quiz1() -> u32 {
+    solve1(INPUT)
+}
+
 fn solve1(data: &str) -> u32 {
     let (_, docs) = load(data).unwrap();
     count_steps(&docs, "AAA", |s| s == "ZZZ")
@@ -93,6 +97,10 @@ fn count_steps<T: Num + Add<Output = T>>(
     steps
 }
 
+pub(crate) fn quiz2() -> u64 {
+    solve2(INPUT)
+}
+
 fn solve2(data: &str) -> u64 {
     let (_, docs) = load(data).unwrap();
 
@@ -145,7 +153,7 @@ ZZZ = (ZZZ, ZZZ)";
 
     #[test]
     fn quiz1_test() {
-        assert_eq!(solve1(INPUT), 12361);
+        assert_eq!(quiz1(), 12361);
     }
 
     const EX3: &str = "LR
@@ -166,7 +174,7 @@ XXX = (XXX, XXX)";
 
     #[test]
     fn quiz2_test() {
-        assert_eq!(solve2(INPUT), 18215611419223);
+        assert_eq!(quiz2(), 18215611419223);
     }
 
     #[test]
@@ -66,12 +66,20 @@ fn load(data: &str) -> IResult<&str, Vec<Vec<i32>>> {
     ).parse(data)
 }
 
+pub(crate) fn quiz1() -> i32 {
+    solve1(INPUT)
+}
+
 fn solve1(data: &str) -> i32 {
     let (_, nss) = load(data).unwrap();
 
     nss.into_iter().map(|vs| guess1(vs)).sum()
 }
 
+pub(crate) fn quiz2() -> i32 {
+    solve2(INPUT)
+}
+
 fn solve2(data: &str) -> i32 {
     let (_, nss) = load(data).unwrap();
 
@@ -120,11 +128,11 @@ mod tests {
 
     #[test]
     fn quiz1_test() {
-        assert_eq!(solve1(INPUT), 1904165718);
+        assert_eq!(quiz1(), 1904165718);
     }
 
     #[test]
     fn quiz2_test() {
-        assert_eq!(solve2(INPUT), 964);
+        assert_eq!(quiz2(), 964);
     }
 }
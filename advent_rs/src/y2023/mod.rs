@@ -14,3 +14,48 @@ mod d6;
 mod d7;
 mod d8;
 mod d9;
+
+use crate::debugger::Target;
+use crate::runner::Solver;
+
+pub(crate) fn solvers() -> Vec<Solver> {
+    vec![
+        Solver::new(2023, 1, 1, Some("54877"), || d1::quiz1().into()),
+        Solver::new(2023, 1, 2, Some("54100"), || d1::quiz2().into()),
+        Solver::new(2023, 2, 1, Some("2061"), || d2::quiz1().into()),
+        Solver::new(2023, 2, 2, Some("72596"), || d2::quiz2().into()),
+        Solver::new(2023, 3, 1, Some("520019"), || d3::quiz1().into()),
+        Solver::new(2023, 3, 2, Some("75519888"), || d3::quiz2().into()),
+        Solver::new(2023, 4, 1, Some("19855"), || d4::quiz1().into()),
+        Solver::new(2023, 4, 2, Some("10378710"), || d4::quiz2().into()),
+        Solver::new(2023, 5, 1, Some("484023871"), || d5::quiz1().into()),
+        Solver::new(2023, 5, 2, Some("46294175"), || d5::quiz2().into()),
+        Solver::new(2023, 6, 1, Some("449820"), || d6::quiz1().into()),
+        Solver::new(2023, 6, 2, Some("42250895"), || d6::quiz2().into()),
+        Solver::new(2023, 7, 1, Some("253638586"), || d7::quiz1().into()),
+        Solver::new(2023, 7, 2, Some("253253225"), || d7::quiz2().into()),
+        Solver::new(2023, 8, 1, Some("12361"), || d8::quiz1().into()),
+        Solver::new(2023, 8, 2, Some("18215611419223"), || d8::quiz2().into()),
+        Solver::new(2023, 9, 1, Some("1904165718"), || d9::quiz1().into()),
+        Solver::new(2023, 9, 2, Some("964"), || d9::quiz2().into()),
+        Solver::new(2023, 10, 1, Some("6820"), || d10::quiz1().into()),
+        Solver::new(2023, 10, 2, Some("337"), || d10::quiz2().into()),
+        Solver::new(2023, 11, 1, Some("10228230"), || d11::quiz1().into()),
+        Solver::new(2023, 11, 2, Some("447073334102"), || d11::quiz2().into()),
+        Solver::new(2023, 12, 1, Some("7361"), || d12::quiz1().into()),
+        Solver::new(2023, 12, 2, None, || d12::quiz2().into()),
+        Solver::new(2023, 13, 1, Some("35360"), || d13::quiz1().into()),
+        Solver::new(2023, 13, 2, Some("36755"), || d13::quiz2().into()),
+        Solver::new(2023, 14, 1, Some("105461"), || d14::quiz1().into()),
+        Solver::new(2023, 15, 1, Some("516469"), || d15::quiz1().into()),
+        Solver::new(2023, 15, 2, Some("221627"), || d15::quiz2().into()),
+        Solver::new(2023, 16, 1, Some("7307"), || d16::quiz1().into()),
+        Solver::new(2023, 16, 2, Some("7635"), || d16::quiz2().into()),
+    ]
+}
+
+pub(crate) fn debug_targets() -> Vec<Target> {
+    vec![
+        Target::new(2023, 16, d16::debug),
+    ]
+}
@@ -22,7 +22,7 @@ fn parse_data(input: &str) -> (Vec<i32>, Vec<i32>) {
         .unzip()
 }
 
-fn quiz1() -> i32 {
+pub(crate) fn quiz1() -> i32 {
     solve1(QUIZ_INPUT)
 }
 
@@ -47,7 +47,7 @@ fn solve2(input: &str) -> i32 {
     })
 }
 
-fn quiz2() -> i32 {
+pub(crate) fn quiz2() -> i32 {
     solve2(QUIZ_INPUT)
 }
 
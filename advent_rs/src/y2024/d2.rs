@@ -21,7 +21,7 @@ fn parse_data(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
     )).parse(input)
 }
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     solve1(QUIZ_INPUT)
 }
 
@@ -58,7 +58,7 @@ fn check2(report: &[i32]) -> bool {
         })
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     solve2(QUIZ_INPUT)
 }
 
@@ -32,7 +32,7 @@ fn parse_mul(input: &str) -> IResult<&str, (i32, i32)> {
     ).parse(input)
 }
 
-fn quiz1() -> i32 {
+pub(crate) fn quiz1() -> i32 {
     solve1(QUIZ_INPUT)
 }
 
@@ -65,7 +65,7 @@ fn solve2(input: &str) -> i32 {
     result
 }
 
-fn quiz2() -> i32 {
+pub(crate) fn quiz2() -> i32 {
     solve2(QUIZ_INPUT)
 }
 
@@ -33,7 +33,7 @@ fn solve1(input: &str) -> usize {
     count
 }
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     solve1(QUIZ_INPUT)
 }
 
@@ -117,7 +117,7 @@ fn solve2(input: &str) -> usize {
     count
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     solve2(QUIZ_INPUT)
 }
 
@@ -58,7 +58,7 @@ fn solve1(input: &str) -> i32 {
         .sum()
 }
 
-fn quiz1() -> i32 {
+pub(crate) fn quiz1() -> i32 {
     solve1(QUIZ_INPUT)
 }
 
@@ -89,7 +89,7 @@ fn solve2(input: &str) -> i32 {
     result
 }
 
-fn quiz2() -> i32 {
+pub(crate) fn quiz2() -> i32 {
     solve2(QUIZ_INPUT)
 }
 
@@ -1,12 +1,13 @@
 use std::collections::HashSet;
 use itertools::Itertools;
+use crate::debugger::{Debugger, Simulation};
 use nom::Parser;
 
 const QUIZ_INPUT: &str = include_str!("../../data/2024/input6.txt");
 
 type Pos = (i32, i32);
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 enum Dir { N, E, S, W }
 
 impl Dir {
@@ -49,7 +50,7 @@ impl LabMap {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 struct Guard {
     pos: Pos,
     dir: Dir,
@@ -131,7 +132,7 @@ fn solve1(input: &str) -> usize {
     marked.iter().filter(|x| **x == 'X' || **x == '^').count()
 }
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     solve1(QUIZ_INPUT)
 }
 
@@ -230,10 +231,69 @@ fn solve2(input: &str) -> usize {
     count
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     solve2(QUIZ_INPUT)
 }
 
+struct GuardWalk {
+    lab_map: LabMap,
+    guard: Guard,
+}
+
+impl GuardWalk {
+    fn new(lab_map: LabMap) -> Self {
+        let guard = Guard::new(lab_map.start, Dir::N);
+        GuardWalk { lab_map, guard }
+    }
+}
+
+impl Simulation for GuardWalk {
+    type State = Guard;
+    type Key = Guard;
+
+    fn state(&self) -> Guard {
+        self.guard
+    }
+
+    fn restore(&mut self, state: Guard) {
+        self.guard = state;
+    }
+
+    fn step(&mut self) -> bool {
+        match step_one(&self.guard, &self.lab_map) {
+            Some(next) => {
+                self.guard = next;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn render(&self) -> String {
+        let (rows, cols) = self.lab_map.size;
+        (0..rows).map(|r| {
+            (0..cols).map(|c| {
+                if (r, c) == self.guard.pos {
+                    match self.guard.dir { Dir::N => '^', Dir::E => '>', Dir::S => 'v', Dir::W => '<' }
+                } else if self.lab_map.is_obstacle((r, c)) {
+                    '#'
+                } else {
+                    '.'
+                }
+            }).collect::<String>()
+        }).join("\n")
+    }
+
+    fn key(&self) -> Guard {
+        self.guard
+    }
+}
+
+pub(crate) fn debug(input: Option<&str>) {
+    let lab_map = parse_data(input.unwrap_or(QUIZ_INPUT));
+    Debugger::new(GuardWalk::new(lab_map)).run_terminal();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -307,6 +367,22 @@ mod tests {
             None);
     }
 
+    #[test]
+    fn test_guard_walk() {
+        let mut dbg = Debugger::new(GuardWalk::new(parse_data(SAMPLE)));
+        assert!(dbg.run_until(|walk| walk.guard.dir == Dir::E));
+        assert_eq!(dbg.sim().render().lines().next(), Some("....#....."));
+        assert_eq!(dbg.sim().render().lines().nth(1), Some("....>....#"));
+        assert_eq!(dbg.find_cycle(10_000), None);
+        assert!(dbg.is_finished());
+
+        let lab_map = parse_data(SAMPLE).add_obstacle((6, 3));
+        let mut dbg = Debugger::new(GuardWalk::new(lab_map));
+        let (first, _) = dbg.jump_to_cycle(10_000).unwrap();
+        assert_eq!(dbg.steps(), first);
+        assert!(!dbg.is_finished());
+    }
+
     #[test]
     fn test_quiz2() {
         assert_eq!(quiz2(), 1723);
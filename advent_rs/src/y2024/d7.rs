@@ -16,11 +16,11 @@ use std::str::Chars;
 
 const QUIZ_INPUT: &str = include_str!("../../data/2024/input7.txt");
 
-fn quiz1() -> u64 {
+pub(crate) fn quiz1() -> u64 {
     solve1(QUIZ_INPUT)
 }
 
-fn quiz2() -> u64 {
+pub(crate) fn quiz2() -> u64 {
     solve2(QUIZ_INPUT)
 }
 
@@ -13,11 +13,11 @@ type Size = (u32, u32);
 type Vector = (Point, Point);
 type Pair = (Point, Point);
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     solve1(QUIZ_INPUT)
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     solve2(QUIZ_INPUT)
 }
 
@@ -5,4 +5,34 @@ mod d4;
 mod d5;
 mod d6;
 mod d7;
-mod d8;
\ No newline at end of file
+mod d8;
+
+use crate::debugger::Target;
+use crate::runner::Solver;
+
+pub(crate) fn solvers() -> Vec<Solver> {
+    vec![
+        Solver::new(2024, 1, 1, Some("1882714"), || d1::quiz1().into()),
+        Solver::new(2024, 1, 2, Some("19437052"), || d1::quiz2().into()),
+        Solver::new(2024, 2, 1, Some("218"), || d2::quiz1().into()),
+        Solver::new(2024, 2, 2, Some("290"), || d2::quiz2().into()),
+        Solver::new(2024, 3, 1, Some("161085926"), || d3::quiz1().into()),
+        Solver::new(2024, 3, 2, Some("82045421"), || d3::quiz2().into()),
+        Solver::new(2024, 4, 1, Some("2583"), || d4::quiz1().into()),
+        Solver::new(2024, 4, 2, Some("1978"), || d4::quiz2().into()),
+        Solver::new(2024, 5, 1, Some("4609"), || d5::quiz1().into()),
+        Solver::new(2024, 5, 2, Some("5723"), || d5::quiz2().into()),
+        Solver::new(2024, 6, 1, Some("4647"), || d6::quiz1().into()),
+        Solver::new(2024, 6, 2, Some("1723"), || d6::quiz2().into()),
+        Solver::new(2024, 7, 1, Some("20665830408335"), || d7::quiz1().into()),
+        Solver::new(2024, 7, 2, Some("354060705047464"), || d7::quiz2().into()),
+        Solver::new(2024, 8, 1, Some("214"), || d8::quiz1().into()),
+        Solver::new(2024, 8, 2, Some("809"), || d8::quiz2().into()),
+    ]
+}
+
+pub(crate) fn debug_targets() -> Vec<Target> {
+    vec![
+        Target::new(2024, 6, d6::debug),
+    ]
+}
@@ -18,7 +18,7 @@ fn parse_data(input: &str) -> IResult<&str, Vec<(char, i32)>> {
     .parse(input)
 }
 
-fn quiz1() -> i32 {
+pub(crate) fn quiz1() -> i32 {
     solve1(QUIZ_INPUT)
 }
 
@@ -81,7 +81,7 @@ fn solve2(input: &str) -> i32 {
     count
 }
 
-fn quiz2() -> i32 {
+pub(crate) fn quiz2() -> i32 {
     solve2(QUIZ_INPUT)
 }
 
@@ -20,22 +20,94 @@ fn parse_data(input: &str) -> IResult<&str, Vec<(u64, u64)>> {
     separated_list1(tag(","), parse_range).parse(input)
 }
 
-fn quiz1() -> u64 {
+pub(crate) fn quiz1() -> u128 {
     solve1(QUIZ_INPUT)
 }
 
-fn solve1(data: &str) -> u64 {
-    let mut sum = 0_u64;
+fn solve1(data: &str) -> u128 {
     let (_, vs) = parse_data(data).unwrap();
-    for (s, e) in vs {
-        for v in s..=e {
-            let s = format!("{v}");
-            if is_invalid1(&s) {
-                sum += v;
+    vs.iter().map(|&(s, e)| doubled(s, e).sum).sum()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Tally {
+    count: u128,
+    sum: u128,
+}
+
+const MAX_DIGITS: u32 = 20;
+
+fn pow10(n: u32) -> u128 {
+    10_u128.pow(n)
+}
+
+// numbers in s..=e with `digits` digits made of a `block`-digit pattern
+// repeated, i.e. pattern * 1 0..01 0..01
+fn repeated(digits: u32, block: u32, s: u64, e: u64) -> Tally {
+    assert!(digits.is_multiple_of(block));
+    let r = (0..digits / block).map(|i| pow10(i * block)).sum::<u128>();
+    let lo = u128::from(s).max(pow10(digits - 1));
+    let hi = u128::from(e).min(pow10(digits) - 1);
+    let p_min = lo.div_ceil(r).max(pow10(block - 1));
+    let p_max = (hi / r).min(pow10(block) - 1);
+    if lo > hi || p_min > p_max {
+        return Tally::default();
+    }
+    let count = p_max - p_min + 1;
+    Tally {
+        count,
+        sum: r * (p_min + p_max) * count / 2,
+    }
+}
+
+// numbers in s..=e that are some pattern repeated exactly twice
+fn doubled(s: u64, e: u64) -> Tally {
+    (1..=MAX_DIGITS / 2)
+        .map(|block| repeated(block * 2, block, s, e))
+        .fold(Tally::default(), |acc, t| Tally {
+            count: acc.count + t.count,
+            sum: acc.sum + t.sum,
+        })
+}
+
+fn mobius(mut n: u32) -> i128 {
+    let mut sign = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
             }
+            sign = -sign;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        -sign
+    } else {
+        sign
+    }
+}
+
+// numbers in s..=e that are some pattern repeated at least twice.
+// A number with period b also has every multiple of b as a period, so
+// summing -mobius(digits / b) over the proper divisors b of the length
+// counts each number exactly once
+fn periodic(s: u64, e: u64) -> Tally {
+    let (mut count, mut sum) = (0_i128, 0_i128);
+    for digits in 2..=MAX_DIGITS {
+        for block in (1..digits).filter(|b| digits.is_multiple_of(*b)) {
+            let weight = -mobius(digits / block);
+            let t = repeated(digits, block, s, e);
+            count += weight * t.count as i128;
+            sum += weight * t.sum as i128;
         }
     }
-    sum
+    Tally {
+        count: count as u128,
+        sum: sum as u128,
+    }
 }
 
 fn is_invalid1(num: &str) -> bool {
@@ -77,17 +149,12 @@ fn invalid_nums(s: u64, e: u64) -> Vec<u64> {
     result
 }
 
-fn solve2(data: &str) -> u64 {
-    let mut sum = 0_u64;
+fn solve2(data: &str) -> u128 {
     let (_, vs) = parse_data(data).unwrap();
-
-    for (s, e) in vs {
-        sum += invalid_nums(s, e).iter().sum::<u64>();
-    }
-    sum
+    vs.iter().map(|&(s, e)| periodic(s, e).sum).sum()
 }
 
-fn quiz2() -> u64 {
+pub(crate) fn quiz2() -> u128 {
     solve2(QUIZ_INPUT)
 }
 
@@ -155,6 +222,64 @@ mod tests {
         assert_eq!(invalid_nums(2121212118, 2121212124), vec![2121212121]);
     }
 
+    #[test]
+    fn test_repeated_count() {
+        assert_eq!(repeated(2, 1, 11, 22), Tally { count: 2, sum: 33 });
+        assert_eq!(
+            repeated(4, 2, 998, 1012),
+            Tally {
+                count: 1,
+                sum: 1010
+            }
+        );
+        assert_eq!(
+            repeated(6, 2, 0, u64::MAX),
+            Tally {
+                count: 90,
+                sum: 10101 * (10 + 99) * 90 / 2
+            }
+        );
+        assert_eq!(repeated(3, 1, 1000, 2000), Tally::default());
+    }
+
+    #[test]
+    fn test_mobius() {
+        let vs = (1..=10).map(mobius).collect::<Vec<_>>();
+        assert_eq!(vs, vec![1, -1, -1, 0, -1, 1, -1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_periodic() {
+        assert_eq!(periodic(95, 115), Tally { count: 2, sum: 210 });
+        assert_eq!(periodic(222220, 222224).sum, 222222);
+        // every doubled number up to 18 digits, then 1000000000..=1844674407 doubled
+        assert_eq!(doubled(0, u64::MAX).count, 1844674407);
+        assert_eq!(periodic(u64::MAX - 1000, u64::MAX), Tally::default());
+    }
+
+    #[test]
+    fn test_brute_force() {
+        let mut state = 0x2545f4914f6cdd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..200 {
+            let digits = (next() % 20) as u32;
+            let s = next() % 10_u64.pow(digits).max(1);
+            let e = s.saturating_add(next() % 5000);
+            let sum1 = (s..=e)
+                .filter(|v| is_invalid1(&format!("{}", v)))
+                .map(u128::from)
+                .sum::<u128>();
+            let sum2 = invalid_nums(s, e).into_iter().map(u128::from).sum::<u128>();
+            assert_eq!(doubled(s, e).sum, sum1, "{}-{}", s, e);
+            assert_eq!(periodic(s, e).sum, sum2, "{}-{}", s, e);
+        }
+    }
+
     #[test]
     fn test_nom() {
         let (input, d) = digit1::<_, (_, ErrorKind)>("11-22").unwrap();
@@ -187,6 +312,14 @@ mod tests {
         assert_eq!(solve1(SAMPLE), 1227775554);
     }
 
+    #[test]
+    fn test_solve_full_range() {
+        // the sums go past u64 long before the range does
+        let all = "0-18446744073709551615";
+        assert_eq!(solve1(all), 12509613850169742155792778978);
+        assert_eq!(solve2(all), 12510120345498176585131248687);
+    }
+
     #[test]
     fn run_quiz1() {
         assert_eq!(quiz1(), 15873079081);
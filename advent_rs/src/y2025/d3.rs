@@ -1,6 +1,6 @@
 const QUIZ_INPUT: &str = include_str!("../../data/2025/input3.txt");
 
-fn quiz1() -> u32 {
+pub(crate) fn quiz1() -> u32 {
     solve1(QUIZ_INPUT)
 }
 
@@ -16,7 +16,7 @@ fn solve2(data: &str) -> u64 {
         .sum()
 }
 
-fn quiz2() -> u64 {
+pub(crate) fn quiz2() -> u64 {
     solve2(QUIZ_INPUT)
 }
 
@@ -2,11 +2,11 @@ use std::ops::RangeInclusive;
 
 const QUIZ_INPUT: &str = include_str!("../../data/2025/input4.txt");
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     solve1(QUIZ_INPUT)
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     solve2(QUIZ_INPUT)
 }
 
@@ -8,11 +8,11 @@ use nom::{IResult, Parser};
 
 const QUIZ_INPUT: &str = include_str!("../../data/2025/input5.txt");
 
-fn quiz1() -> u64 {
+pub(crate) fn quiz1() -> u64 {
     solve1(QUIZ_INPUT)
 }
 
-fn quiz2() -> u64 {
+pub(crate) fn quiz2() -> u64 {
     solve2(QUIZ_INPUT)
 }
 
@@ -4,11 +4,11 @@ use std::collections::HashMap;
 
 const QUIZ_INPUT: &str = include_str!("../../data/2025/input7.txt");
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     solve1(QUIZ_INPUT)
 }
 
-fn quiz2() -> usize {
+pub(crate) fn quiz2() -> usize {
     solve2(QUIZ_INPUT)
 }
 
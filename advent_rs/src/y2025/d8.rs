@@ -1,3 +1,4 @@
+use crate::kdtree::KdTree;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
@@ -15,11 +16,11 @@ impl P {
 
 const QUIZ_INPUT: &str = include_str!("../../data/2025/input8.txt");
 
-fn quiz1() -> usize {
+pub(crate) fn quiz1() -> usize {
     solve1(QUIZ_INPUT, 1000)
 }
 
-fn quiz2() -> u64 {
+pub(crate) fn quiz2() -> u64 {
     solve2(QUIZ_INPUT)
 }
 
@@ -36,84 +37,96 @@ fn parse_data(input: &str) -> Vec<P> {
         .collect()
 }
 
-fn distances(pts: &[P]) -> Vec<(u64, P, P)> {
-    let mut result = vec![];
-    for i in 0..pts.len() - 1 {
-        let p1 = &pts[i];
-        for j in i + 1..pts.len() {
-            let p2 = &pts[j];
-            let d = p1.dist(p2);
-            result.push((d, *p1, *p2));
-        }
-    }
-    result.sort_by_key(|(d, _, _)| *d);
-    result
+// union-find over point indices
+struct Clusters {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
 }
 
-fn solve1(input: &str, count: usize) -> usize {
-    let pts = parse_data(input);
-    let mut circuits: Vec<HashSet<P>> = vec![];
+impl Clusters {
+    fn new(n: usize) -> Self {
+        Clusters {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            count: n,
+        }
+    }
 
-    for (i, (_, p1, p2)) in distances(&pts).into_iter().enumerate() {
-        if i >= count {
-            break;
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
         }
+        i
+    }
 
-        connect(&mut circuits, p1, p2);
+    // false when both were already in the same cluster
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (big, small) = if self.size[a] < self.size[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        self.count -= 1;
+        true
     }
 
-    circuits
-        .iter()
-        .map(|c| c.len())
-        .sorted()
-        .rev()
-        .take(3)
-        .product()
+    // sizes of every cluster, largest first
+    fn sizes(&mut self) -> Vec<usize> {
+        let roots = (0..self.parent.len())
+            .filter(|i| self.find(*i) == *i)
+            .collect::<Vec<_>>();
+        roots
+            .into_iter()
+            .map(|i| self.size[i])
+            .sorted()
+            .rev()
+            .collect()
+    }
 }
 
-fn solve2(input: &str) -> u64 {
-    let pts = parse_data(input);
-    let mut circuits: Vec<HashSet<P>> = vec![];
+fn tree(pts: &[P]) -> KdTree {
+    KdTree::new(
+        pts.iter()
+            .map(|p| [p.0 as i64, p.1 as i64, p.2 as i64])
+            .collect(),
+    )
+}
 
-    for (_, p1, p2) in distances(&pts) {
-        connect(&mut circuits, p1, p2);
-        if circuits.len() == 1 && circuits[0].len() == pts.len() {
-            return p1.0 * p2.0;
-        }
+// cluster sizes after joining the `edges` closest pairs
+fn cluster_sizes(pts: &[P], edges: usize) -> Vec<usize> {
+    let mut clusters = Clusters::new(pts.len());
+    for (_, i, j) in tree(pts).closest_pairs().take(edges) {
+        clusters.union(i, j);
     }
-
-    panic!("???")
+    clusters.sizes()
 }
 
-fn connect(circuits: &mut Vec<HashSet<P>>, p1: P, p2: P) -> bool {
-    let c1 = circuits
-        .iter()
-        .find_position(|set| set.contains(&p1))
-        .map(|(i, _)| i);
-    let c2 = circuits
-        .iter()
-        .find_position(|set| set.contains(&p2))
-        .map(|(i, _)| i);
-
-    if c1.is_none() && c2.is_none() {
-        circuits.push(HashSet::from([p1, p2]));
-    } else if c1.is_some() && c2.is_none() {
-        circuits[c1.unwrap()].insert(p2);
-    } else if c1.is_none() && c2.is_some() {
-        circuits[c2.unwrap()].insert(p1);
-    } else {
-        let p1 = c1.unwrap();
-        let p2 = c2.unwrap();
-        if p1 == p2 {
-            return false
-        }
+// the pair whose connection leaves a single cluster
+fn completing_edge(pts: &[P]) -> Option<(P, P)> {
+    let mut clusters = Clusters::new(pts.len());
+    tree(pts)
+        .closest_pairs()
+        .find(|(_, i, j)| clusters.union(*i, *j) && clusters.count == 1)
+        .map(|(_, i, j)| (pts[i], pts[j]))
+}
 
-        let (p1, p2) = if p1 < p2 { (p1, p2) } else { (p2, p1) };
+fn solve1(input: &str, count: usize) -> usize {
+    let pts = parse_data(input);
+    cluster_sizes(&pts, count).iter().take(3).product()
+}
 
-        let s2 = circuits.remove(p2);
-        circuits[p1].extend(s2);
-    }
-    true
+fn solve2(input: &str) -> u64 {
+    let pts = parse_data(input);
+    let (p1, p2) = completing_edge(&pts).unwrap();
+    p1.0 * p2.0
 }
 
 #[cfg(test)]
@@ -148,6 +161,20 @@ mod tests {
         assert_eq!(pts[1], P(57, 618, 57));
     }
 
+    #[test]
+    fn test_clusters() {
+        let pts = parse_data(SAMPLE);
+        assert_eq!(cluster_sizes(&pts, 0), vec![1; 20]);
+        let sizes = cluster_sizes(&pts, 10);
+        assert_eq!(sizes.len(), 11);
+        assert_eq!(sizes[..3], [5, 4, 2]);
+        assert_eq!(
+            completing_edge(&pts),
+            Some((P(216, 146, 977), P(117, 168, 530)))
+        );
+        assert_eq!(completing_edge(&pts[..1]), None);
+    }
+
     #[test]
     fn test_solve1() {
         assert_eq!(solve1(SAMPLE, 10), 40);
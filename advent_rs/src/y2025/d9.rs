@@ -1,3 +1,4 @@
+use crate::polygon::{Point, Polygon, Region};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 use std::ops::RangeInclusive;
@@ -10,6 +11,10 @@ impl P {
         (self.0.abs_diff(p.0) + 1) * (self.1.abs_diff(p.1) + 1)
     }
 
+    fn point(&self) -> Point {
+        (self.0 as i64, self.1 as i64)
+    }
+
     fn dist(&self, p: &P) -> u64 {
         let d1 = self.0.abs_diff(p.0);
         let d2 = self.1.abs_diff(p.1);
@@ -17,60 +22,13 @@ impl P {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
-struct S {
-    start: P,
-    end: P,
-    is_vertical: bool,
-    range: (u64, u64),
-}
-
-impl S {
-    fn new(start: P, end: P) -> Self {
-        let is_vertical = start.0 == end.0;
-        let range = if is_vertical {
-            (u64::min(start.1, end.1), u64::max(start.1, end.1))
-        } else {
-            (u64::min(start.0, end.0), u64::max(start.0, end.0))
-        };
-        Self {
-            start,
-            end,
-            is_vertical,
-            range,
-        }
-    }
-
-    fn contains(&self, p: &P) -> bool {
-        if self.is_vertical {
-            self.start.0 == p.0 && (self.range.0 <= p.1 && p.1 <= self.range.1)
-        } else {
-            self.start.1 == p.1 && (self.range.0 <= p.0 && p.0 <= self.range.1)
-        }
-    }
-
-    fn intersect(&self, pt: &P) -> u32 {
-        assert!(self.is_vertical);
-        let x = self.start.0;
-        if x > pt.0 || pt.1 < self.range.0 || pt.1 > self.range.1 {
-            0
-        } else {
-            if pt.1 == self.range.0 || pt.1 == self.range.1 {
-                1
-            } else {
-                2
-            }
-        }
-    }
-}
-
 const QUIZ_INPUT: &str = include_str!("../../data/2025/input9.txt");
 
-fn quiz1() -> u64 {
+pub(crate) fn quiz1() -> u64 {
     solve1(QUIZ_INPUT)
 }
 
-fn quiz2() -> u64 {
+pub(crate) fn quiz2() -> u64 {
     solve2(QUIZ_INPUT)
 }
 
@@ -87,15 +45,6 @@ fn parse_data(input: &str) -> Vec<P> {
         .collect()
 }
 
-fn segments(pts: &[P]) -> Vec<S> {
-    let mut result = vec![];
-    for i in 1..pts.len() {
-        result.push(S::new(pts[i - 1], pts[i]));
-    }
-    result.push(S::new(pts[pts.len() - 1], pts[0]));
-    result
-}
-
 fn areas(pts: &[P]) -> Vec<(u64, P, P)> {
     let mut result = vec![];
     for i in 0..pts.len() - 1 {
@@ -123,54 +72,15 @@ fn solve1(input: &str) -> u64 {
 
 fn solve2(input: &str) -> u64 {
     let pts = parse_data(input);
-    let segments = segments(&pts);
-    let areas = areas(&pts);
+    let polygon = Polygon::new(pts.iter().map(P::point).collect());
+    let region = Region::new(&polygon);
 
-    areas
+    areas(&pts)
         .iter()
-        .sorted_by_key(|(a, _, _)| *a)
-        .rev()
-        .find_or_first(|(area, p1, p2)| {
-            let x1 = u64::min(p1.0, p2.0);
-            let x2 = u64::max(p1.0, p2.0);
-            let y1 = u64::min(p1.1, p2.1);
-            let y2 = u64::max(p1.1, p2.1);
-
-            for x in x1..=x2 {
-                for y in y1..=y2 {
-                    let c = contains(&segments, &P(x, y));
-                    if !c {
-                        return false;
-                    }
-                }
-            }
-
-            true
-            // let c1 = P(p1.0, p2.1);
-            // let c2 = P(p2.0, p1.1);
-            // let cn1 = contains(&segments, &c1);
-            // let cn2 = contains(&segments, &c2);
-            // println!("{:?},{:?}={area} -> {:?}({cn1}),{:?}({cn2})", p1, p2, c1, c2);
-            // cn1 && cn2
-        })
+        .filter(|(_, p1, p2)| region.contains_rect(p1.point(), p2.point()))
+        .map(|(area, _, _)| *area)
+        .max()
         .unwrap()
-        .0
-}
-
-fn contains(segments: &[S], p: &P) -> bool {
-    if let Some(_) = segments.iter().find(|&s|s.contains(p)) {
-        true
-    } else {
-        let sum: u32 = segments.iter()
-            .filter(|&s| s.is_vertical)
-            .map(|&s| {
-                let v = s.intersect(p);
-                // if v != 0 {println!("{p:?} -> {s:?}, {v}");}
-                v
-            })
-            .sum::<u32>();
-        (sum / 2) % 2 == 1
-    }
 }
 
 #[cfg(test)]
@@ -199,48 +109,6 @@ mod tests {
         assert_eq!(P(2, 5).area(&P(11, 1)), 50);
     }
 
-    #[test]
-    fn test_segment() {
-        let s_v = S::new(P(11,1), P(11,7));
-        assert!(s_v.is_vertical);
-
-        assert!(!s_v.contains(&P(11, 0)));
-        assert!(s_v.contains(&P(11, 1)));
-        assert!(s_v.contains(&P(11, 4)));
-        assert!(s_v.contains(&P(11, 7)));
-        assert!(!s_v.contains(&P(11, 8)));
-        assert!(!s_v.contains(&P(10, 4)));
-
-        let s_h = S::new(P(7,1), P(11,1));
-        assert!(!s_h.is_vertical);
-
-        assert!(!s_h.contains(&P(6, 1)));
-        assert!(s_h.contains(&P(7, 1)));
-        assert!(s_h.contains(&P(8, 1)));
-        assert!(s_h.contains(&P(11, 1)));
-        assert!(!s_h.contains(&P(12, 1)));
-        assert!(!s_h.contains(&P(8, 2)));
-    }
-
-    #[test]
-    fn test_intersect() {
-        assert_eq!(S::new(P(2, 5), P(2, 2)).intersect(&P(9, 3)), 2);
-        assert_eq!(S::new(P(2, 5), P(2, 3)).intersect(&P(9, 3)), 1);
-        assert_eq!(S::new(P(2, 5), P(2, 4)).intersect(&P(9, 3)), 0);
-
-        // let s_h = S::new(P(7,1), P(11,1));
-        // let s_v = S::new(P(11,1), P(11,7));
-        // assert!(s_v.intersect(&s_h));
-        //
-        // let s_h = S::new(P(7,1), P(10,1));
-        // let s_v = S::new(P(11,1), P(11,7));
-        // assert!(!s_v.intersect(&s_h));
-        //
-        // let s_h = S::new(P(12,2), P(7,2));
-        // let s_v = S::new(P(11,1), P(11,7));
-        // assert!(s_v.intersect(&s_h));
-    }
-
     #[test]
     fn test_solve1() {
         assert_eq!(solve1(SAMPLE), 50);
@@ -7,3 +7,28 @@ mod d6;
 mod d7;
 mod d8;
 mod d9;
+
+use crate::runner::Solver;
+
+pub(crate) fn solvers() -> Vec<Solver> {
+    vec![
+        Solver::new(2025, 1, 1, Some("1145"), || d1::quiz1().into()),
+        Solver::new(2025, 1, 2, Some("6561"), || d1::quiz2().into()),
+        Solver::new(2025, 2, 1, Some("15873079081"), || d2::quiz1().into()),
+        Solver::new(2025, 2, 2, Some("22617871034"), || d2::quiz2().into()),
+        Solver::new(2025, 3, 1, Some("17332"), || d3::quiz1().into()),
+        Solver::new(2025, 3, 2, Some("172516781546707"), || d3::quiz2().into()),
+        Solver::new(2025, 4, 1, Some("1449"), || d4::quiz1().into()),
+        Solver::new(2025, 4, 2, Some("8746"), || d4::quiz2().into()),
+        Solver::new(2025, 5, 1, Some("640"), || d5::quiz1().into()),
+        Solver::new(2025, 5, 2, Some("365804144481581"), || d5::quiz2().into()),
+        Solver::new(2025, 6, 1, Some("4583860641327"), || d6::quiz1().into()),
+        Solver::new(2025, 6, 2, Some("11602774058280"), || d6::quiz2().into()),
+        Solver::new(2025, 7, 1, Some("1550"), || d7::quiz1().into()),
+        Solver::new(2025, 7, 2, Some("9897897326778"), || d7::quiz2().into()),
+        Solver::new(2025, 8, 1, Some("66640"), || d8::quiz1().into()),
+        Solver::new(2025, 8, 2, Some("78894156"), || d8::quiz2().into()),
+        Solver::new(2025, 9, 1, Some("4748769124"), || d9::quiz1().into()),
+        Solver::new(2025, 9, 2, None, || d9::quiz2().into()),
+    ]
+}